@@ -41,6 +41,7 @@ fn build_scene(particle_count: usize) -> (Physics, TerrainBody, Vec<RigidBodyHan
         length: TERRAIN_LENGTH,
         density: 10.0,
         shape: config::WorldShape::Cylinder,
+        ..Default::default()
     };
     let terrain = physics.create_terrain(&cfg, alpha, TERRAIN_WIDTH, TERRAIN_HEIGHT);
 
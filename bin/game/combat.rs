@@ -0,0 +1,140 @@
+//! Combat glue: the editable ground that craters are dug into.
+//!
+//! The ground exists in four copies — the height map, its RGBA image on the
+//! GPU, the TIN chunks the renderer draws, and the trimesh chunks physics
+//! collides with. A crater edits the first and refreshes only the chunks
+//! and texture rows it touched in the others.
+
+use vandals_and_heroes::{Impact, Physics, Render, Terrain, TerrainBody, tin};
+
+/// A bowl dug into the ground; see `TerrainMesh::dig_crater`.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Crater {
+    pub center: [f32; 3],
+    pub radius: f32,
+    pub depth: f32,
+}
+
+pub struct Ground {
+    pub mesh: tin::TerrainMesh,
+    /// Height map (the map's alpha channel), edited in place by craters.
+    pub alpha: Vec<u8>,
+    /// The full map image as uploaded; its alpha follows `alpha`.
+    pub rgba: Vec<u8>,
+    pub extent: blade_graphics::Extent,
+    /// The height map as loaded, for rebuilding the ground from scratch.
+    pub pristine: Vec<u8>,
+    /// Fit quality the mesh was built at.
+    pub quality: f32,
+    /// Every crater dug so far, in order. Replaying them onto `pristine`
+    /// reproduces `alpha` and `mesh` exactly.
+    pub craters: Vec<Crater>,
+}
+
+impl Ground {
+    /// Dig the crater of `impact` and bring physics, the GPU chunks and the
+    /// terrain texture up to date with it.
+    pub fn dig(
+        &mut self,
+        impact: &Impact,
+        physics: &mut Physics,
+        terrain_body: &mut TerrainBody,
+        terrain: &mut Terrain,
+        render: &mut Render,
+    ) {
+        profiling::scope!("Ground::dig");
+        let p = impact.point;
+        let crater = Crater {
+            center: [p.x, p.y, p.z],
+            radius: impact.crater_radius,
+            depth: impact.crater_depth,
+        };
+        let deformation = self.carve(crater);
+        if deformation.chunks.is_empty() {
+            return;
+        }
+        physics.rebuild_terrain_chunks(terrain_body, &self.mesh, &deformation.chunks);
+        self.upload(&deformation.chunks, deformation.rows, terrain, render);
+        log::info!(
+            "crater at ({:.1}, {:.1}, {:.1}): {} chunks rebuilt",
+            p.x,
+            p.y,
+            p.z,
+            deformation.chunks.len()
+        );
+    }
+
+    /// Bring the CPU and GPU ground to the state `craters` describes, for a
+    /// loaded game. Physics is not touched: the loaded world carries its
+    /// own terrain colliders.
+    pub fn restore(&mut self, craters: &[Crater], terrain: &mut Terrain, render: &mut Render) {
+        profiling::scope!("Ground::restore");
+        if let Some(newer) = craters.strip_prefix(self.craters.as_slice()) {
+            // The save continues this session: dig only what is missing.
+            for &crater in newer {
+                let deformation = self.carve(crater);
+                self.upload(&deformation.chunks, deformation.rows, terrain, render);
+            }
+            return;
+        }
+        // Craters we dug are not in the save; start over from the map.
+        self.alpha.copy_from_slice(&self.pristine);
+        self.mesh = tin::build(
+            &self.alpha,
+            self.extent.width,
+            self.extent.height,
+            &terrain.config,
+            self.quality,
+        );
+        self.craters.clear();
+        for &crater in craters {
+            self.carve(crater);
+        }
+        for (texel, &alpha) in self.rgba.chunks_exact_mut(4).zip(self.alpha.iter()) {
+            texel[3] = alpha;
+        }
+        let chunks: Vec<usize> = (0..self.mesh.chunks.len()).collect();
+        let rows = vec![0..self.extent.height];
+        self.upload(&chunks, rows, terrain, render);
+        log::info!("ground rebuilt with {} craters", craters.len());
+    }
+
+    /// The CPU half of a crater: the height map, the image and the mesh.
+    fn carve(&mut self, crater: Crater) -> tin::Deformation {
+        let deformation =
+            self.mesh
+                .dig_crater(&mut self.alpha, crater.center, crater.radius, crater.depth);
+        let width = self.extent.width as usize;
+        for rows in deformation.rows.iter() {
+            for y in rows.start as usize..rows.end as usize {
+                for x in y * width..(y + 1) * width {
+                    self.rgba[x * 4 + 3] = self.alpha[x];
+                }
+            }
+        }
+        self.craters.push(crater);
+        deformation
+    }
+
+    fn upload(
+        &self,
+        chunks: &[usize],
+        rows: Vec<std::ops::Range<u32>>,
+        terrain: &mut Terrain,
+        render: &mut Render,
+    ) {
+        if chunks.is_empty() {
+            return;
+        }
+        // The encoder is shared with the frame; let the last frame finish
+        // before recording the uploads, as the initial load does.
+        render.wait_for_gpu();
+        let mut loader = render.start_loading();
+        loader.reload_terrain_chunks(&self.mesh, chunks, &mut terrain.chunks);
+        for rows in rows {
+            loader.update_terrain_rows(&terrain.texture, self.extent, &self.rgba, rows);
+        }
+        let submission = loader.finish();
+        render.accept_submission(submission);
+    }
+}
@@ -0,0 +1,108 @@
+//! Client mode: the game as one player of a `server` session, started with
+//! `--connect HOST:PORT`. The local car is still simulated here — that is
+//! the prediction `net::Client` reconciles with the server — while the
+//! other players' cars are drawn where the server had them a few ticks ago.
+//!
+//! Only driving is networked: weapons, jumps, rolls and save games stay
+//! off while connected, as the server would undo their effects.
+
+use std::net::ToSocketAddrs as _;
+use vandals_and_heroes::{DriveInput, Kinematics, ModelInstance, Physics, Vehicle, net};
+
+pub struct Multiplayer {
+    client: net::Client,
+    /// Render instances of the other players' cars, rebuilt every tick.
+    pub instances: Vec<ModelInstance>,
+}
+
+fn isometry(kinematics: &Kinematics) -> nalgebra::Isometry3<f32> {
+    let [x, y, z, w] = kinematics.rotation;
+    nalgebra::Isometry3::from_parts(
+        nalgebra::Vector3::from(kinematics.translation).into(),
+        nalgebra::UnitQuaternion::new_normalize(nalgebra::Quaternion::new(w, x, y, z)),
+    )
+}
+
+impl Multiplayer {
+    /// Connect to the server named by `--connect`, if the command line has
+    /// one. Failures are logged and leave the game offline.
+    pub fn from_args() -> Option<Self> {
+        let mut args = std::env::args().skip(1);
+        let address = loop {
+            if args.next()? == "--connect" {
+                break args.next()?;
+            }
+        };
+        let server = match address.to_socket_addrs().map(|mut addrs| addrs.next()) {
+            Ok(Some(server)) => server,
+            Ok(None) => {
+                log::error!("{address} resolves to nothing");
+                return None;
+            }
+            Err(e) => {
+                log::error!("Unable to resolve {address}: {e}");
+                return None;
+            }
+        };
+        match net::Client::connect(server) {
+            Ok(client) => {
+                log::info!("Connecting to {server}");
+                Some(Self {
+                    client,
+                    instances: Vec::new(),
+                })
+            }
+            Err(e) => {
+                log::error!("Unable to connect to {server}: {e}");
+                None
+            }
+        }
+    }
+
+    /// Before the local step: take in the server's news, pull the local
+    /// car toward the server's and send this tick's controls.
+    pub fn pre_step(&mut self, physics: &mut Physics, vehicle: &Vehicle, input: DriveInput) {
+        if let Err(e) = self.client.poll() {
+            log::error!("network: {e}");
+        }
+        self.client.reconcile(physics, vehicle);
+        if let Err(e) = self.client.send_input(input) {
+            log::error!("network: {e}");
+        }
+    }
+
+    /// After the local step: remember the prediction and move the other
+    /// cars, drawn with the local car's models.
+    pub fn post_step(&mut self, physics: &Physics, car: &super::Object) {
+        self.client.record(physics, &car.vehicle);
+        self.instances.clear();
+        for (_, bodies) in self.client.remote_cars() {
+            let Some(chassis) = bodies.first() else {
+                continue;
+            };
+            self.instances.push(ModelInstance {
+                model: car.chassis_instance.model.clone(),
+                transform: isometry(chassis),
+                geometry_filter: None,
+                casts_shadow: true,
+            });
+            // Wheel bodies follow the chassis in `Vehicle::bodies` order.
+            for (template, wheel) in car.wheel_instances.iter().zip(&bodies[1..]) {
+                if let Some(template) = template.as_ref() {
+                    self.instances.push(ModelInstance {
+                        model: template.model.clone(),
+                        transform: isometry(wheel),
+                        geometry_filter: None,
+                        casts_shadow: true,
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn disconnect(&self) {
+        if let Err(e) = self.client.disconnect() {
+            log::warn!("Unable to say goodbye to the server: {e}");
+        }
+    }
+}
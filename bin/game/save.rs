@@ -0,0 +1,47 @@
+//! Save games: the whole session in one bincode file. `Physics` carries the
+//! simulation itself — every body, collider and joint, contact caches
+//! included — so a loaded game steps on exactly as the saved one would
+//! have. The rest is what lives outside physics: the crater list the ground
+//! is rebuilt from, the car's handles and weapons, health, respawn timers,
+//! snow bookkeeping, the mode and the camera.
+
+use std::{fs, io, path::Path};
+use vandals_and_heroes::{Damage, Physics, Recovery, TerrainBody, Vehicle};
+
+/// Where F5 writes and F9 reads.
+pub const QUICKSAVE_PATH: &str = "quicksave.bin";
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SaveGame {
+    /// Map and car the game was started with. The ground and the car's
+    /// render state come from those files, so a save only loads into a
+    /// session with the same pair.
+    pub map: String,
+    pub car: String,
+    pub physics: Physics,
+    pub terrain_body: TerrainBody,
+    pub craters: Vec<super::combat::Crater>,
+    pub vehicle: Vehicle,
+    pub damage: Damage,
+    pub recovery: Recovery,
+    pub snow: super::snow::SnowState,
+    pub mode: super::Mode,
+    pub camera_pos: [f32; 3],
+    /// Camera rotation quaternion as `(i, j, k, w)`.
+    pub camera_rot: [f32; 4],
+}
+
+pub fn write(path: &Path, save: &SaveGame) -> io::Result<()> {
+    let file = fs::File::create(path)?;
+    let mut writer = io::BufWriter::new(file);
+    bincode::serde::encode_into_std_write(save, &mut writer, bincode::config::standard())
+        .map_err(io::Error::other)?;
+    io::Write::flush(&mut writer)
+}
+
+pub fn read(path: &Path) -> io::Result<SaveGame> {
+    let file = fs::File::open(path)?;
+    let mut reader = io::BufReader::new(file);
+    bincode::serde::decode_from_std_read(&mut reader, bincode::config::standard())
+        .map_err(io::Error::other)
+}
@@ -37,6 +37,10 @@ const PARTICLE_LINEAR_DAMPING: f32 = 0.6;
 /// portion both stay visible — 6 s minimum, 40 s maximum.
 const LIFETIME_MIN_TICKS: u32 = 360;
 const LIFETIME_MAX_TICKS: u32 = 2400;
+/// Drag coefficient of a sphere at the Reynolds numbers snow sees. Combined
+/// with the particle cross-section it lets the map's wind push the flakes
+/// around, on top of the linear damping above.
+const PARTICLE_DRAG_COEFFICIENT: f32 = 0.47;
 /// How far above the outer radius we spawn fresh particles. Just outside the
 /// shell so they drop in from a slight height.
 const SPAWN_RADIUS_OFFSET: f32 = 0.05;
@@ -63,6 +67,17 @@ pub struct Snow {
     debug_tick: u32,
 }
 
+/// The part of `Snow` a save game has to carry; the particles themselves
+/// are bodies in the saved `Physics`.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SnowState {
+    bodies: Vec<rapier3d::dynamics::RigidBodyHandle>,
+    age_ticks: Vec<u32>,
+    lifetime_ticks: Vec<u32>,
+    rng_state: u64,
+    debug_tick: u32,
+}
+
 /// How far (in metres) cylinder-mode snow spawns either side of the car's
 /// initial z. 30 m corresponds to roughly twice the car's clip-near + chase
 /// camera distance, so spawning here keeps a comfortable density right where
@@ -110,6 +125,8 @@ impl Snow {
             n
         };
         let model = Arc::new(loader.load_model(&snowflake_mesh_desc(PARTICLE_RADIUS)));
+        let drag_area =
+            PARTICLE_DRAG_COEFFICIENT * std::f32::consts::PI * PARTICLE_RADIUS * PARTICLE_RADIUS;
         let mut snow = Self {
             model,
             instances: Vec::with_capacity(count),
@@ -138,6 +155,7 @@ impl Snow {
             let PhysicsBodyHandle {
                 rigid_body_handle, ..
             } = physics.add_rigid_body(body, vec![collider]);
+            physics.set_drag_area(rigid_body_handle, drag_area);
             snow.bodies.push(rigid_body_handle);
             snow.instances.push(ModelInstance {
                 model: snow.model.clone(),
@@ -253,6 +271,36 @@ impl Snow {
         self.model.free(ctx);
     }
 
+    pub fn save(&self) -> SnowState {
+        SnowState {
+            bodies: self.bodies.clone(),
+            age_ticks: self.age_ticks.clone(),
+            lifetime_ticks: self.lifetime_ticks.clone(),
+            rng_state: self.rng_state,
+            debug_tick: self.debug_tick,
+        }
+    }
+
+    /// Take over the particles of a loaded game, whose bodies already live
+    /// in `physics`.
+    pub fn restore(&mut self, state: SnowState, physics: &Physics) {
+        self.instances = state
+            .bodies
+            .iter()
+            .map(|&body| ModelInstance {
+                model: self.model.clone(),
+                transform: physics.get_transform(body),
+                geometry_filter: None,
+                casts_shadow: false,
+            })
+            .collect();
+        self.bodies = state.bodies;
+        self.age_ticks = state.age_ticks;
+        self.lifetime_ticks = state.lifetime_ticks;
+        self.rng_state = state.rng_state;
+        self.debug_tick = state.debug_tick;
+    }
+
     /// Pick a random spawn point on the outer shell.
     fn sample_spawn(&mut self) -> (rapier3d::math::Vec3, rapier3d::math::Rotation) {
         let theta = self.rand_f32() * std::f32::consts::TAU;
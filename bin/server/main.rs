@@ -0,0 +1,72 @@
+//! Headless multiplayer server: owns the world's physics and steps it at
+//! the game's 60 Hz, driving every connected player's car with the inputs
+//! they send. Map and car come from `data/config.ron`, like the game's.
+//!
+//!     cargo run --release --bin server -- --bind 0.0.0.0:7878
+//!
+//! Players join with `cargo run --release -- --connect HOST:7878`.
+
+use std::{net::UdpSocket, path, thread, time};
+use vandals_and_heroes::{Physics, assets, config, load_world, net, vehicle};
+
+/// Fixed physics timestep; matches the game's.
+const PHYSICS_DT: time::Duration = time::Duration::from_nanos(16_666_667);
+
+fn parse_args() -> String {
+    let mut args = std::env::args().skip(1);
+    let mut bind = format!("0.0.0.0:{}", net::DEFAULT_PORT);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bind" => bind = args.next().expect("--bind needs an address"),
+            other => panic!("Unknown argument {other}"),
+        }
+    }
+    bind
+}
+
+fn main() {
+    env_logger::init();
+    let bind = parse_args();
+
+    let config: config::Config =
+        ron::de::from_bytes(&assets::read(path::Path::new("data/config.ron")))
+            .expect("Unable to parse the main config");
+
+    let world = load_world(&config);
+    let mut physics = Physics::default();
+    let terrain = physics.create_terrain_mesh(&world.map, &world.mesh);
+
+    let arena = net::Arena {
+        spawn_radius: vehicle::spawn_radius(
+            &world.map,
+            &world.alpha,
+            world.extent.width,
+            world.extent.height,
+        ),
+        map: world.map,
+        car: world.car,
+        car_model: world.car_model,
+        recovery: config.recovery,
+    };
+    let socket = UdpSocket::bind(&bind).unwrap_or_else(|e| panic!("Unable to bind {bind}: {e}"));
+    let mut server =
+        net::Server::new(socket, physics, terrain, arena).expect("Unable to set up the socket");
+    log::info!("Serving on {}", server.local_addr().unwrap());
+
+    let mut next_tick = time::Instant::now();
+    loop {
+        if let Err(e) = server.tick() {
+            log::error!("Network error: {e}");
+        }
+        next_tick += PHYSICS_DT;
+        let now = time::Instant::now();
+        if next_tick > now {
+            thread::sleep(next_tick - now);
+        } else if now - next_tick > PHYSICS_DT * 10 {
+            // Fell far behind (a stall, a suspended process): resume from
+            // now rather than racing through the backlog.
+            log::warn!("Server fell {:?} behind", now - next_tick);
+            next_tick = now;
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Headless simulation: loads the map and car from `data/config.ron` exactly
+//! as the game does, drives the car through a scripted sequence of inputs
+//! at the game's fixed timestep and records every tick, all without a
+//! window or GPU. The script is either a RON `Timeline` (a `.ron` file) or
+//! the shorthand described in `script.rs`.
+//!
+//!     cargo run --release --bin sim -- drive.txt --steps 600 --record sim.ron
+//!
+//! `--steps` defaults to the script's own length, and is required for a
+//! looping timeline; past the end the car coasts with nothing held. The
+//! `Recorder` log goes to `--record` (default `sim.ron`), and a summary of
+//! the run is printed as RON. The car spawns a little above the ground, as
+//! in the game, so scripts usually open with `idle for 1s` to let it settle.
+
+mod script;
+
+use std::path;
+use vandals_and_heroes::{
+    Physics, Recorder, TerrainBody, Timeline, TimelinePlayer, Vehicle, assets, config, load_world,
+    timeline::Event, vehicle,
+};
+
+/// Fixed physics timestep; matches the game's.
+const PHYSICS_DT: f32 = 1.0 / 60.0;
+
+struct Args {
+    script: path::PathBuf,
+    steps: Option<usize>,
+    record: path::PathBuf,
+}
+
+fn parse_args() -> Args {
+    let mut args = std::env::args().skip(1);
+    let mut script = None;
+    let mut steps = None;
+    let mut record = path::PathBuf::from("sim.ron");
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--steps" => {
+                let value = args.next().expect("--steps needs a count");
+                steps = Some(value.parse().expect("--steps needs a count"));
+            }
+            "--record" => record = args.next().expect("--record needs a path").into(),
+            other if script.is_none() && !other.starts_with("--") => script = Some(other.into()),
+            other => panic!("Unknown argument {other}"),
+        }
+    }
+    Args {
+        script: script.expect("Usage: sim SCRIPT [--steps N] [--record PATH]"),
+        steps,
+        record,
+    }
+}
+
+/// What the run amounted to, printed at the end.
+#[derive(Debug, Default, serde::Serialize)]
+struct Summary {
+    ticks: usize,
+    /// Simulated seconds.
+    time: f32,
+    /// Length of the path the chassis took (m).
+    distance: f32,
+    /// Straight-line distance from the spawn to where the chassis ended (m).
+    displacement: f32,
+    /// Largest angle between the chassis up and the world up (degrees).
+    max_tilt: f32,
+    /// Seconds with neither the wheels nor the chassis touching the ground.
+    airtime: f32,
+    jumps: usize,
+}
+
+fn record(recorder: &mut Recorder, physics: &Physics, vehicle: &Vehicle) {
+    let mut bodies = vec![("car".to_string(), vehicle.chassis)];
+    for (i, w) in vehicle.wheels.iter().enumerate() {
+        bodies.push((format!("wheel{}", i), w.rigid_body));
+    }
+    recorder.record(
+        physics.last_time(),
+        physics,
+        bodies.iter().map(|(n, h)| (n.as_str(), *h)),
+    );
+}
+
+fn tilt(physics: &Physics, terrain: &TerrainBody, vehicle: &Vehicle) -> f32 {
+    let Some(pose) = physics.body_pose(vehicle.chassis) else {
+        return 0.0;
+    };
+    let up = pose.rotation * rapier3d::math::Vec3::Y;
+    up.angle_between(terrain.up(pose.translation)).to_degrees()
+}
+
+fn main() {
+    env_logger::init();
+    let args = parse_args();
+    let source = assets::read(&args.script);
+    let timeline: Timeline = if args.script.extension().is_some_and(|ext| ext == "ron") {
+        ron::de::from_bytes(&source).unwrap_or_else(|e| panic!("{}: {e}", args.script.display()))
+    } else {
+        let source = String::from_utf8(source.into_owned()).expect("The script is not UTF-8");
+        script::parse(&source).unwrap_or_else(|e| panic!("{}: {e}", args.script.display()))
+    };
+    assert!(
+        !timeline.looping || args.steps.is_some(),
+        "A looping timeline needs --steps"
+    );
+    let mut player = TimelinePlayer::new(timeline, PHYSICS_DT);
+
+    let config: config::Config =
+        ron::de::from_bytes(&assets::read(path::Path::new("data/config.ron")))
+            .expect("Unable to parse the main config");
+
+    let world = load_world(&config);
+    let map = world.map;
+    let mut physics = Physics::default();
+    let terrain = physics.create_terrain_mesh(&map, &world.mesh);
+    let spawn_radius =
+        vehicle::spawn_radius(&map, &world.alpha, world.extent.width, world.extent.height);
+    let spawn_pose = vehicle::spawn_pose(&map, spawn_radius, vehicle::spawn_axial(&map));
+    let vehicle = Vehicle::spawn(
+        &mut physics,
+        &world.car,
+        &world.car_model,
+        spawn_pose.into(),
+    );
+
+    let mut recorder = Recorder::new(&config::Recorder {
+        path: args.record,
+        format: config::RecorderFormat::Ron,
+    });
+    let start = physics.get_transform(vehicle.chassis).translation.vector;
+    let mut last = start;
+    let mut summary = Summary::default();
+    let mut tick = 0;
+    while args.steps.is_none_or(|steps| tick < steps) {
+        let next = match player.next() {
+            Some(next) => next,
+            // Past the end of the script: coast to the requested count.
+            None if args.steps.is_some() => Default::default(),
+            None => break,
+        };
+        for event in next.events {
+            match event {
+                Event::Jump { charge } => match vehicle.jump(&mut physics, &terrain, charge) {
+                    Some(velocity) => {
+                        log::info!("tick {tick}: jump, v={velocity:.2} m/s");
+                        summary.jumps += 1;
+                    }
+                    None => log::info!("tick {tick}: jump while airborne, ignored"),
+                },
+            }
+        }
+        let input = next.input;
+        // Same order as the game: gravity resets the forces the drive adds.
+        physics.update_gravity(&terrain);
+        physics.update_aerodynamics(&map.atmosphere);
+        vehicle.drive(&mut physics, &terrain, &input);
+        physics.step();
+        record(&mut recorder, &physics, &vehicle);
+
+        let position = physics.get_transform(vehicle.chassis).translation.vector;
+        summary.distance += (position - last).norm();
+        last = position;
+        summary.max_tilt = summary.max_tilt.max(tilt(&physics, &terrain, &vehicle));
+        if !vehicle.grounded(&physics, &terrain) {
+            summary.airtime += PHYSICS_DT;
+        }
+        tick += 1;
+    }
+    summary.ticks = tick;
+    summary.time = physics.last_time();
+    summary.displacement = (last - start).norm();
+
+    let pretty = ron::ser::PrettyConfig::default();
+    println!(
+        "{}",
+        ron::ser::to_string_pretty(&summary, pretty).expect("ron serialize")
+    );
+}
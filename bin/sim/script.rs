@@ -0,0 +1,92 @@
+//! The shorthand input script `sim` accepts besides a RON `Timeline`:
+//! steps separated by commas or new lines, each either keys held for a
+//! while or a one-off event.
+//!
+//!     # settle, then a lap of manoeuvres
+//!     idle for 1s
+//!     W for 2s, W+A for 1s, jump
+//!     W+Shift for 500ms
+//!
+//! Keys are `W`, `A`, `S`, `D`, `Shift` (turbo) and `Ctrl` (handbrake),
+//! joined with `+`, and do what they do in the game: `S` brakes, then
+//! reverses once the car has stopped. `idle` holds nothing. `jump` fires
+//! a tap-jump on the tick it is reached. Everything after `#` on a line is
+//! a comment.
+
+use std::fmt;
+use vandals_and_heroes::{
+    Timeline,
+    timeline::{Event, Key},
+};
+
+#[derive(Debug)]
+pub struct Error {
+    line: usize,
+    message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn parse_keys(keys: &str) -> Result<Vec<Key>, String> {
+    if keys.eq_ignore_ascii_case("idle") {
+        return Ok(Vec::new());
+    }
+    keys.split('+')
+        .map(str::trim)
+        .map(|key| match key.to_ascii_lowercase().as_str() {
+            "w" => Ok(Key::Forward),
+            "s" => Ok(Key::Brake),
+            "a" => Ok(Key::Left),
+            "d" => Ok(Key::Right),
+            "shift" => Ok(Key::Turbo),
+            "ctrl" => Ok(Key::Handbrake),
+            _ => Err(format!("unknown key `{key}`")),
+        })
+        .collect()
+}
+
+/// `2s`, `1.5s` or `500ms`, in seconds.
+fn parse_duration(text: &str) -> Result<f32, String> {
+    let seconds = if let Some(ms) = text.strip_suffix("ms") {
+        ms.trim().parse::<f32>().map(|ms| ms / 1000.0)
+    } else if let Some(s) = text.strip_suffix('s') {
+        s.trim().parse::<f32>()
+    } else {
+        return Err(format!("duration `{text}` needs a unit, `s` or `ms`"));
+    };
+    match seconds {
+        Ok(seconds) if seconds >= 0.0 => Ok(seconds),
+        _ => Err(format!("bad duration `{text}`")),
+    }
+}
+
+fn parse_step(timeline: Timeline, text: &str) -> Result<Timeline, String> {
+    if text.eq_ignore_ascii_case("jump") {
+        return Ok(timeline.event(Event::Jump { charge: 0.0 }));
+    }
+    let (keys, duration) = text
+        .split_once(" for ")
+        .ok_or_else(|| format!("expected `KEYS for DURATION` or `jump`, got `{text}`"))?;
+    Ok(timeline.hold(&parse_keys(keys.trim())?, parse_duration(duration.trim())?))
+}
+
+/// Parse a whole script into the timeline it stands for.
+pub fn parse(source: &str) -> Result<Timeline, Error> {
+    let mut timeline = Timeline::default();
+    for (index, line) in source.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default();
+        for text in line.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            timeline = parse_step(timeline, text).map_err(|message| Error {
+                line: index + 1,
+                message,
+            })?;
+        }
+    }
+    Ok(timeline)
+}
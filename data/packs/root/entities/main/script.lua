@@ -0,0 +1,38 @@
+-- Level driver for the main level: drops a fresh cube onto the floor every
+-- few seconds, up to a handful, and nudges anything that lands on the
+-- ground back up into the air.
+
+local DROP_INTERVAL = 3.0
+local MAX_DROPS = 8
+
+local timer = 0.0
+local drops = 0
+
+-- The floor's place in the level, in surface coordinates.
+local FLOOR_U = 0.25
+local FLOOR_V = 0.6054
+
+function on_spawn(id)
+  local x, y, z = world.surface_position(FLOOR_U, FLOOR_V)
+  if x then
+    world.log(string.format("main: ground under the floor at radius %.2f",
+      world.terrain_height(x, y, z)))
+  end
+end
+
+function on_tick(id, dt)
+  timer = timer + dt
+  if timer < DROP_INTERVAL or drops >= MAX_DROPS then
+    return
+  end
+  timer = 0.0
+  drops = drops + 1
+  -- Each a little further across the floor than the last, so they
+  -- don't land square on top of each other.
+  local x, y, z = world.surface_position(FLOOR_U, FLOOR_V, 3.5, 0.08 * drops, 0.0)
+  if not x then
+    return
+  end
+  local cube = world.spawn("cube", x, y, z, 0.0, 0.3 * drops, 0.0)
+  world.log("main: dropped cube " .. cube)
+end
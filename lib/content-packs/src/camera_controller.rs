@@ -21,18 +21,6 @@ impl CameraController {
 
     pub fn on_event(&mut self, event: &winit::event::WindowEvent) {
         match *event {
-            winit::event::WindowEvent::KeyboardInput {
-                event:
-                    winit::event::KeyEvent {
-                        physical_key: winit::keyboard::PhysicalKey::Code(key_code),
-                        state: winit::event::ElementState::Pressed,
-                        ..
-                    },
-                ..
-            } => {
-                let delta = 0.1;
-                self.camera.on_key(key_code, delta);
-            }
             winit::event::WindowEvent::MouseWheel { delta, .. } => {
                 self.camera.on_wheel(delta);
             }
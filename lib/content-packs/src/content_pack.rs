@@ -1,56 +1,400 @@
-use crate::definitions::{LevelDesc, ObjectDesc};
-use ron::de::from_reader;
+//! Content packs and the stack they are mounted in. `packs.ron` lists the
+//! packs to mount, in order; each is a directory with a `pack.ron`
+//! manifest, an optional `entities.ron` and an optional `levels` directory
+//! of level files. A later pack replaces earlier packs' entities and levels
+//! that have the same id, and its files shadow theirs at the same path.
+//!
+//! Loading carries on past problems, collecting a [`Diagnostic`] for each,
+//! so `validate` can report them all; [`PackSet::new`] gives up on the
+//! first error instead.
+
+use crate::definitions::{LevelDesc, ObjectDesc, PackDesc};
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
-use std::fs::File;
+use std::fmt;
 use std::path::{Path, PathBuf};
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The pack will not load or will misbehave.
+    Error,
+    /// Probably a mistake, but harmless.
+    Warning,
+}
+
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub file: PathBuf,
+    /// 1-based.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match self.line {
+            Some(line) => write!(f, "{}:{line}: ", self.file.display())?,
+            None => write!(f, "{}: ", self.file.display())?,
+        }
+        write!(f, "{severity}: {}", self.message)
+    }
+}
+
+/// What stopped the packs from mounting.
+#[derive(Debug)]
+pub struct PackError(Diagnostic);
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for PackError {}
+
+#[derive(Default)]
+pub struct Report(pub Vec<Diagnostic>);
+
+impl Report {
+    fn add(&mut self, severity: Severity, file: &Path, line: Option<usize>, message: String) {
+        self.0.push(Diagnostic {
+            severity,
+            file: file.to_path_buf(),
+            line,
+            message,
+        });
+    }
+
+    pub fn error(&mut self, file: &Path, line: Option<usize>, message: String) {
+        self.add(Severity::Error, file, line, message);
+    }
+
+    pub fn warning(&mut self, file: &Path, line: Option<usize>, message: String) {
+        self.add(Severity::Warning, file, line, message);
+    }
+}
+
+/// A file's text, kept to point diagnostics at lines in it.
+pub struct Source {
+    pub path: PathBuf,
+    text: String,
+}
+
+impl Source {
+    pub fn read(path: &Path, report: &mut Report) -> Option<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Some(Self {
+                path: path.to_path_buf(),
+                text,
+            }),
+            Err(e) => {
+                report.error(path, None, format!("unable to read: {e}"));
+                None
+            }
+        }
+    }
+
+    pub fn parse<T: DeserializeOwned>(&self, report: &mut Report) -> Option<T> {
+        ron::from_str(&self.text)
+            .map_err(|e| report.error(&self.path, Some(e.span.start.line), e.code.to_string()))
+            .ok()
+    }
+
+    /// Line of the `nth` (from 0) place `value` appears as a quoted string.
+    pub fn line_of(&self, value: &str, nth: usize) -> Option<usize> {
+        let quoted = format!("\"{value}\"");
+        let (offset, _) = self.text.match_indices(&quoted).nth(nth)?;
+        Some(self.text[..offset].matches('\n').count() + 1)
+    }
+}
+
+/// What is wrong with mounting `manifest` on top of the `mounted` packs:
+/// a pack by the same name already there, a dependency missing or at a
+/// version that will not do.
+fn dependency_problems(manifest: &PackDesc, mounted: &[&PackDesc]) -> Vec<String> {
+    let find = |name: &str| mounted.iter().find(|pack| pack.name == name);
+    let mut problems = Vec::new();
+    if find(&manifest.name).is_some() {
+        problems.push("mounted twice".to_string());
+    }
+    for dependency in &manifest.dependencies {
+        match find(&dependency.name) {
+            None => problems.push(format!(
+                "needs `{}` {} mounted before it",
+                dependency.name, dependency.version
+            )),
+            Some(pack) if !pack.version.satisfies(dependency.version) => problems.push(format!(
+                "needs `{}` {}, but {} is mounted",
+                dependency.name, dependency.version, pack.version
+            )),
+            Some(_) => {}
+        }
+    }
+    problems
+}
+
+/// Where `path` is found in a stack of pack `directories`, mount order,
+/// looking from the last down. A path none has resolves into the last,
+/// so the error reading it names a sensible place.
+fn resolve<'a>(directories: impl DoubleEndedIterator<Item = &'a Path>, path: &Path) -> PathBuf {
+    let mut candidates = directories.rev().map(|directory| directory.join(path));
+    let last = candidates.next().unwrap_or_else(|| path.to_path_buf());
+    if last.exists() {
+        return last;
+    }
+    candidates
+        .find(|candidate| candidate.exists())
+        .unwrap_or(last)
+}
+
+/// One pack directory, as much of it as would parse.
 pub struct ContentPack {
-    entities: HashMap<String, ObjectDesc>,
-    levels: Vec<LevelDesc>,
-    pub(crate) directory: PathBuf,
+    pub directory: PathBuf,
+    pub manifest: Option<PackDesc>,
+    pub entities: Option<(Source, Vec<ObjectDesc>)>,
+    pub levels: Vec<(Source, LevelDesc)>,
 }
 
 impl ContentPack {
-    pub fn new(directory: &Path) -> Self {
-        let entities_vec: Vec<ObjectDesc> = Self::read_ron(&directory.join("entities.ron"));
-        let entities: HashMap<String, ObjectDesc> = entities_vec
-            .into_iter()
-            .map(|e| (e.id.clone(), e))
-            .collect();
+    pub fn load(directory: &Path, report: &mut Report) -> Self {
+        let manifest = Source::read(&directory.join("pack.ron"), report)
+            .and_then(|source| source.parse(report));
+
+        let entities_path = directory.join("entities.ron");
+        let entities = if entities_path.exists() {
+            Source::read(&entities_path, report).and_then(|source| {
+                let entities = source.parse(report)?;
+                Some((source, entities))
+            })
+        } else {
+            None
+        };
 
         let mut levels = Vec::new();
         let levels_directory = directory.join("levels");
-        for entry in std::fs::read_dir(levels_directory).unwrap() {
-            let entry = entry.unwrap();
-            let path = entry.path();
-            if let Some(ext) = path.extension() {
-                assert_eq!(ext, "ron");
-                levels.push(Self::read_ron(&path));
+        if levels_directory.exists() {
+            let mut paths = match std::fs::read_dir(&levels_directory) {
+                Ok(entries) => entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .collect(),
+                Err(e) => {
+                    report.error(&levels_directory, None, format!("unable to list: {e}"));
+                    Vec::new()
+                }
+            };
+            // Directory order is up to the file system.
+            paths.sort();
+            for path in paths {
+                if path.extension().is_none_or(|ext| ext != "ron") {
+                    report.warning(&path, None, "not a `.ron` level; ignored".to_string());
+                    continue;
+                }
+                levels.extend(Source::read(&path, report).and_then(|source| {
+                    let level = source.parse(report)?;
+                    Some((source, level))
+                }));
             }
         }
 
         Self {
+            directory: directory.to_path_buf(),
+            manifest,
             entities,
             levels,
-            directory: directory.to_path_buf(),
         }
     }
+}
+
+/// Every pack `packs.ron` lists, mounted on top of each other.
+pub struct PackSet {
+    /// In mount order: later packs win.
+    pub packs: Vec<ContentPack>,
+    /// Pack and place in its `entities.ron` of each entity that won.
+    entities: HashMap<String, (usize, usize)>,
+    /// Pack and level file of each level that won, in the order their ids
+    /// were first seen.
+    levels: Vec<(usize, usize)>,
+}
 
-    fn read_ron<T: DeserializeOwned>(path: &Path) -> T {
-        let file = File::open(path).unwrap();
-        from_reader(file).unwrap()
+impl PackSet {
+    /// Mount the packs listed in `directory/packs.ron`, checking that each
+    /// pack's dependencies are already mounted at a version that will do.
+    /// Fails on the first error `load` finds.
+    pub fn new(directory: &Path) -> Result<Self, PackError> {
+        let mut report = Report::default();
+        let set = Self::load(directory, &mut report);
+        match (report.0.into_iter()).find(|diagnostic| diagnostic.severity == Severity::Error) {
+            Some(error) => Err(PackError(error)),
+            None => Ok(set),
+        }
+    }
+
+    /// Mount what can be mounted of the packs listed in
+    /// `directory/packs.ron`, reporting every problem on the way.
+    pub fn load(directory: &Path, report: &mut Report) -> Self {
+        let mut set = Self {
+            packs: Vec::new(),
+            entities: HashMap::new(),
+            levels: Vec::new(),
+        };
+        let Some(list) = Source::read(&directory.join("packs.ron"), report) else {
+            return set;
+        };
+        let Some(names) = list.parse::<Vec<String>>(report) else {
+            return set;
+        };
+        for (index, name) in names.iter().enumerate() {
+            let pack_directory = directory.join(name);
+            if pack_directory.is_dir() {
+                set.mount(ContentPack::load(&pack_directory, report), report);
+            } else {
+                let nth = names[..index].iter().filter(|n| *n == name).count();
+                report.error(
+                    &list.path,
+                    list.line_of(name, nth),
+                    format!("no pack directory {}", pack_directory.display()),
+                );
+            }
+        }
+        set
+    }
+
+    fn mount(&mut self, pack: ContentPack, report: &mut Report) {
+        if let Some(ref manifest) = pack.manifest {
+            let mounted: Vec<_> = (self.packs.iter())
+                .filter_map(|pack| pack.manifest.as_ref())
+                .collect();
+            let path = pack.directory.join("pack.ron");
+            for message in dependency_problems(manifest, &mounted) {
+                report.error(&path, None, message);
+            }
+            log::info!("Mounting pack {} {}", manifest.name, manifest.version);
+        }
+
+        let index = self.packs.len();
+        self.packs.push(pack);
+        let pack = &self.packs[index];
+        if let Some((_, ref entities)) = pack.entities {
+            for (position, entity) in entities.iter().enumerate() {
+                self.entities.insert(entity.id.clone(), (index, position));
+            }
+        }
+        let packs = &self.packs;
+        for (position, (_, level)) in pack.levels.iter().enumerate() {
+            let existing = (self.levels.iter_mut())
+                .find(|&&mut (pack, file)| packs[pack].levels[file].1.id == level.id);
+            match existing {
+                Some(existing) => *existing = (index, position),
+                None => self.levels.push((index, position)),
+            }
+        }
+    }
+
+    /// The entities that won, each with the file it comes from.
+    pub fn entities(&self) -> impl Iterator<Item = (&ObjectDesc, &Source)> {
+        self.entities.values().map(|&(pack, position)| {
+            let (ref source, ref entities) = *self.packs[pack].entities.as_ref().unwrap();
+            (&entities[position], source)
+        })
+    }
+
+    /// The levels that won, each with the file it comes from.
+    pub fn levels(&self) -> impl Iterator<Item = (&LevelDesc, &Source)> {
+        self.levels.iter().map(|&(pack, file)| {
+            let (ref source, ref level) = self.packs[pack].levels[file];
+            (level, source)
+        })
     }
 
     pub fn get_entity_by_id(&self, id: &str) -> Option<&ObjectDesc> {
-        self.entities.get(id)
+        let &(pack, position) = self.entities.get(id)?;
+        let (_, ref entities) = *self.packs[pack].entities.as_ref()?;
+        entities.get(position)
     }
 
+    /// Where `path` is found, looking through the packs from the last
+    /// mounted down; see `resolve`.
     pub fn get_resource_path(&self, path: &Path) -> PathBuf {
-        self.directory.join(path)
+        resolve(self.packs.iter().map(|pack| pack.directory.as_path()), path)
     }
 
     pub fn get_level_by_id(&self, level_id: &str) -> Option<&LevelDesc> {
-        self.levels.iter().find(|l| l.id == level_id)
+        self.levels()
+            .map(|(level, _)| level)
+            .find(|l| l.id == level_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::Version;
+
+    fn version(text: &str) -> Version {
+        Version::try_from(text.to_string()).unwrap()
+    }
+
+    /// Why mounting the packs in `fixtures/<name>` fails.
+    fn mount_error(name: &str) -> String {
+        let root = Path::new("fixtures").join(name);
+        PackSet::new(&root).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn later_pack_overrides_by_id() {
+        let set = PackSet::new(Path::new("fixtures/stack")).unwrap();
+        let levels: Vec<_> = set
+            .levels()
+            .map(|(level, _)| (level.id.as_str(), level.name.as_str()))
+            .collect();
+        assert_eq!(levels, [("arena", "Arena"), ("main", "Modded")]);
+        let cube = set.get_entity_by_id("cube").unwrap();
+        assert_eq!(cube.scene_path.as_deref(), Some(Path::new("big_cube.glb")));
+        assert!(set.get_entity_by_id("ball").is_some());
+        assert_eq!(set.entities().count(), 2);
+    }
+
+    #[test]
+    fn later_pack_shadows_files() {
+        let root = Path::new("fixtures/stack");
+        let set = PackSet::new(root).unwrap();
+        let resolve = |path: &str| set.get_resource_path(Path::new(path));
+        assert_eq!(resolve("shared.txt"), root.join("mod/shared.txt"));
+        assert_eq!(resolve("base_only.txt"), root.join("base/base_only.txt"));
+        assert_eq!(resolve("nowhere.txt"), root.join("mod/nowhere.txt"));
+    }
+
+    #[test]
+    fn missing_dependency_stops_the_mount() {
+        let error = mount_error("missing-dependency");
+        assert!(
+            error.contains("needs `base` 1.1.0 mounted before it"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn incompatible_dependency_stops_the_mount() {
+        let error = mount_error("incompatible");
+        assert!(
+            error.contains("needs `base` 1.1.0, but 2.0.0 is mounted"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn version_satisfies_same_major_no_older() {
+        let required = version("1.2.0");
+        assert!(version("1.2.0").satisfies(required));
+        assert!(version("1.2.3").satisfies(required));
+        assert!(version("1.10.0").satisfies(required));
+        assert!(!version("1.1.9").satisfies(required));
+        assert!(!version("2.0.0").satisfies(required));
+        assert!(!version("0.9.0").satisfies(required));
     }
 }
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::ops::Range;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use vandals_and_heroes::config::WorldShape;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TransformDesc {
@@ -46,22 +49,100 @@ impl From<TransformDesc> for nalgebra::Isometry3<f32> {
     }
 }
 
+/// Collider shapes; the round ones stand along Y.
 #[derive(Serialize, Deserialize, Clone)]
 pub enum ShapeDesc {
-    Box { size: (f32, f32, f32) },
-    Sphere { radius: f32 },
-    Mesh { path: PathBuf },
+    Box {
+        size: (f32, f32, f32),
+    },
+    Sphere {
+        radius: f32,
+    },
+    /// `half_height` of the straight part, between the rounded ends.
+    Capsule {
+        half_height: f32,
+        radius: f32,
+    },
+    Cylinder {
+        half_height: f32,
+        radius: f32,
+    },
+    /// The glTF's triangles as they are. Dynamic bodies barely collide
+    /// with it, so keep it to static ones.
+    Mesh {
+        path: PathBuf,
+    },
+    /// The smallest convex shape around the glTF's vertices.
+    ConvexHull {
+        path: PathBuf,
+    },
+    /// The glTF split into up to `max_hulls` convex pieces by V-HACD,
+    /// cached beside it.
+    ConvexDecomposition {
+        path: PathBuf,
+        max_hulls: u32,
+    },
+}
+
+impl ShapeDesc {
+    /// The glTF the shape is built from, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match *self {
+            Self::Mesh { ref path }
+            | Self::ConvexHull { ref path }
+            | Self::ConvexDecomposition { ref path, .. } => Some(path),
+            Self::Box { .. }
+            | Self::Sphere { .. }
+            | Self::Capsule { .. }
+            | Self::Cylinder { .. } => None,
+        }
+    }
+}
+
+/// Which colliders meet: two do when each one's `filter` shares a bit with
+/// the other's `memberships`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CollisionGroupsDesc {
+    pub memberships: u32,
+    pub filter: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ColliderDesc {
     pub shape: ShapeDesc,
     pub transform: TransformDesc,
+    /// kg/m³. On a `RigidBody` the densities are scaled to add up to its
+    /// `mass`, so only their ratios matter.
+    #[serde(default = "ColliderDesc::default_density")]
+    pub density: f32,
+    #[serde(default = "ColliderDesc::default_friction")]
+    pub friction: f32,
+    #[serde(default)]
+    pub restitution: f32,
+    /// Reports overlaps without pushing anything.
+    #[serde(default)]
+    pub sensor: bool,
+    /// Everything collides with everything when absent.
+    #[serde(default)]
+    pub collision_groups: Option<CollisionGroupsDesc>,
+}
+
+impl ColliderDesc {
+    pub fn default_density() -> f32 {
+        1.0
+    }
+
+    pub fn default_friction() -> f32 {
+        0.5
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub enum PhysicsBodyDesc {
-    RigidBody { mass: f32 },
+    RigidBody {
+        /// kg, spread over the colliders by their densities.
+        mass: f32,
+    },
     StaticBody,
 }
 
@@ -71,12 +152,111 @@ pub struct PhysicsDesc {
     pub colliders: Vec<ColliderDesc>,
 }
 
+/// How a child's body may move against its parent's. Revolute and
+/// prismatic joints turn about and slide along `axis`.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum JointKind {
+    Fixed,
+    Revolute {
+        axis: (f32, f32, f32),
+        /// Radians either way of where the child starts.
+        #[serde(default)]
+        limits: Option<Range<f32>>,
+    },
+    Prismatic {
+        axis: (f32, f32, f32),
+        /// Metres either way of where the child starts.
+        #[serde(default)]
+        limits: Option<Range<f32>>,
+    },
+    Spherical,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JointDesc {
+    pub kind: JointKind,
+    /// Where the joint sits, in the child's frame.
+    #[serde(default = "TransformDesc::default_position")]
+    pub anchor: (f32, f32, f32),
+}
+
+/// An entity attached to a prefab.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChildDesc {
+    pub entity_id: String,
+    /// Relative to the parent.
+    #[serde(default)]
+    pub transform: TransformDesc,
+    /// Ties the child's body to the parent's; without one, a child with a
+    /// body is left to move on its own and one without follows the parent.
+    #[serde(default)]
+    pub joint: Option<JointDesc>,
+}
+
+/// A car the player can drive.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VehicleDesc {
+    /// A `car.ron` like those in `data/cars`: wheels, drivetrain and the
+    /// rest, for the entity's scene as the body.
+    pub config_path: PathBuf,
+}
+
+/// Trigger volumes, centred on the object.
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub enum TriggerShape {
+    Box { size: (f32, f32, f32) },
+    Sphere { radius: f32 },
+}
+
+/// A volume that fires the level's events as bodies pass through it.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TriggerDesc {
+    pub shape: TriggerShape,
+    /// Fired for every body that comes in.
+    #[serde(default)]
+    pub on_enter: Option<String>,
+    /// Fired for every body that leaves, or is removed while inside.
+    #[serde(default)]
+    pub on_exit: Option<String>,
+}
+
+impl TriggerDesc {
+    /// The sensor the trigger is made of: massless, pushing nothing.
+    pub fn collider(&self) -> ColliderDesc {
+        let shape = match self.shape {
+            TriggerShape::Box { size } => ShapeDesc::Box { size },
+            TriggerShape::Sphere { radius } => ShapeDesc::Sphere { radius },
+        };
+        ColliderDesc {
+            shape,
+            transform: TransformDesc::default(),
+            density: 0.0,
+            friction: 0.0,
+            restitution: 0.0,
+            sensor: true,
+            collision_groups: None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ObjectDesc {
     pub id: String,
     pub scene_path: Option<PathBuf>,
     pub physics: Option<PhysicsDesc>,
     pub script_path: Option<PathBuf>,
+    /// Makes the entity a prefab: these come with every object made from
+    /// it. Their scripts do not run; the root's script drives the lot.
+    #[serde(default)]
+    pub children: Vec<ChildDesc>,
+    /// Makes the entity a car, building its own bodies in place of
+    /// `physics`. Only a level's `player_start` places one.
+    #[serde(default)]
+    pub vehicle: Option<VehicleDesc>,
+    /// Goes on the entity's body, or on a fixed one of its own without
+    /// `physics`.
+    #[serde(default)]
+    pub trigger: Option<TriggerDesc>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -85,20 +265,151 @@ pub struct HeightMapDesc {
     pub image_path: PathBuf,
     pub radius: Range<f32>,
     pub density: f32,
+    #[serde(default)]
+    pub shape: WorldShape,
+    /// Cylinder length or torus centreline circumference; derived from the
+    /// image's aspect ratio when left out.
+    #[serde(default)]
+    pub length: Option<f32>,
+    /// Sky image drawn around the world.
+    #[serde(default)]
+    pub environment_path: Option<PathBuf>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// A place on the ground, the same on every world shape: `u` goes around
+/// the world and `v` along it, both from 0 to 1 across the height map.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SurfaceDesc {
+    pub u: f32,
+    pub v: f32,
+    /// Above the ground, along the local up.
+    #[serde(default)]
+    pub altitude: f32,
+    /// Radians counterclockwise, seen from above, from facing +Z along
+    /// increasing `v`.
+    #[serde(default)]
+    pub heading: f32,
+    /// Metres to step over the ground from `u, v` before standing there,
+    /// along the object's own X and Z once turned by `heading`.
+    #[serde(default)]
+    pub offset: (f32, f32),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LevelObjectDesc {
     pub id: String,
     pub entity_id: String,
     #[serde(default)]
     pub transform: TransformDesc,
+    /// Stands the object on the ground, in place of `transform`'s position
+    /// and rotation.
+    #[serde(default)]
+    pub surface: Option<SurfaceDesc>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// One thing a level event does.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum EventActionDesc {
+    /// Make an object, placed as the level's own are. Its `id` names it for
+    /// a later `Despawn`.
+    Spawn(LevelObjectDesc),
+    /// Take the object with this id, and its children, out of the level.
+    Despawn(String),
+    /// Tell the player something.
+    Message(String),
+    /// Stop the level, won or lost.
+    EndLevel { success: bool },
+}
+
+/// Where the player's car starts.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlayerStartDesc {
+    /// An entity with a `vehicle`.
+    pub entity_id: String,
+    #[serde(default)]
+    pub transform: TransformDesc,
+    /// Stands the car on the ground with its nose along `heading`, in
+    /// place of `transform`'s position and rotation.
+    #[serde(default)]
+    pub surface: Option<SurfaceDesc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LevelDesc {
     pub id: String,
     pub name: String,
     pub height_map: HeightMapDesc,
     pub objects: Vec<LevelObjectDesc>,
+    /// Without one there is nothing to drive and the camera flies free.
+    #[serde(default)]
+    pub player_start: Option<PlayerStartDesc>,
+    /// The events triggers fire, by name, each doing its actions in order.
+    #[serde(default)]
+    pub events: HashMap<String, Vec<EventActionDesc>>,
+}
+
+/// A `major.minor.patch` pack version, written as a string in RON.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    /// Whether a pack at this version can stand in for `required`: same
+    /// major version, and no older.
+    pub fn satisfies(self, required: Version) -> bool {
+        self.major == required.major && self >= required
+    }
+}
+
+impl TryFrom<String> for Version {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, String> {
+        let parts = text
+            .split('.')
+            .map(str::parse)
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(|e| format!("version `{text}`: {e}"))?;
+        match parts[..] {
+            [major, minor, patch] => Ok(Self {
+                major,
+                minor,
+                patch,
+            }),
+            _ => Err(format!("version `{text}` is not `major.minor.patch`")),
+        }
+    }
+}
+
+impl From<Version> for String {
+    fn from(version: Version) -> Self {
+        version.to_string()
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PackDependencyDesc {
+    pub name: String,
+    /// Oldest version that will do; see `Version::satisfies`.
+    pub version: Version,
+}
+
+/// `pack.ron`, the manifest at the root of every pack.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PackDesc {
+    pub name: String,
+    pub version: Version,
+    /// Packs that must be mounted before this one.
+    #[serde(default)]
+    pub dependencies: Vec<PackDependencyDesc>,
 }
@@ -1,34 +1,58 @@
 use crate::camera_controller::CameraController;
-use crate::content_pack::ContentPack;
-use crate::definitions::{HeightMapDesc, ObjectDesc};
-use crate::instances::{Object, TerrainObject};
+use crate::content_pack::PackSet;
+use crate::definitions::{EventActionDesc, HeightMapDesc, LevelObjectDesc, ObjectDesc};
+use crate::instances::{Object, PlayerCar, TerrainObject};
+use crate::scripting::{ScriptRuntime, Spawn, World};
 use crate::templates::ObjectTemplate;
 use blade_graphics as gpu;
 use nalgebra::{Matrix4, UnitQuaternion, Vector3};
-use std::{
-    collections::{HashMap, HashSet},
-    f32::consts::PI,
-    fs,
-    path::Path,
-    sync::Arc,
-    time,
+use std::{collections::HashMap, f32::consts::PI, fs, path::Path, sync::Arc, time};
+use vandals_and_heroes::{
+    Camera, Damage, DriveInput, Loader, ModelInstance, Physics, Recovery, Render, Shells, Terrain,
+    Vehicle, config, controls, tin, vehicle,
 };
-use vandals_and_heroes::{Camera, Loader, Physics, Render, Terrain, config, tin};
 use winit::event_loop::EventLoop;
 
+/// One physics step, rapier's default; the game takes one every redraw.
+const PHYSICS_DT: f32 = 1.0 / 60.0;
+
 pub struct Game {
     render: Render,
     physics: Physics,
     pub window: winit::window::Window,
     window_size: winit::dpi::PhysicalSize<u32>,
     camera_controller: CameraController,
-    content_pack: ContentPack,
+    content_pack: PackSet,
     templates: HashMap<String, ObjectTemplate>,
     terrain: Option<TerrainObject>,
     instances: Vec<Object>,
+    scripts: ScriptRuntime,
     /// Terrain mesh fit quality from `data/config.ron`, applied to every
     /// level height map this pack loads.
     terrain_quality: f32,
+    /// The car from the level's `player_start`, if it has one.
+    player: Option<PlayerCar>,
+    /// How the player's car is recovered, from `data/config.ron`.
+    recovery: config::Recovery,
+    /// Health of the player's car.
+    damage: Damage,
+    /// The player's shells in flight.
+    shells: Shells,
+    /// Bound keys and gamepads, and what they hold, for driving the player.
+    controls: controls::Controls,
+    /// The world stands still and the camera flies free.
+    paused: bool,
+    /// The jump being charged while Jump is held.
+    jump_charge: vehicle::JumpCharge,
+    /// False until the camera has first snapped behind the player.
+    camera_follows: bool,
+    /// The level's events, by name.
+    events: HashMap<String, Vec<EventActionDesc>>,
+    /// Objects by the id the level or a `Spawn` action gave them.
+    object_ids: HashMap<String, usize>,
+    /// Whether the level was won, once an event has ended it. The world
+    /// stands still from then on.
+    ended: Option<bool>,
 }
 
 pub struct QuitEvent;
@@ -59,78 +83,322 @@ impl Game {
 
         let gpu_surface = gpu_context.create_surface(&window).unwrap();
 
-        let render = Render::new(gpu_context, gpu_surface, extent);
+        let mut render = Render::new(gpu_context, gpu_surface, extent);
+        let mut loader = render.start_loading();
+        let shells = Shells::new(&mut loader);
+        let submission = loader.finish();
+        render.accept_submission(submission);
 
         let config: config::Config = ron::de::from_bytes(
             &fs::read("data/config.ron").expect("Unable to open the main config"),
         )
         .expect("Unable to parse the main config");
+        let bindings: controls::Bindings = ron::de::from_bytes(
+            &fs::read("data/bindings.ron").expect("Unable to open the bindings"),
+        )
+        .expect("Unable to parse the bindings");
 
         Self {
             terrain_quality: config.terrain_quality,
             camera_controller: CameraController::new(Camera::default()),
             render,
             physics: Physics::default(),
-            content_pack: ContentPack::new(mods_directory),
+            content_pack: PackSet::new(mods_directory)
+                .unwrap_or_else(|e| panic!("Unable to mount the content packs: {e}")),
             window,
             window_size,
             terrain: None,
             templates: HashMap::new(),
             instances: Vec::new(),
+            scripts: ScriptRuntime::default(),
+            player: None,
+            recovery: config.recovery,
+            damage: Damage::default(),
+            shells,
+            controls: controls::Controls::new(bindings),
+            paused: false,
+            jump_charge: vehicle::JumpCharge::default(),
+            camera_follows: false,
+            events: HashMap::new(),
+            object_ids: HashMap::new(),
+            ended: None,
         }
     }
 
     pub fn load_level(&mut self, level_id: &str) {
         let level = self.content_pack.get_level_by_id(level_id).unwrap();
 
-        let entity_ids: HashSet<String> =
-            level.objects.iter().map(|o| o.entity_id.clone()).collect();
-
         let mut loader = self.render.start_loading();
-        self.templates = entity_ids
-            .iter()
-            .filter_map(|id| {
-                if let Some(object) = self.content_pack.get_entity_by_id(id) {
-                    Some((id.clone(), object.load(&self.content_pack, &mut loader)))
-                } else {
-                    log::warn!("Cannot find entity with id: {}", id);
-                    None
-                }
-            })
-            .collect();
+        self.templates = HashMap::new();
+        // What events spawn too, so nothing loads mid-level.
+        let spawned = level
+            .events
+            .values()
+            .flatten()
+            .filter_map(|action| match *action {
+                EventActionDesc::Spawn(ref object) => Some(object.entity_id.as_str()),
+                _ => None,
+            });
+        Self::load_templates(
+            &self.content_pack,
+            &mut loader,
+            &mut self.templates,
+            level
+                .objects
+                .iter()
+                .map(|o| o.entity_id.as_str())
+                .chain(spawned),
+        );
 
         {
-            let (terrain, mesh) = Self::load_heightmap(
+            let terrain = Self::load_heightmap(
                 &self.content_pack,
                 &mut loader,
+                &mut self.physics,
                 &level.height_map,
                 self.terrain_quality,
             );
-            let camera = self.camera_controller.camera_mut();
-
-            camera.pos = Vector3::new(0.0, 20.0, 0.1 * terrain.config.length);
-            camera.rot = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.3 * PI);
-            camera.clip.end = terrain.config.length;
 
-            let body = self.physics.create_terrain_mesh(&terrain.config, &mesh);
-            self.terrain = Some(TerrainObject { terrain, body });
+            // Looking down on the level from above the ground range, on the
+            // +Y side of the cylinder and sphere and the outer +Z side of
+            // the torus.
+            let (top, frame) = terrain.surface_frame(0.25, 0.6);
+            let up = frame * Vector3::y();
+            let camera = self.camera_controller.camera_mut();
+            camera.pos = Vector3::new(top.x, top.y, top.z) + 5.0 * up;
+            camera.rot = frame * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.3 * PI);
+            // Far enough to see the far side of the world.
+            let config = &terrain.terrain.config;
+            camera.clip.end = match config.shape {
+                config::WorldShape::Cylinder => config.length,
+                config::WorldShape::Sphere => 4.0 * config.radius.end,
+                config::WorldShape::Torus => 2.0 * (terrain.body.major_radius + config.radius.end),
+            };
+            self.terrain = Some(terrain);
         }
 
         let submission = loader.finish();
         self.render.accept_submission(submission);
 
+        // Placed before any object is made, so none stands on another.
+        let transforms: Vec<nalgebra::Isometry3<f32>> = level
+            .objects
+            .iter()
+            .map(|level_object| self.place(level_object))
+            .collect();
+        let player_start = level.player_start.as_ref().map(|start| {
+            let pose = match (start.surface.as_ref(), self.terrain.as_ref()) {
+                (Some(surface), Some(terrain)) => {
+                    let pose = terrain.surface_pose(surface);
+                    // Cars face -X, so turn the nose to where +Z points.
+                    vehicle::upright_pose(
+                        pose.translation.vector,
+                        pose.rotation * Vector3::y(),
+                        pose.rotation * Vector3::z(),
+                    )
+                }
+                _ => start.transform.clone().into(),
+            };
+            (start.entity_id.as_str(), pose)
+        });
         self.instances = level
             .objects
             .iter()
-            .map(|level_object| {
+            .zip(transforms)
+            .map(|(level_object, transform)| {
                 let template = self.templates.get(&level_object.entity_id).unwrap();
                 template.instantiate(
+                    &self.templates,
                     &self.content_pack,
                     &mut self.physics,
-                    level_object.transform.clone().into(),
+                    &self.scripts,
+                    transform,
                 )
             })
             .collect();
+        self.player = player_start.and_then(|(entity_id, pose)| {
+            let Some(entity) = self.content_pack.get_entity_by_id(entity_id) else {
+                log::warn!("Cannot find the player's entity: {}", entity_id);
+                return None;
+            };
+            let mut loader = self.render.start_loading();
+            let player = Self::load_player_car(
+                &self.content_pack,
+                &mut loader,
+                &mut self.physics,
+                entity,
+                pose,
+                self.recovery,
+            );
+            let submission = loader.finish();
+            self.render.accept_submission(submission);
+            player
+        });
+        self.damage = Damage::default();
+        if let Some(player) = self.player.as_ref() {
+            self.damage
+                .track(&player.vehicle.bodies(), player.vehicle.health);
+        }
+        self.camera_follows = false;
+        self.events = level.events.clone();
+        self.object_ids = (level.objects.iter().enumerate())
+            .map(|(index, object)| (object.id.clone(), index))
+            .collect();
+        self.ended = None;
+        let spawns = self.run_scripts(|scripts, world| {
+            let count = world.objects.len();
+            scripts.spawned(world, 0..count);
+        });
+        self.add_spawns(spawns);
+    }
+
+    /// Where `level_object` goes: on the ground at its `surface`, or else
+    /// at its `transform`.
+    fn place(&self, level_object: &LevelObjectDesc) -> nalgebra::Isometry3<f32> {
+        match (level_object.surface.as_ref(), self.terrain.as_ref()) {
+            (Some(surface), Some(terrain)) => terrain.surface_pose(surface),
+            _ => level_object.transform.clone().into(),
+        }
+    }
+
+    /// Build the car of a `vehicle` entity with its chassis at `pose`,
+    /// which is where it respawns until it finds better ground.
+    fn load_player_car(
+        content: &PackSet,
+        loader: &mut Loader,
+        physics: &mut Physics,
+        entity: &ObjectDesc,
+        pose: nalgebra::Isometry3<f32>,
+        recovery: config::Recovery,
+    ) -> Option<PlayerCar> {
+        let (Some(desc), Some(scene_path)) = (entity.vehicle.as_ref(), entity.scene_path.as_ref())
+        else {
+            log::warn!("Entity {} is not a vehicle with a scene", entity.id);
+            return None;
+        };
+        let config_path = content.get_resource_path(&desc.config_path);
+        let car: config::Car = ron::de::from_bytes(
+            &fs::read(&config_path)
+                .unwrap_or_else(|e| panic!("Unable to read {}: {e}", config_path.display())),
+        )
+        .unwrap_or_else(|e| panic!("Unable to parse {}: {e}", config_path.display()));
+
+        let model_desc = Loader::read_gltf(
+            &content.get_resource_path(scene_path),
+            Matrix4::identity().scale(car.scale),
+        );
+        let mut model = loader.load_model(&model_desc);
+        // Tint the body, but not the tires, which would go all but black.
+        for (material, desc) in model.materials.iter_mut().zip(&model_desc.materials) {
+            let is_wheel =
+                (desc.name.as_deref()).is_some_and(|name| name.to_lowercase().contains("wheel"));
+            if !is_wheel {
+                for (factor, tint) in material.base_color_factor.iter_mut().zip(car.body_color) {
+                    *factor *= tint;
+                }
+            }
+        }
+        let vehicle = Vehicle::spawn(physics, &car, &model_desc, pose.into());
+
+        let wheel_radius = car.wheels.first().map_or(0.15, |wheel| wheel.radius);
+        let wheel_model = Arc::new(loader.load_model(&vehicle::wheel_mesh_desc(wheel_radius)));
+        let wheels = (vehicle.wheels.iter())
+            .filter(|wheel| wheel.is_steering)
+            .map(|wheel| {
+                let instance = ModelInstance {
+                    casts_shadow: true,
+                    model: wheel_model.clone(),
+                    transform: physics.get_transform(wheel.rigid_body),
+                    geometry_filter: None,
+                };
+                (wheel.rigid_body, instance)
+            })
+            .collect();
+        Some(PlayerCar {
+            chassis: ModelInstance {
+                casts_shadow: true,
+                model: Arc::new(model),
+                transform: pose,
+                geometry_filter: None,
+            },
+            wheels,
+            vehicle,
+            recovery: Recovery::new(recovery, pose.into()),
+        })
+    }
+
+    /// Load the entities in `ids` that are not in `templates` yet, along
+    /// with the children of prefabs among them.
+    fn load_templates<'a>(
+        content: &PackSet,
+        loader: &mut Loader,
+        templates: &mut HashMap<String, ObjectTemplate>,
+        ids: impl Iterator<Item = &'a str>,
+    ) {
+        let mut pending: Vec<String> = ids.map(str::to_string).collect();
+        while let Some(id) = pending.pop() {
+            if templates.contains_key(&id) {
+                continue;
+            }
+            let Some(object) = content.get_entity_by_id(&id) else {
+                log::warn!("Cannot find entity with id: {}", id);
+                continue;
+            };
+            pending.extend(object.children.iter().map(|c| c.entity_id.clone()));
+            templates.insert(id, object.load(content, loader));
+        }
+    }
+
+    /// Let `run` call into the scripts, returning what they spawned.
+    fn run_scripts(&mut self, run: impl FnOnce(&ScriptRuntime, &mut World)) -> Vec<Spawn> {
+        let mut world = World {
+            content_pack: &self.content_pack,
+            physics: &mut self.physics,
+            terrain: self.terrain.as_ref(),
+            objects: &mut self.instances,
+            spawns: Vec::new(),
+        };
+        run(&self.scripts, &mut world);
+        world.spawns
+    }
+
+    /// Create the objects scripts spawned, loading any entity the level did
+    /// not already use, and call their `on_spawn`, until nothing more is
+    /// spawned.
+    fn add_spawns(&mut self, mut spawns: Vec<Spawn>) {
+        while !spawns.is_empty() {
+            let missing = spawns
+                .iter()
+                .any(|spawn| !self.templates.contains_key(&spawn.entity_id));
+            if missing {
+                let mut loader = self.render.start_loading();
+                Self::load_templates(
+                    &self.content_pack,
+                    &mut loader,
+                    &mut self.templates,
+                    spawns.iter().map(|spawn| spawn.entity_id.as_str()),
+                );
+                let submission = loader.finish();
+                self.render.accept_submission(submission);
+            }
+
+            let first = self.instances.len();
+            for spawn in spawns {
+                let template = &self.templates[&spawn.entity_id];
+                let object = template.instantiate(
+                    &self.templates,
+                    &self.content_pack,
+                    &mut self.physics,
+                    &self.scripts,
+                    spawn.transform,
+                );
+                self.instances.push(object);
+            }
+            spawns = self.run_scripts(|scripts, world| {
+                let count = world.objects.len();
+                scripts.spawned(world, first..count);
+            });
+        }
     }
 
     pub fn on_event(
@@ -149,10 +417,24 @@ impl Game {
                     });
                 }
             }
+            winit::event::WindowEvent::KeyboardInput { ref event, .. } => {
+                let pressed = matches!(event.state, winit::event::ElementState::Pressed);
+                let winit::keyboard::PhysicalKey::Code(key_code) = event.physical_key else {
+                    return Ok(None);
+                };
+                if let Some(press) = self.controls.key(key_code, pressed, self.flying()) {
+                    self.on_action(press)?;
+                }
+            }
             winit::event::WindowEvent::CloseRequested => {
                 return Err(QuitEvent);
             }
             winit::event::WindowEvent::RedrawRequested => {
+                if self.player.is_some() {
+                    for press in self.controls.poll_gamepads() {
+                        self.on_action(press)?;
+                    }
+                }
                 self.redraw();
 
                 let wait = time::Duration::from_millis(16);
@@ -174,59 +456,318 @@ impl Game {
     }
 
     fn load_heightmap(
-        content: &ContentPack,
+        content: &PackSet,
         loader: &mut Loader,
+        physics: &mut Physics,
         def: &HeightMapDesc,
         quality: f32,
-    ) -> (Terrain, tin::TerrainMesh) {
+    ) -> TerrainObject {
         let (texture, extent, alpha) = loader.load_png(&content.get_resource_path(&def.image_path));
-        let circumference = 2.0 * PI * def.radius.start;
-        let length = circumference * (extent.height as f32) / (extent.width as f32);
+        let length = def.length.unwrap_or_else(|| {
+            let circumference = 2.0 * PI * def.radius.start;
+            circumference * (extent.height as f32) / (extent.width as f32)
+        });
+        let env_texture = def
+            .environment_path
+            .as_ref()
+            .map(|path| loader.load_environment(&content.get_resource_path(path)));
 
         let map_config = config::Map {
             radius: def.radius.clone(),
             length,
             density: def.density,
-            shape: config::WorldShape::Cylinder,
+            shape: def.shape,
+            ..Default::default()
         };
         // Triangulate once; the renderer draws these chunks and the physics
         // collides with the very same triangles.
         let mesh = tin::build(&alpha, extent.width, extent.height, &map_config, quality);
         let chunks = loader.load_terrain_mesh(&mesh);
+        let body = physics.create_terrain_mesh(&map_config, &mesh);
 
-        (
-            Terrain {
+        TerrainObject {
+            mapping: tin::Mapping::new(&map_config, extent.width, extent.height),
+            terrain: Terrain {
                 texture,
-                env_texture: None,
+                env_texture,
                 config: map_config,
                 chunks,
             },
-            mesh,
-        )
+            body,
+            heights: alpha,
+        }
     }
 
-    fn redraw(&mut self) {
+    /// React to a bound control being pressed or released. Held actions
+    /// (throttle, brake, steer, handbrake, turbo) are read from `controls`
+    /// every physics step instead.
+    fn on_action(&mut self, press: controls::Press) -> Result<(), QuitEvent> {
+        use controls::Action as A;
+        let step = |axis: Vector3<f32>| axis * press.direction;
+        match press.action {
+            A::Quit if press.pressed => return Err(QuitEvent),
+            A::TogglePause if press.pressed => self.toggle_pause(),
+            A::Jump if !self.paused => self.handle_jump_key(press.pressed),
+            A::Roll if press.pressed && !self.paused => self.roll(press.direction),
+            A::FlyRight | A::FlyUp | A::FlyForward | A::FlyTurn
+                if press.pressed && self.flying() =>
+            {
+                let (motion, turn) = match press.action {
+                    A::FlyRight => (step(Vector3::x()), 0.0),
+                    A::FlyUp => (step(Vector3::y()), 0.0),
+                    A::FlyForward => (step(Vector3::z()), 0.0),
+                    _ => (Vector3::zeros(), press.direction),
+                };
+                self.camera_controller.camera_mut().fly(motion, turn, 0.1);
+            }
+            // Saving, loading and dumping the camera are the game's own.
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// The fly keys steer the free camera while paused, and all the time
+    /// with no car to drive.
+    fn flying(&self) -> bool {
+        self.paused || self.player.is_none()
+    }
+
+    fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.jump_charge.cancel();
+        // Nobody drives while paused; the controls take over again on return.
+        if let Some(player) = self.player.as_ref() {
+            player.vehicle.release(&mut self.physics);
+        }
+        log::info!("Paused: {}", self.paused);
+    }
+
+    /// Flip the player's car about its forward axis; see [`Vehicle::roll`].
+    fn roll(&mut self, direction: f32) {
+        let (Some(player), Some(terrain)) = (self.player.as_ref(), self.terrain.as_ref()) else {
+            return;
+        };
+        if !player
+            .vehicle
+            .roll(&mut self.physics, &terrain.body, direction)
+        {
+            log::info!("roll {:+.0}: airborne, ignored", direction);
+        }
+    }
+
+    /// Charge a jump while Jump is held; see [`vehicle::JumpCharge`].
+    fn handle_jump_key(&mut self, pressed: bool) {
+        if !pressed {
+            if let Some(charge) = self.jump_charge.release() {
+                self.jump(charge);
+            }
+            return;
+        }
+        let grounded = (self.player.as_ref().zip(self.terrain.as_ref()))
+            .is_some_and(|(player, terrain)| player.vehicle.grounded(&self.physics, &terrain.body));
+        self.jump_charge.press(grounded);
+    }
+
+    /// Jump with `charge` in `0..=1`.
+    fn jump(&mut self, charge: f32) {
+        let (Some(player), Some(terrain)) = (self.player.as_ref(), self.terrain.as_ref()) else {
+            return;
+        };
+        // The car may have driven off a ledge while charging.
+        match player
+            .vehicle
+            .jump(&mut self.physics, &terrain.body, charge)
+        {
+            Some(velocity) => log::info!("jump at {velocity:.2} m/s"),
+            None => log::info!("jump released mid-air, cancelled"),
+        }
+    }
+
+    /// Keep the camera behind and above the player's car.
+    fn follow_player(&mut self) {
+        let (Some(player), Some(terrain)) = (self.player.as_ref(), self.terrain.as_ref()) else {
+            return;
+        };
+        let dt = self.camera_follows.then_some(PHYSICS_DT);
+        self.camera_controller
+            .camera_mut()
+            .chase_car(&player.chassis.transform, &terrain.body, dt);
+        self.camera_follows = true;
+    }
+
+    fn step(&mut self) {
+        // A held jump goes off by itself once fully charged.
+        if let Some(charge) = self.jump_charge.full() {
+            self.jump(charge);
+        }
+
+        let input = self.controls.drive_input(PHYSICS_DT);
         if let Some(terrain) = self.terrain.as_ref() {
             self.physics.update_gravity(&terrain.body);
+            self.physics
+                .update_aerodynamics(&terrain.terrain.config.atmosphere);
+            // After update_gravity, which clears the forces driving adds.
+            if let Some(player) = self.player.as_ref() {
+                player
+                    .vehicle
+                    .drive(&mut self.physics, &terrain.body, &input);
+            }
         }
         self.physics.step();
+        self.recover(&input);
+        self.update_weapons(&input);
+        let spawns = self.run_scripts(|scripts, world| scripts.tick(world));
+        self.add_spawns(spawns);
 
+        let mut groups: Vec<_> = (self.instances.iter())
+            .map(|instance| {
+                let mut bodies = Vec::new();
+                instance.bodies(&mut bodies);
+                bodies
+            })
+            .collect();
+        groups.extend(self.player.as_ref().map(|player| player.vehicle.bodies()));
+        let owners = body_owners(groups);
+        let mut events = Vec::new();
         for instance in &mut self.instances {
-            if let Some(body) = &instance.body {
-                instance.transform = self.physics.get_transform(body.rigid_body_handle);
+            instance.trigger_events(&self.physics, &owners, &mut events);
+        }
+        for event in events {
+            if self.ended.is_some() {
+                break;
             }
-            if let Some(model_instance) = &mut instance.model_instance {
-                model_instance.transform = instance.transform;
+            self.fire(&event);
+        }
+
+        for instance in &mut self.instances {
+            instance.sync(&self.physics);
+        }
+        if let Some(player) = self.player.as_mut() {
+            player.sync(&self.physics);
+        }
+    }
+
+    /// Put the player's car back on good ground once it has left the world,
+    /// wedged itself or stayed on its roof for too long.
+    fn recover(&mut self, input: &DriveInput) {
+        let (Some(player), Some(terrain)) = (self.player.as_mut(), self.terrain.as_ref()) else {
+            return;
+        };
+        let trouble = player.recovery.check(
+            &self.physics,
+            &terrain.body,
+            &terrain.terrain.config,
+            &player.vehicle,
+            input,
+        );
+        if let Some(trouble) = trouble {
+            log::info!("respawn: {trouble:?}");
+            player
+                .recovery
+                .respawn(&mut self.physics, &terrain.body, &player.vehicle);
+            // Snap the camera to the new spot instead of sweeping across.
+            self.camera_follows = false;
+        }
+    }
+
+    /// Pull the player's triggers while Fire is held, then resolve this
+    /// step's hits on tracked bodies. Pack levels keep their ground as
+    /// loaded, so shells leave no craters here.
+    fn update_weapons(&mut self, input: &DriveInput) {
+        let Some(player) = self.player.as_mut() else {
+            return;
+        };
+        let weapons = &mut player.vehicle.weapons;
+        if input.fire {
+            for i in 0..weapons.mount_count() {
+                if weapons.fire(i, &mut self.physics, player.vehicle.chassis) {
+                    log::info!("fire: mount {i}, ammo {:?}", weapons.ammo(i));
+                }
             }
         }
+        let impacts = weapons.update(&mut self.physics, PHYSICS_DT);
+        let shielded = player
+            .recovery
+            .invulnerable()
+            .then(|| player.vehicle.bodies())
+            .unwrap_or_default();
+        for impact in impacts {
+            if impact.body.is_some_and(|body| shielded.contains(&body)) {
+                log::info!("hit while invulnerable");
+            } else if let Some(id) = self.damage.apply(&impact) {
+                log::info!("wrecked {id:?}");
+            }
+        }
+        self.shells.sync(&self.physics, &player.vehicle.weapons);
+    }
+
+    /// Do what the level's event `name` does.
+    fn fire(&mut self, name: &str) {
+        let Some(actions) = self.events.get(name).cloned() else {
+            log::warn!("Level has no event {name}");
+            return;
+        };
+        log::info!("Event {name}");
+        for action in actions {
+            match action {
+                EventActionDesc::Spawn(object) => {
+                    let transform = self.place(&object);
+                    self.object_ids.insert(object.id, self.instances.len());
+                    self.add_spawns(vec![Spawn {
+                        entity_id: object.entity_id,
+                        transform,
+                    }]);
+                }
+                EventActionDesc::Despawn(id) => match self.object_ids.get(&id) {
+                    Some(&index) => self.instances[index].despawn(&mut self.physics),
+                    None => log::warn!("Event {name} despawns {id}, which is not in the level"),
+                },
+                EventActionDesc::Message(text) => self.show_message(&text),
+                EventActionDesc::EndLevel { success } => {
+                    self.end_level(success);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Put `text` in the title bar, for want of anywhere else to show it.
+    fn show_message(&self, text: &str) {
+        log::info!("{text}");
+        self.window
+            .set_title(&format!("Vandals and Heroes: {text}"));
+    }
+
+    /// Stop the world with the level won or lost.
+    fn end_level(&mut self, success: bool) {
+        self.ended = Some(success);
+        self.jump_charge.cancel();
+        if let Some(player) = self.player.as_ref() {
+            player.vehicle.release(&mut self.physics);
+        }
+        self.show_message(if success {
+            "level complete"
+        } else {
+            "level failed"
+        });
+    }
+
+    fn redraw(&mut self) {
+        if !self.paused && self.ended.is_none() {
+            self.step();
+            self.follow_player();
+        }
 
         let terrain = &self.terrain.as_ref().unwrap();
 
-        let model_instances = self
-            .instances
-            .iter()
-            .filter_map(|instance| instance.model_instance.as_ref())
-            .collect();
+        let mut model_instances = Vec::new();
+        for instance in &self.instances {
+            instance.model_instances(&mut model_instances);
+        }
+        if let Some(player) = self.player.as_ref() {
+            player.model_instances(&mut model_instances);
+        }
+        model_instances.extend(self.shells.instances.iter());
 
         self.render.draw(
             &self.camera_controller.camera,
@@ -237,7 +778,7 @@ impl Game {
 }
 
 impl ObjectDesc {
-    fn load(&self, content: &ContentPack, loader: &mut Loader) -> ObjectTemplate {
+    fn load(&self, content: &PackSet, loader: &mut Loader) -> ObjectTemplate {
         let identity = Matrix4::identity();
         let model_desc = self
             .scene_path
@@ -249,9 +790,16 @@ impl ObjectDesc {
             .map(|model_desc| loader.load_model(model_desc))
             .map(Arc::new);
 
+        let script = self.script_path.as_ref().map(|path| {
+            let path = content.get_resource_path(path);
+            fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("Unable to read {}: {e}", path.display()))
+        });
+
         ObjectTemplate {
             desc: self.clone(),
             model,
+            script,
         }
     }
 }
@@ -260,6 +808,14 @@ impl Drop for Game {
     fn drop(&mut self) {
         self.render.wait_for_gpu();
         self.instances.clear();
+        self.shells.free(self.render.context());
+        if let Some(player) = self.player.as_ref() {
+            player.chassis.model.free(self.render.context());
+            // The wheels all share one model.
+            if let Some((_, wheel)) = player.wheels.first() {
+                wheel.model.free(self.render.context());
+            }
+        }
         for entity in self.templates.values_mut() {
             entity.deinit(self.render.context());
         }
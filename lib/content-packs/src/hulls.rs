@@ -0,0 +1,126 @@
+//! Convex decompositions of glTF meshes. V-HACD takes seconds on anything
+//! but a toy mesh, so the hulls are written beside the asset as
+//! `<name>.hulls.ron` the first time they are computed and read back from
+//! there while the asset and the hull budget stay the same.
+
+use rapier3d::math::Vec3;
+use rapier3d::parry::transformation::vhacd::{VHACD, VHACDParameters};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use vandals_and_heroes::{FNV_OFFSET, fnv1a};
+
+#[derive(Serialize, Deserialize)]
+struct HullCache {
+    /// FNV-1a of the asset file the hulls came from.
+    source_hash: u64,
+    max_hulls: u32,
+    /// Vertices of each hull.
+    hulls: Vec<Vec<[f32; 3]>>,
+}
+
+fn cache_path(asset: &Path) -> PathBuf {
+    asset.with_extension("hulls.ron")
+}
+
+/// Hash of the file at `asset`, or `0` when there is none to read.
+fn source_hash(asset: &Path) -> u64 {
+    std::fs::read(asset).map_or(0, |bytes| fnv1a(FNV_OFFSET, &bytes))
+}
+
+fn read_cache(path: &Path, source_hash: u64, max_hulls: u32) -> Option<Vec<Vec<Vec3>>> {
+    let cache: HullCache = ron::de::from_bytes(&std::fs::read(path).ok()?).ok()?;
+    (cache.source_hash == source_hash && cache.max_hulls == max_hulls).then(|| {
+        cache
+            .hulls
+            .into_iter()
+            .map(|hull| hull.into_iter().map(Vec3::from).collect())
+            .collect()
+    })
+}
+
+fn write_cache(
+    path: &Path,
+    source_hash: u64,
+    max_hulls: u32,
+    hulls: &[Vec<Vec3>],
+) -> Result<(), String> {
+    let cache = HullCache {
+        source_hash,
+        max_hulls,
+        hulls: hulls
+            .iter()
+            .map(|hull| hull.iter().map(|p| p.to_array()).collect())
+            .collect(),
+    };
+    let text = ron::ser::to_string(&cache).map_err(|e| e.to_string())?;
+    std::fs::write(path, text).map_err(|e| e.to_string())
+}
+
+/// The pieces last decomposed from `asset` with `max_hulls`, if they are
+/// cached and still current. Never runs V-HACD nor writes anything.
+pub fn cached_decomposition(asset: &Path, max_hulls: u32) -> Option<Vec<Vec<Vec3>>> {
+    read_cache(&cache_path(asset), source_hash(asset), max_hulls)
+}
+
+/// Up to `max_hulls` convex pieces covering the mesh loaded from `asset`,
+/// each as the points it wraps.
+pub fn convex_decomposition(
+    asset: &Path,
+    vertices: &[Vec3],
+    indices: &[[u32; 3]],
+    max_hulls: u32,
+) -> Vec<Vec<Vec3>> {
+    if let Some(hulls) = cached_decomposition(asset, max_hulls) {
+        return hulls;
+    }
+    let cache_path = cache_path(asset);
+    let source_hash = source_hash(asset);
+
+    log::info!(
+        "Decomposing {} into up to {max_hulls} hulls",
+        asset.display()
+    );
+    let params = VHACDParameters {
+        max_convex_hulls: max_hulls,
+        ..Default::default()
+    };
+    let hulls: Vec<Vec<Vec3>> = VHACD::decompose(&params, vertices, indices, false)
+        .compute_convex_hulls(params.convex_hull_downsampling)
+        .into_iter()
+        .map(|(points, _)| points)
+        .collect();
+
+    if let Err(e) = write_cache(&cache_path, source_hash, max_hulls, &hulls) {
+        log::warn!("Unable to cache hulls in {}: {e}", cache_path.display());
+    }
+    hulls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_holds_until_the_asset_or_budget_changes() {
+        let directory = std::env::temp_dir().join(format!("hulls-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let asset = directory.join("rock.glb");
+        std::fs::write(&asset, "rock").unwrap();
+        let hulls = vec![vec![Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z]];
+        write_cache(&cache_path(&asset), source_hash(&asset), 4, &hulls).unwrap();
+
+        // No mesh to decompose: the hulls can only come from the cache.
+        assert_eq!(convex_decomposition(&asset, &[], &[], 4), hulls);
+        assert!(
+            cached_decomposition(&asset, 8).is_none(),
+            "read back for another budget"
+        );
+        std::fs::write(&asset, "boulder").unwrap();
+        assert!(
+            cached_decomposition(&asset, 4).is_none(),
+            "read back for an edited asset"
+        );
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
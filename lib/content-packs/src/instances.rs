@@ -1,13 +1,347 @@
-use vandals_and_heroes::{ModelInstance, PhysicsBodyHandle, Terrain, TerrainBody};
+use crate::definitions::SurfaceDesc;
+use crate::scripting::ScriptInstance;
+use nalgebra::{Matrix3, Rotation3, UnitQuaternion, Vector3};
+use rapier3d::dynamics::{ImpulseJointHandle, RigidBodyHandle};
+use rapier3d::geometry::ColliderHandle;
+use rapier3d::math::Vec3;
+use std::collections::HashMap;
+use vandals_and_heroes::{
+    ModelInstance, Physics, PhysicsBodyHandle, Recovery, Terrain, TerrainBody, Vehicle, config, tin,
+};
+
+/// Every body in `groups` keyed to the first of its group: one group per
+/// object or car, which a trigger then counts once.
+pub fn body_owners(
+    groups: impl IntoIterator<Item = Vec<RigidBodyHandle>>,
+) -> HashMap<RigidBodyHandle, RigidBodyHandle> {
+    let mut owners = HashMap::new();
+    for bodies in groups {
+        if let Some(&owner) = bodies.first() {
+            owners.extend(bodies.into_iter().map(|body| (body, owner)));
+        }
+    }
+    owners
+}
 
 pub struct Object {
     pub model_instance: Option<ModelInstance>,
     pub body: Option<PhysicsBodyHandle>,
     pub transform: nalgebra::Isometry3<f32>,
-    // TODO: script instance
+    pub script: Option<ScriptInstance>,
+    /// What a prefab brought along.
+    pub children: Vec<Child>,
+    /// Fires the level's events as bodies pass through.
+    pub trigger: Option<Trigger>,
+}
+
+/// A sensor on an object's body that fires level events.
+pub struct Trigger {
+    pub collider: ColliderHandle,
+    pub on_enter: Option<String>,
+    pub on_exit: Option<String>,
+    /// Owners (see [`body_owners`]) of the bodies that were in it at the
+    /// last check.
+    pub inside: Vec<RigidBodyHandle>,
+}
+
+/// An object attached to another.
+pub struct Child {
+    pub object: Object,
+    /// Pose relative to the parent, kept by children without a body.
+    pub offset: nalgebra::Isometry3<f32>,
+    pub joint: Option<ImpulseJointHandle>,
+}
+
+impl Object {
+    /// Catch up with the physics: objects with a body go where it went,
+    /// and children without one keep their place on the parent.
+    pub fn sync(&mut self, physics: &Physics) {
+        if let Some(ref body) = self.body {
+            self.transform = physics.get_transform(body.rigid_body_handle);
+        }
+        if let Some(ref mut model_instance) = self.model_instance {
+            model_instance.transform = self.transform;
+        }
+        for child in &mut self.children {
+            if child.object.body.is_none() {
+                child.object.transform = self.transform * child.offset;
+            }
+            child.object.sync(physics);
+        }
+    }
+
+    /// Where the object is now, which for one with a body may be ahead of
+    /// `transform`.
+    pub fn pose(&self, physics: &Physics) -> nalgebra::Isometry3<f32> {
+        match self.body {
+            Some(ref body) => physics.get_transform(body.rigid_body_handle),
+            None => self.transform,
+        }
+    }
+
+    /// Move the object to `pose`, taking its children along.
+    pub fn set_pose(&mut self, physics: &mut Physics, pose: nalgebra::Isometry3<f32>) {
+        let moved = pose * self.pose(physics).inverse();
+        self.transform = pose;
+        if let Some(ref body) = self.body {
+            physics.set_body_pose(body.rigid_body_handle, pose.into());
+        }
+        for child in &mut self.children {
+            let child_pose = moved * child.object.pose(physics);
+            child.object.set_pose(physics, child_pose);
+        }
+    }
+
+    /// Bodies of the object and all its children.
+    pub fn bodies(&self, out: &mut Vec<RigidBodyHandle>) {
+        out.extend(self.body.as_ref().map(|body| body.rigid_body_handle));
+        for child in &self.children {
+            child.object.bodies(out);
+        }
+    }
+
+    /// Events fired by bodies entering and leaving the triggers of the
+    /// object and its children since the last call, in order. Bodies are
+    /// counted by their entry in `owners`, so something made of several
+    /// fires once.
+    pub fn trigger_events(
+        &mut self,
+        physics: &Physics,
+        owners: &HashMap<RigidBodyHandle, RigidBodyHandle>,
+        out: &mut Vec<String>,
+    ) {
+        if let Some(ref mut trigger) = self.trigger {
+            let mut inside = Vec::new();
+            for body in physics.sensor_overlaps(trigger.collider) {
+                let owner = owners.get(&body).copied().unwrap_or(body);
+                if !inside.contains(&owner) {
+                    inside.push(owner);
+                }
+            }
+            for body in &inside {
+                if !trigger.inside.contains(body) {
+                    out.extend(trigger.on_enter.clone());
+                }
+            }
+            for body in &trigger.inside {
+                if !inside.contains(body) {
+                    out.extend(trigger.on_exit.clone());
+                }
+            }
+            trigger.inside = inside;
+        }
+        for child in &mut self.children {
+            child.object.trigger_events(physics, owners, out);
+        }
+    }
+
+    /// Take the object and its children out of the level, bodies and all.
+    /// What is left draws, runs and fires nothing, but keeps its id and
+    /// last `transform`.
+    pub fn despawn(&mut self, physics: &mut Physics) {
+        let mut bodies = Vec::new();
+        self.bodies(&mut bodies);
+        for body in bodies {
+            physics.remove_rigid_body(body);
+        }
+        self.model_instance = None;
+        self.body = None;
+        self.script = None;
+        self.children.clear();
+        self.trigger = None;
+    }
+
+    /// What to draw for the object and all its children.
+    pub fn model_instances<'a>(&'a self, out: &mut Vec<&'a ModelInstance>) {
+        out.extend(self.model_instance.as_ref());
+        for child in &self.children {
+            child.object.model_instances(out);
+        }
+    }
+}
+
+/// The car the player drives.
+pub struct PlayerCar {
+    pub vehicle: Vehicle,
+    pub chassis: ModelInstance,
+    /// Only the steered wheels are drawn on their own, as in the game: the
+    /// body model has the others baked in.
+    pub wheels: Vec<(RigidBodyHandle, ModelInstance)>,
+    /// Puts the car back on the ground when it flips or gets stuck.
+    pub recovery: Recovery,
+}
+
+impl PlayerCar {
+    /// Catch up with the physics.
+    pub fn sync(&mut self, physics: &Physics) {
+        self.chassis.transform = physics.get_transform(self.vehicle.chassis);
+        for &mut (body, ref mut wheel) in &mut self.wheels {
+            wheel.transform = physics.get_transform(body);
+        }
+    }
+
+    /// What to draw for the car.
+    pub fn model_instances<'a>(&'a self, out: &mut Vec<&'a ModelInstance>) {
+        out.push(&self.chassis);
+        out.extend(self.wheels.iter().map(|(_, wheel)| wheel));
+    }
 }
 
 pub struct TerrainObject {
     pub terrain: Terrain,
     pub body: TerrainBody,
+    /// The height map the ground was built from.
+    pub heights: Vec<u8>,
+    pub mapping: tin::Mapping,
+}
+
+fn na(v: Vec3) -> Vector3<f32> {
+    Vector3::new(v.x, v.y, v.z)
+}
+
+impl TerrainObject {
+    /// Where the ground lies under `point`, read off the height map so it
+    /// holds before physics has stepped; `None` past the ends of a cylinder.
+    pub fn ground(&self, point: Vec3) -> Option<Vec3> {
+        let [x, y] = self.mapping.locate(point.to_array());
+        let axial = 0.0..=self.mapping.height as f32;
+        if self.mapping.shape == config::WorldShape::Cylinder && !axial.contains(&y) {
+            return None;
+        }
+        let height = self.mapping.height_at(&self.heights, x, y);
+        Some(Vec3::from(self.mapping.embed(x, y, height)))
+    }
+
+    /// The top of the world's ground range at surface coordinates `(u, v)`,
+    /// and the frame there with +Y up and +Z towards increasing `v`.
+    pub fn surface_frame(&self, u: f32, v: f32) -> (Vec3, UnitQuaternion<f32>) {
+        const STEP: f32 = 1e-3;
+        let mapping = tin::Mapping::new(&self.terrain.config, 1, 1);
+        let point = |u: f32, v: f32| Vec3::from(mapping.embed(u, v, 255.0));
+        let top = point(u, v);
+        let up = na(self.body.up(top));
+        let along = na(point(u, v + STEP) - point(u, v - STEP));
+        let north = along - up * along.dot(&up);
+        let north = if north.norm() > 1e-6 {
+            north.normalize()
+        } else {
+            // At a pole of the sphere every way is south: any will do.
+            let side = if up.x.abs() < 0.9 {
+                Vector3::x()
+            } else {
+                Vector3::y()
+            };
+            up.cross(&side).normalize()
+        };
+        let rotation = UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(
+            Matrix3::from_columns(&[up.cross(&north), up, north]),
+        ));
+        (top, rotation)
+    }
+
+    /// The pose of an object placed at `surface`, standing on the ground
+    /// there.
+    pub fn surface_pose(&self, surface: &SurfaceDesc) -> nalgebra::Isometry3<f32> {
+        let (top, frame) = self.surface_frame(surface.u, surface.v);
+        let ground = self.ground(top).unwrap_or(top);
+        let up = self.body.up(top);
+        let rotation = UnitQuaternion::from_axis_angle(
+            &nalgebra::Unit::new_normalize(na(up)),
+            surface.heading,
+        ) * frame;
+        let (x, z) = surface.offset;
+        if x != 0.0 || z != 0.0 {
+            // Step over, then stand where the step lands as if placed there.
+            let step = rotation * Vector3::new(x, 0.0, z);
+            let landing = ground + Vec3::new(step.x, step.y, step.z);
+            let [u, v] = self.mapping.locate(landing.to_array());
+            return self.surface_pose(&SurfaceDesc {
+                u: u / self.mapping.width as f32,
+                v: v / self.mapping.height as f32,
+                offset: (0.0, 0.0),
+                ..surface.clone()
+            });
+        }
+        nalgebra::Isometry3::from_parts(na(ground + up * surface.altitude).into(), rotation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vandals_and_heroes::Texture;
+
+    #[test]
+    fn surface_pose_stands_on_the_ground() {
+        let config = config::Map {
+            radius: 10.0..15.0,
+            length: 20.0,
+            density: 1.0,
+            ..Default::default()
+        };
+        // Rising around the world, a step of 16 per texel.
+        let (width, height) = (16, 8);
+        let heights: Vec<u8> = (0..width * height)
+            .map(|i| (i % width * 16) as u8)
+            .collect();
+        let mesh = tin::build(&heights, width, height, &config, 1.0);
+        let mut physics = Physics::default();
+        let body = physics.create_terrain_mesh(&config, &mesh);
+        let terrain = TerrainObject {
+            mapping: tin::Mapping::new(&config, width, height),
+            terrain: Terrain {
+                texture: Texture::default(),
+                env_texture: None,
+                config,
+                chunks: Vec::new(),
+            },
+            body,
+            heights,
+        };
+
+        // Halfway between the texels of heights 48 and 64.
+        let surface = SurfaceDesc {
+            u: 0.25,
+            v: 0.5,
+            altitude: 2.0,
+            heading: 0.0,
+            offset: (0.0, 0.0),
+        };
+        let pose = terrain.surface_pose(&surface);
+        let p = pose.translation.vector;
+        let ground = terrain.mapping.ground_radius(56.0 / 255.0);
+        let radius = p.xy().norm();
+        assert!(
+            (radius - ground - 2.0).abs() < 1e-3,
+            "placed at radius {radius}"
+        );
+
+        // A metre along the world, where the ramp is just as high.
+        let stepped = terrain.surface_pose(&SurfaceDesc {
+            offset: (0.0, 1.0),
+            ..surface.clone()
+        });
+        let q = stepped.translation.vector;
+        assert!(
+            ((q - p).norm() - 1.0).abs() < 1e-3,
+            "stepped {} m",
+            (q - p).norm()
+        );
+        assert!(
+            (q.xy().norm() - radius).abs() < 1e-3,
+            "stepped to radius {}",
+            q.xy().norm()
+        );
+
+        // Where the ground turns out to be once physics has caught up.
+        physics.step();
+        let position = Vec3::new(p.x, p.y, p.z);
+        let up = terrain.body.up(position);
+        let hit = physics.cast_ray(position, -up, 5.0, &[]).unwrap();
+        assert!(
+            (hit.distance - 2.0).abs() < 0.05,
+            "{} above the ground",
+            hit.distance
+        );
+    }
 }
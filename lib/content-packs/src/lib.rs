@@ -0,0 +1,12 @@
+//! Content packs for Vandals and Heroes: loading a stack of packs, checking
+//! them, and the game that plays their levels.
+
+mod camera_controller;
+pub mod content_pack;
+pub mod definitions;
+pub mod game;
+mod hulls;
+mod instances;
+mod scripting;
+mod templates;
+pub mod validate;
@@ -1,19 +1,48 @@
 extern crate core;
 
-use crate::game::Game;
+use content_packs::content_pack::Severity;
+use content_packs::game::Game;
+use content_packs::validate;
 use std::path::Path;
 
-mod camera_controller;
-mod content_pack;
-mod definitions;
-mod game;
-mod instances;
-mod templates;
+/// `content-packs validate [DIRECTORY]`: print every problem in the packs
+/// `DIRECTORY/packs.ron` lists, and fail if any is an error.
+fn validate(directory: &Path) -> std::process::ExitCode {
+    let diagnostics = validate::validate(directory);
+    for diagnostic in &diagnostics {
+        println!("{diagnostic}");
+    }
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    println!(
+        "{errors} error(s), {} warning(s)",
+        diagnostics.len() - errors
+    );
+    if errors > 0 {
+        std::process::ExitCode::FAILURE
+    } else {
+        std::process::ExitCode::SUCCESS
+    }
+}
 
-fn main() {
+fn main() -> std::process::ExitCode {
     env_logger::init();
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        None => {}
+        Some("validate") => {
+            let directory = args.next().unwrap_or_else(|| "data/packs".to_string());
+            return validate(Path::new(&directory));
+        }
+        Some(other) => {
+            panic!("Unknown command {other}; usage: content-packs [validate [DIRECTORY]]")
+        }
+    }
+
     let event_loop = winit::event_loop::EventLoop::new().unwrap();
-    let mut game = Game::new(&event_loop, Path::new("data/packs/root"));
+    let mut game = Game::new(&event_loop, Path::new("data/packs"));
     game.load_level("main");
 
     #[allow(deprecated)] //TODO
@@ -36,4 +65,5 @@ fn main() {
             _ => {}
         })
         .unwrap();
+    std::process::ExitCode::SUCCESS
 }
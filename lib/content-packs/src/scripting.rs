@@ -0,0 +1,664 @@
+//! Lua behaviour for content-pack entities. An entity's `script_path` names
+//! a Lua file that is run once for every object made from it, each time in
+//! a global table of its own, and may define any of these hooks:
+//!
+//! - `on_spawn(id)`: right after the object enters the level,
+//! - `on_tick(id, dt)`: after every physics step,
+//! - `on_contact(id, other)`: when the object's body starts touching another
+//!   object's (`other` is that object's id) or the ground (`other` is nil).
+//!
+//! Objects are numbered from 0 in the order they entered the level. Hooks
+//! reach the level through the `world` table:
+//!
+//! - `world.position(id)` returns `x, y, z`,
+//!   `world.set_position(id, x, y, z)` moves the object,
+//! - `world.rotation(id)` returns `roll, pitch, yaw` (radians, as in a level
+//!   transform), `world.set_rotation(id, roll, pitch, yaw)` turns it,
+//! - `world.apply_impulse(id, x, y, z)` pushes its body,
+//! - `world.spawn(entity_id, x, y, z [, roll, pitch, yaw])` returns the id
+//!   of a new object, which appears once the current hook returns,
+//! - `world.terrain_height(x, y, z)` is how far from the world's axis the
+//!   ground lies under that point, or nil off the map,
+//! - `world.surface_position(u, v [, altitude [, x, z]])` returns the
+//!   `x, y, z` of the point `altitude` above the ground at surface
+//!   coordinates `u, v`, stepped `x, z` metres over the ground (as in a
+//!   level's `surface` placement), or nil without a height map,
+//! - `world.log(message)` writes `message` to the game's log.
+//!
+//! Scripts get the `table`, `string`, `math` and `utf8` libraries, read-only,
+//! but nothing that touches files or loads more code. A hook that runs too
+//! long is aborted, as is one that takes the Lua state over its memory
+//! limit. An error in a hook is logged and the game carries on.
+
+use crate::content_pack::PackSet;
+use crate::definitions::SurfaceDesc;
+use crate::instances::{Object, TerrainObject};
+use mlua::{Function, HookTriggers, Lua, LuaOptions, RegistryKey, StdLib, Table, Value, Variadic};
+use rapier3d::dynamics::RigidBodyHandle;
+use rapier3d::math::Vec3;
+use std::cell::{Cell, RefCell};
+use std::ops::Range;
+use std::rc::Rc;
+use vandals_and_heroes::Physics;
+
+/// Base-library functions that read files or compile code at run time.
+const UNSAFE_GLOBALS: [&str; 3] = ["dofile", "loadfile", "load"];
+/// Libraries every script sees through a read-only proxy of its own.
+const LIBRARIES: [&str; 4] = ["table", "string", "math", "utf8"];
+/// Bytes all scripts together may allocate.
+const MEMORY_LIMIT: usize = 64 << 20;
+/// Lua instructions one hook, or a script's top level, may run.
+const INSTRUCTION_LIMIT: u32 = 10_000_000;
+/// Instructions between checks against `INSTRUCTION_LIMIT`.
+const HOOK_INTERVAL: u32 = 10_000;
+
+/// `u, v` and the optional `altitude, x, z` of `world.surface_position`.
+type SurfaceArgs = (f32, f32, Option<f32>, Option<f32>, Option<f32>);
+
+/// The script running on one object.
+pub struct ScriptInstance {
+    /// The object's own global table, holding its hooks and state.
+    env: RegistryKey,
+    /// Bodies the object's was touching after the last tick, so
+    /// `on_contact` only fires as a contact begins.
+    touching: Vec<RigidBodyHandle>,
+}
+
+/// An object a script spawned, to be created once the hook returns.
+pub struct Spawn {
+    pub entity_id: String,
+    pub transform: nalgebra::Isometry3<f32>,
+}
+
+/// The part of the level hooks can see and change.
+pub struct World<'a> {
+    pub content_pack: &'a PackSet,
+    pub physics: &'a mut Physics,
+    pub terrain: Option<&'a TerrainObject>,
+    pub objects: &'a mut Vec<Object>,
+    /// Filled by `world.spawn`; the ids handed out continue on from
+    /// `objects`, so these must be created in order.
+    pub spawns: Vec<Spawn>,
+}
+
+fn no_object(id: usize) -> mlua::Error {
+    mlua::Error::RuntimeError(format!("no object {id}"))
+}
+
+impl World<'_> {
+    fn object(&self, id: usize) -> mlua::Result<&Object> {
+        self.objects.get(id).ok_or_else(|| no_object(id))
+    }
+
+    fn pose(&self, id: usize) -> mlua::Result<nalgebra::Isometry3<f32>> {
+        Ok(self.object(id)?.pose(self.physics))
+    }
+
+    fn set_pose(&mut self, id: usize, pose: nalgebra::Isometry3<f32>) -> mlua::Result<()> {
+        let object = self.objects.get_mut(id).ok_or_else(|| no_object(id))?;
+        object.set_pose(self.physics, pose);
+        Ok(())
+    }
+
+    fn apply_impulse(&mut self, id: usize, impulse: Vec3) -> mlua::Result<()> {
+        let object = self.object(id)?;
+        let Some(ref body) = object.body else {
+            return Err(mlua::Error::RuntimeError(format!(
+                "object {id} has no body to push"
+            )));
+        };
+        let handle = body.rigid_body_handle;
+        self.physics.apply_impulse(handle, impulse);
+        Ok(())
+    }
+
+    fn spawn(
+        &mut self,
+        entity_id: String,
+        transform: nalgebra::Isometry3<f32>,
+    ) -> mlua::Result<usize> {
+        match self.content_pack.get_entity_by_id(&entity_id) {
+            None => {
+                return Err(mlua::Error::RuntimeError(format!(
+                    "no entity {entity_id:?} in the pack"
+                )));
+            }
+            Some(entity) if entity.vehicle.is_some() => {
+                return Err(mlua::Error::RuntimeError(format!(
+                    "entity {entity_id:?} is a vehicle, which only a level's player start places"
+                )));
+            }
+            Some(_) => {}
+        }
+        self.spawns.push(Spawn {
+            entity_id,
+            transform,
+        });
+        Ok(self.objects.len() + self.spawns.len() - 1)
+    }
+
+    /// Distance from the world's axis to the ground under `point`.
+    fn terrain_height(&self, point: Vec3) -> Option<f32> {
+        let terrain = self.terrain?;
+        let ground = terrain.ground(point)?;
+        Some((ground - terrain.body.gravity_anchor(point)).length())
+    }
+
+    /// The point `surface.altitude` above the ground at `surface`.
+    fn surface_position(&self, surface: &SurfaceDesc) -> Option<nalgebra::Vector3<f32>> {
+        let terrain = self.terrain?;
+        let pose = terrain.surface_pose(surface);
+        Some(pose.translation.vector)
+    }
+
+    /// Bodies that started touching object `id`'s since the last call.
+    fn new_contacts(&mut self, id: usize) -> Vec<RigidBodyHandle> {
+        let object = &mut self.objects[id];
+        let (Some(body), Some(script)) = (object.body.as_ref(), object.script.as_mut()) else {
+            return Vec::new();
+        };
+        let touching = self.physics.touching_bodies(body.rigid_body_handle);
+        let new = touching
+            .iter()
+            .filter(|other| !script.touching.contains(other))
+            .copied()
+            .collect();
+        script.touching = touching;
+        new
+    }
+
+    /// What `on_contact` is told it touched: `Some(id)` for an object (or
+    /// a part of its prefab), `Some(None)` (nil) for the ground and `None`
+    /// for anything else.
+    fn contact_other(&self, body: RigidBodyHandle) -> Option<Option<usize>> {
+        let object = self.objects.iter().position(|object| {
+            let mut bodies = Vec::new();
+            object.bodies(&mut bodies);
+            bodies.contains(&body)
+        });
+        if object.is_some() {
+            return Some(object);
+        }
+        self.terrain
+            .is_some_and(|terrain| terrain.body.body() == body)
+            .then_some(None)
+    }
+}
+
+/// The Lua state every object's script runs in.
+pub struct ScriptRuntime {
+    lua: Lua,
+    /// Instructions the running hook has left, counted down by the Lua hook.
+    budget: Rc<Cell<u32>>,
+}
+
+impl Default for ScriptRuntime {
+    fn default() -> Self {
+        let libs = StdLib::TABLE | StdLib::STRING | StdLib::MATH | StdLib::UTF8;
+        let lua = Lua::new_with(libs, LuaOptions::default()).expect("Unable to start Lua");
+        let sandbox = || -> mlua::Result<()> {
+            for name in UNSAFE_GLOBALS.into_iter().chain(["_G"]) {
+                lua.globals().set(name, Value::Nil)?;
+            }
+            // Strings index the real `string` table; keep it out of reach.
+            lua.load("getmetatable('').__metatable = false").exec()?;
+            lua.set_memory_limit(MEMORY_LIMIT)?;
+            Ok(())
+        };
+        sandbox().expect("Unable to sandbox Lua");
+        let budget = Rc::new(Cell::new(INSTRUCTION_LIMIT));
+        let left = budget.clone();
+        let triggers = HookTriggers::new().every_nth_instruction(HOOK_INTERVAL);
+        lua.set_hook(triggers, move |_, _| {
+            match left.get().checked_sub(HOOK_INTERVAL) {
+                Some(rest) => {
+                    left.set(rest);
+                    Ok(())
+                }
+                None => Err(mlua::Error::RuntimeError(format!(
+                    "script ran past {INSTRUCTION_LIMIT} instructions"
+                ))),
+            }
+        });
+        Self { lua, budget }
+    }
+}
+
+impl ScriptRuntime {
+    /// Run `source` for a new object. Its top level should only define
+    /// hooks and state: `world` is not there until the first hook.
+    pub fn instantiate(&self, name: &str, source: &str) -> mlua::Result<ScriptInstance> {
+        let env = self.lua.create_table()?;
+        for name in LIBRARIES {
+            let library: Table = self.lua.globals().get(name)?;
+            env.set(name, self.read_only(library)?)?;
+        }
+        env.set("_G", env.clone())?;
+        let meta = self.lua.create_table()?;
+        meta.set("__index", self.lua.globals())?;
+        meta.set("__metatable", false)?;
+        env.set_metatable(Some(meta));
+        self.budget.set(INSTRUCTION_LIMIT);
+        self.lua
+            .load(source)
+            .set_name(name)
+            .set_environment(env.clone())
+            .exec()?;
+        Ok(ScriptInstance {
+            env: self.lua.create_registry_value(env)?,
+            touching: Vec::new(),
+        })
+    }
+
+    /// A table reading through to `table` that refuses writes, so a script
+    /// cannot change what the others see.
+    fn read_only<'lua>(&'lua self, table: Table<'lua>) -> mlua::Result<Table<'lua>> {
+        let refuse = self
+            .lua
+            .create_function(|_, _: mlua::MultiValue| -> mlua::Result<()> {
+                Err(mlua::Error::RuntimeError("library is read-only".into()))
+            })?;
+        let proxy = self.lua.create_table()?;
+        let meta = self.lua.create_table()?;
+        meta.set("__index", table)?;
+        meta.set("__newindex", refuse)?;
+        meta.set("__metatable", false)?;
+        proxy.set_metatable(Some(meta));
+        Ok(proxy)
+    }
+
+    /// Call `on_spawn` on the objects in `ids`.
+    pub fn spawned(&self, world: &mut World, ids: Range<usize>) {
+        self.with_world(world, |world| {
+            for id in ids {
+                self.call(world, id, "on_spawn", |hook| hook.call(id));
+            }
+        });
+    }
+
+    /// Call `on_contact` for every contact the last step began, then
+    /// `on_tick` on every object.
+    pub fn tick(&self, world: &mut World) {
+        let dt = world.physics.dt();
+        self.with_world(world, |world| {
+            let count = world.borrow().objects.len();
+            for id in 0..count {
+                let contacts = world.borrow_mut().new_contacts(id);
+                for body in contacts {
+                    let Some(other) = world.borrow().contact_other(body) else {
+                        continue;
+                    };
+                    self.call(world, id, "on_contact", |hook| hook.call((id, other)));
+                }
+            }
+            for id in 0..count {
+                self.call(world, id, "on_tick", |hook| hook.call((id, dt)));
+            }
+        });
+    }
+
+    /// Look up `hook` in object `id`'s script and run it through `call`,
+    /// logging whatever goes wrong.
+    fn call<'lua>(
+        &'lua self,
+        world: &RefCell<&mut World>,
+        id: usize,
+        hook: &str,
+        call: impl FnOnce(Function<'lua>) -> mlua::Result<()>,
+    ) {
+        let env = match world.borrow().objects[id].script {
+            Some(ref script) => self.lua.registry_value::<Table>(&script.env),
+            None => return,
+        };
+        self.budget.set(INSTRUCTION_LIMIT);
+        let result = env
+            .and_then(|env| env.raw_get::<_, Option<Function>>(hook))
+            .and_then(|function| function.map_or(Ok(()), call));
+        if let Err(e) = result {
+            log::warn!("Script of object {id} failed in {hook}: {e}");
+        }
+    }
+
+    /// Publish the `world` table, backed by `world`, for the duration of
+    /// `body`.
+    fn with_world<'w>(&self, world: &mut World<'w>, body: impl FnOnce(&RefCell<&mut World<'w>>)) {
+        let world = RefCell::new(world);
+        let result = self.lua.scope(|scope| {
+            let api = self.lua.create_table()?;
+            api.set(
+                "position",
+                scope.create_function(|_, id: usize| {
+                    let t = world.borrow().pose(id)?.translation.vector;
+                    Ok((t.x, t.y, t.z))
+                })?,
+            )?;
+            api.set(
+                "set_position",
+                scope.create_function(|_, (id, x, y, z): (usize, f32, f32, f32)| {
+                    let mut world = world.borrow_mut();
+                    let mut pose = world.pose(id)?;
+                    pose.translation = nalgebra::Translation3::new(x, y, z);
+                    world.set_pose(id, pose)
+                })?,
+            )?;
+            api.set(
+                "rotation",
+                scope.create_function(|_, id: usize| {
+                    Ok(world.borrow().pose(id)?.rotation.euler_angles())
+                })?,
+            )?;
+            api.set(
+                "set_rotation",
+                scope.create_function(|_, (id, roll, pitch, yaw): (usize, f32, f32, f32)| {
+                    let mut world = world.borrow_mut();
+                    let mut pose = world.pose(id)?;
+                    pose.rotation = nalgebra::UnitQuaternion::from_euler_angles(roll, pitch, yaw);
+                    world.set_pose(id, pose)
+                })?,
+            )?;
+            api.set(
+                "apply_impulse",
+                scope.create_function(|_, (id, x, y, z): (usize, f32, f32, f32)| {
+                    world.borrow_mut().apply_impulse(id, Vec3::new(x, y, z))
+                })?,
+            )?;
+            api.set(
+                "spawn",
+                scope.create_function(
+                    |_,
+                     (entity_id, x, y, z, roll, pitch, yaw): (
+                        String,
+                        f32,
+                        f32,
+                        f32,
+                        Option<f32>,
+                        Option<f32>,
+                        Option<f32>,
+                    )| {
+                        let transform = nalgebra::Isometry3::from_parts(
+                            nalgebra::Translation3::new(x, y, z),
+                            nalgebra::UnitQuaternion::from_euler_angles(
+                                roll.unwrap_or_default(),
+                                pitch.unwrap_or_default(),
+                                yaw.unwrap_or_default(),
+                            ),
+                        );
+                        world.borrow_mut().spawn(entity_id, transform)
+                    },
+                )?,
+            )?;
+            api.set(
+                "terrain_height",
+                scope.create_function(|_, (x, y, z): (f32, f32, f32)| {
+                    Ok(world.borrow().terrain_height(Vec3::new(x, y, z)))
+                })?,
+            )?;
+            api.set(
+                "surface_position",
+                scope.create_function(|_, (u, v, altitude, x, z): SurfaceArgs| {
+                    let surface = SurfaceDesc {
+                        u,
+                        v,
+                        altitude: altitude.unwrap_or_default(),
+                        heading: 0.0,
+                        offset: (x.unwrap_or_default(), z.unwrap_or_default()),
+                    };
+                    let position = world.borrow().surface_position(&surface);
+                    // Nothing, so `x` comes out nil.
+                    Ok(position
+                        .map_or_else(Variadic::new, |p| Variadic::from_iter([p.x, p.y, p.z])))
+                })?,
+            )?;
+            api.set(
+                "log",
+                scope.create_function(|_, message: String| {
+                    log::info!("{message}");
+                    Ok(())
+                })?,
+            )?;
+            self.lua.globals().set("world", api)?;
+            body(&world);
+            Ok(())
+        });
+        if let Err(e) = result {
+            log::error!("Unable to run scripts: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::definitions::ObjectDesc;
+    use crate::templates::ObjectTemplate;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    /// An object of entity `desc` running `script`, at `pose`.
+    fn object(
+        packs: &PackSet,
+        physics: &mut Physics,
+        scripts: &ScriptRuntime,
+        desc: ObjectDesc,
+        script: &str,
+        pose: nalgebra::Isometry3<f32>,
+    ) -> Object {
+        let template = ObjectTemplate {
+            model: None,
+            script: Some(script.to_string()),
+            desc,
+        };
+        template.instantiate(&HashMap::new(), packs, physics, scripts, pose)
+    }
+
+    /// An object with nothing but `script`.
+    fn marker(
+        packs: &PackSet,
+        physics: &mut Physics,
+        scripts: &ScriptRuntime,
+        script: &str,
+    ) -> Object {
+        let desc = ron::from_str(r#"(id: "marker")"#).unwrap();
+        object(
+            packs,
+            physics,
+            scripts,
+            desc,
+            script,
+            nalgebra::Isometry3::identity(),
+        )
+    }
+
+    /// The global `name` of `object`'s script.
+    fn global<'lua, T: mlua::FromLua<'lua>>(
+        scripts: &'lua ScriptRuntime,
+        object: &Object,
+        name: &str,
+    ) -> T {
+        let env = &object.script.as_ref().unwrap().env;
+        let env: Table = scripts.lua.registry_value(env).unwrap();
+        env.raw_get(name).unwrap()
+    }
+
+    fn world<'a>(
+        packs: &'a PackSet,
+        physics: &'a mut Physics,
+        objects: &'a mut Vec<Object>,
+    ) -> World<'a> {
+        World {
+            content_pack: packs,
+            physics,
+            terrain: None,
+            objects,
+            spawns: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn hooks_get_their_ids() {
+        let packs = PackSet::new(Path::new("../../data/packs")).unwrap();
+        let scripts = ScriptRuntime::default();
+        let mut physics = Physics::default();
+        let source = "function on_spawn(id) spawned = id end
+            function on_tick(id, dt) ticked = id; step = dt end";
+        let mut objects: Vec<Object> = (0..3)
+            .map(|_| marker(&packs, &mut physics, &scripts, source))
+            .collect();
+        let dt = physics.dt();
+        let mut world = world(&packs, &mut physics, &mut objects);
+        scripts.spawned(&mut world, 1..3);
+        scripts.tick(&mut world);
+
+        for (id, object) in objects.iter().enumerate() {
+            let spawned: Option<usize> = global(&scripts, object, "spawned");
+            assert_eq!(spawned, (id > 0).then_some(id));
+            assert_eq!(global::<usize>(&scripts, object, "ticked"), id);
+            assert_eq!(global::<f32>(&scripts, object, "step"), dt);
+        }
+    }
+
+    #[test]
+    fn contact_names_the_other_object() {
+        let packs = PackSet::new(Path::new("../../data/packs")).unwrap();
+        let scripts = ScriptRuntime::default();
+        let mut physics = Physics::default();
+        let source = "function on_contact(id, other) me = id; other_id = other end";
+        let entity = |id: &str| packs.get_entity_by_id(id).unwrap().clone();
+        let mut objects = vec![
+            object(
+                &packs,
+                &mut physics,
+                &scripts,
+                entity("floor"),
+                source,
+                nalgebra::Isometry3::identity(),
+            ),
+            object(
+                &packs,
+                &mut physics,
+                &scripts,
+                entity("cube"),
+                source,
+                nalgebra::Isometry3::translation(0.0, 0.5, 0.0),
+            ),
+        ];
+        let cube = objects[1].body.as_ref().unwrap().rigid_body_handle;
+        physics.set_linvel(cube, Vec3::new(0.0, -3.0, 0.0));
+        for _ in 0..60 {
+            physics.step();
+            scripts.tick(&mut world(&packs, &mut physics, &mut objects));
+        }
+
+        assert_eq!(global::<usize>(&scripts, &objects[0], "me"), 0);
+        assert_eq!(global::<usize>(&scripts, &objects[0], "other_id"), 1);
+        assert_eq!(global::<usize>(&scripts, &objects[1], "me"), 1);
+        assert_eq!(global::<usize>(&scripts, &objects[1], "other_id"), 0);
+    }
+
+    #[test]
+    fn spawned_ids_match_the_objects() {
+        let packs = PackSet::new(Path::new("../../data/packs")).unwrap();
+        let scripts = ScriptRuntime::default();
+        let mut physics = Physics::default();
+        let source = r#"function on_spawn(id)
+                first = world.spawn("cube", 0, 1, 0)
+                second = world.spawn("cube", 0, 2, 0)
+            end"#;
+        let mut objects = vec![
+            marker(&packs, &mut physics, &scripts, ""),
+            marker(&packs, &mut physics, &scripts, source),
+        ];
+        let mut world = world(&packs, &mut physics, &mut objects);
+        scripts.spawned(&mut world, 1..2);
+        // As the game adds them, in order.
+        let cube = ObjectTemplate {
+            model: None,
+            script: None,
+            desc: packs.get_entity_by_id("cube").unwrap().clone(),
+        };
+        for spawn in std::mem::take(&mut world.spawns) {
+            let object = cube.instantiate(
+                &HashMap::new(),
+                &packs,
+                world.physics,
+                &scripts,
+                spawn.transform,
+            );
+            world.objects.push(object);
+        }
+
+        for (name, height) in [("first", 1.0), ("second", 2.0)] {
+            let id: usize = global(&scripts, &objects[1], name);
+            let y = objects[id].pose(&physics).translation.y;
+            assert_eq!(y, height, "{name} is object {id}");
+        }
+    }
+
+    #[test]
+    fn sandbox_keeps_scripts_apart() {
+        let packs = PackSet::new(Path::new("../../data/packs")).unwrap();
+        let scripts = ScriptRuntime::default();
+        let mut physics = Physics::default();
+        let meddler = marker(
+            &packs,
+            &mut physics,
+            &scripts,
+            r#"rawset(string, "upper", nil)
+            math.pi = 3
+            shared = 1
+            _G.leaked = 1"#,
+        );
+        assert!(meddler.script.is_none(), "wrote to a library");
+        let meddler = marker(
+            &packs,
+            &mut physics,
+            &scripts,
+            r#"rawset(string, "upper", nil)
+            shared = 1
+            _G.leaked = 1"#,
+        );
+        assert!(meddler.script.is_some());
+        let probe = marker(
+            &packs,
+            &mut physics,
+            &scripts,
+            r#"missing = load == nil and loadfile == nil and dofile == nil and io == nil
+            upper = string.upper("a")
+            pi = math.pi
+            seen = shared ~= nil or leaked ~= nil
+            hidden = getmetatable("") == false"#,
+        );
+        assert!(global::<bool>(&scripts, &probe, "missing"));
+        assert_eq!(global::<String>(&scripts, &probe, "upper"), "A");
+        assert_eq!(global::<f32>(&scripts, &probe, "pi"), std::f32::consts::PI);
+        assert!(!global::<bool>(&scripts, &probe, "seen"));
+        assert!(global::<bool>(&scripts, &probe, "hidden"));
+    }
+
+    #[test]
+    fn runaway_script_is_aborted() {
+        let packs = PackSet::new(Path::new("../../data/packs")).unwrap();
+        let scripts = ScriptRuntime::default();
+        let mut physics = Physics::default();
+        let stuck = marker(&packs, &mut physics, &scripts, "while true do end");
+        assert!(stuck.script.is_none());
+        let mut objects = vec![
+            marker(
+                &packs,
+                &mut physics,
+                &scripts,
+                "function on_tick() while true do end end",
+            ),
+            marker(
+                &packs,
+                &mut physics,
+                &scripts,
+                "function on_tick() ticks = (ticks or 0) + 1 end",
+            ),
+        ];
+        let mut world = world(&packs, &mut physics, &mut objects);
+        scripts.tick(&mut world);
+        scripts.tick(&mut world);
+        assert_eq!(global::<u32>(&scripts, &objects[1], "ticks"), 2);
+    }
+}
@@ -1,47 +1,258 @@
-use crate::content_pack::ContentPack;
-use crate::definitions::{ColliderDesc, ObjectDesc, PhysicsBodyDesc, ShapeDesc};
-use crate::instances::Object;
+use crate::content_pack::PackSet;
+use crate::definitions::{
+    ColliderDesc, JointDesc, JointKind, ObjectDesc, PhysicsBodyDesc, ShapeDesc,
+};
+use crate::hulls;
+use crate::instances::{Child, Object, Trigger};
+use crate::scripting::ScriptRuntime;
 use blade_graphics as gpu;
+use rapier3d::dynamics::{GenericJoint, GenericJointBuilder, JointAxesMask, JointAxis};
+use rapier3d::geometry::{ColliderBuilder, Group, InteractionGroups, SharedShape};
+use rapier3d::math::{Pose, Vec3};
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use vandals_and_heroes::Physics;
 use vandals_and_heroes::{Loader, Model, ModelInstance};
 
 pub struct ObjectTemplate {
     pub model: Option<Arc<Model>>,
+    /// Source of the entity's Lua script, run afresh for every object.
+    pub script: Option<String>,
     pub desc: ObjectDesc,
 }
 
+/// Vertices and triangles of the glTF at `path` in the pack set.
+fn read_mesh(content_pack: &PackSet, path: &Path) -> (PathBuf, Vec<Vec3>, Vec<[u32; 3]>) {
+    let full_path = content_pack.get_resource_path(path);
+    let model_desc = Loader::read_gltf(&full_path, nalgebra::Matrix4::identity());
+    let vertices = model_desc
+        .positions()
+        .into_iter()
+        .map(|p| Vec3::new(p.x, p.y, p.z))
+        .collect();
+    let indices = model_desc.indices();
+    (full_path, vertices, indices)
+}
+
+fn convex_hull(path: &Path, vertices: &[Vec3]) -> Result<SharedShape, String> {
+    SharedShape::convex_hull(vertices)
+        .ok_or_else(|| format!("no convex hull around {}", path.display()))
+}
+
+fn convex_pieces(path: &Path, hulls: &[Vec<Vec3>]) -> Result<Vec<(Pose, SharedShape)>, String> {
+    let pieces: Vec<_> = hulls
+        .iter()
+        .filter_map(|points| SharedShape::convex_hull(points))
+        .map(|shape| (Pose::from_translation(Vec3::ZERO), shape))
+        .collect();
+    if pieces.is_empty() {
+        return Err(format!("no convex pieces in {}", path.display()));
+    }
+    Ok(pieces)
+}
+
+/// The collider `shape` describes, still to be placed. A hull that cannot
+/// be wrapped around its mesh, such as a flat one, is an error.
+fn collider_shape(content_pack: &PackSet, shape: &ShapeDesc) -> Result<ColliderBuilder, String> {
+    Ok(match *shape {
+        ShapeDesc::Box { size: (hx, hy, hz) } => ColliderBuilder::cuboid(hx, hy, hz),
+        ShapeDesc::Sphere { radius } => ColliderBuilder::ball(radius),
+        ShapeDesc::Capsule {
+            half_height,
+            radius,
+        } => ColliderBuilder::capsule_y(half_height, radius),
+        ShapeDesc::Cylinder {
+            half_height,
+            radius,
+        } => ColliderBuilder::cylinder(half_height, radius),
+        ShapeDesc::Mesh { ref path } => {
+            let (_, vertices, indices) = read_mesh(content_pack, path);
+            ColliderBuilder::trimesh(vertices, indices).unwrap()
+        }
+        ShapeDesc::ConvexHull { ref path } => {
+            let (_, vertices, _) = read_mesh(content_pack, path);
+            ColliderBuilder::new(convex_hull(path, &vertices)?)
+        }
+        ShapeDesc::ConvexDecomposition {
+            ref path,
+            max_hulls,
+        } => {
+            let (full_path, vertices, indices) = read_mesh(content_pack, path);
+            let hulls = hulls::convex_decomposition(&full_path, &vertices, &indices, max_hulls);
+            ColliderBuilder::compound(convex_pieces(path, &hulls)?)
+        }
+    })
+}
+
+/// Checks that the hulls `shape` asks for can be built, without the
+/// seconds of V-HACD `collider_shape` may spend: a decomposition is judged
+/// by its cached pieces, or else by the hull around the whole mesh.
+pub fn check_hulls(content_pack: &PackSet, shape: &ShapeDesc) -> Result<(), String> {
+    match *shape {
+        ShapeDesc::ConvexHull { ref path } => {
+            let (_, vertices, _) = read_mesh(content_pack, path);
+            convex_hull(path, &vertices)?;
+        }
+        ShapeDesc::ConvexDecomposition {
+            ref path,
+            max_hulls,
+        } => {
+            let full_path = content_pack.get_resource_path(path);
+            match hulls::cached_decomposition(&full_path, max_hulls) {
+                Some(hulls) => {
+                    convex_pieces(path, &hulls)?;
+                }
+                None => {
+                    let (_, vertices, _) = read_mesh(content_pack, path);
+                    convex_hull(path, &vertices)?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The collider of entity `id` that `desc` describes, or nothing, with a
+/// warning, when its shape cannot be made.
 fn create_collider(
-    content_pack: &ContentPack,
+    content_pack: &PackSet,
+    id: &str,
     desc: &ColliderDesc,
-) -> rapier3d::geometry::Collider {
-    let builder = match &desc.shape {
+) -> Option<rapier3d::geometry::Collider> {
+    let builder = collider_shape(content_pack, &desc.shape)
+        .map_err(|e| log::warn!("Leaving out a collider of {id}: {e}"))
+        .ok()?;
+    let iso: nalgebra::Isometry3<f32> = desc.transform.clone().into();
+    let groups = desc
+        .collision_groups
+        .as_ref()
+        .map_or(InteractionGroups::all(), |groups| {
+            InteractionGroups::all()
+                .with_memberships(Group::from_bits_truncate(groups.memberships))
+                .with_filter(Group::from_bits_truncate(groups.filter))
+        });
+    let collider = builder
+        .position(iso.into())
+        .density(desc.density)
+        .friction(desc.friction)
+        .restitution(desc.restitution)
+        .sensor(desc.sensor)
+        .collision_groups(groups)
+        .build();
+    Some(collider)
+}
+
+/// Drag area (`C_d · A`) of a loose prop, estimated from its collider
+/// shape: the mean face area of a box, the cross-section of a sphere, the
+/// mean of side-on and end-on views of a cylinder or capsule. Meshes and
+/// hulls are too irregular to guess at and stay out of the air.
+fn drag_area(desc: &ColliderDesc) -> f32 {
+    const BOX_DRAG_COEFFICIENT: f32 = 1.05;
+    const SPHERE_DRAG_COEFFICIENT: f32 = 0.47;
+    const CYLINDER_SIDE_DRAG_COEFFICIENT: f32 = 1.2;
+    const CYLINDER_END_DRAG_COEFFICIENT: f32 = 0.82;
+    match desc.shape {
         ShapeDesc::Box { size: (hx, hy, hz) } => {
-            rapier3d::geometry::ColliderBuilder::cuboid(*hx, *hy, *hz)
+            BOX_DRAG_COEFFICIENT * 4.0 * (hx * hy + hy * hz + hx * hz) / 3.0
         }
-        ShapeDesc::Sphere { radius } => rapier3d::geometry::ColliderBuilder::ball(*radius),
-        ShapeDesc::Mesh { path } => {
-            let full_path = content_pack.get_resource_path(path);
-            let model_desc = Loader::read_gltf(&full_path, nalgebra::Matrix4::identity());
-            let vertices = model_desc
-                .positions()
-                .into_iter()
-                .map(|p| rapier3d::math::Vec3::new(p.x, p.y, p.z))
-                .collect();
-            rapier3d::geometry::ColliderBuilder::trimesh(vertices, model_desc.indices()).unwrap()
+        ShapeDesc::Sphere { radius } => SPHERE_DRAG_COEFFICIENT * PI * radius * radius,
+        ShapeDesc::Cylinder {
+            half_height,
+            radius,
+        } => {
+            let side = CYLINDER_SIDE_DRAG_COEFFICIENT * 4.0 * half_height * radius;
+            let end = CYLINDER_END_DRAG_COEFFICIENT * PI * radius * radius;
+            0.5 * (side + end)
+        }
+        ShapeDesc::Capsule {
+            half_height,
+            radius,
+        } => {
+            let side = CYLINDER_SIDE_DRAG_COEFFICIENT * 4.0 * half_height * radius
+                + SPHERE_DRAG_COEFFICIENT * PI * radius * radius;
+            let end = SPHERE_DRAG_COEFFICIENT * PI * radius * radius;
+            0.5 * (side + end)
         }
+        ShapeDesc::Mesh { .. }
+        | ShapeDesc::ConvexHull { .. }
+        | ShapeDesc::ConvexDecomposition { .. } => 0.0,
+    }
+}
+
+/// The joint holding a child, placed `offset` from its parent, to the
+/// parent. Its X axis, about which revolute joints turn and along which
+/// prismatic ones slide, points along the desc's `axis`.
+fn create_joint(desc: &JointDesc, offset: &nalgebra::Isometry3<f32>) -> GenericJoint {
+    let (locked, axis, limits) = match desc.kind {
+        JointKind::Fixed => (JointAxesMask::LOCKED_FIXED_AXES, (1.0, 0.0, 0.0), None),
+        JointKind::Revolute { axis, ref limits } => (
+            JointAxesMask::LOCKED_REVOLUTE_AXES,
+            axis,
+            limits.as_ref().map(|limits| (JointAxis::AngX, limits)),
+        ),
+        JointKind::Prismatic { axis, ref limits } => (
+            JointAxesMask::LOCKED_PRISMATIC_AXES,
+            axis,
+            limits.as_ref().map(|limits| (JointAxis::LinX, limits)),
+        ),
+        JointKind::Spherical => (JointAxesMask::LOCKED_SPHERICAL_AXES, (1.0, 0.0, 0.0), None),
     };
-    let iso: nalgebra::Isometry3<f32> = desc.transform.clone().into();
-    builder
-        .position(iso.into())
-        // TODO: density
-        .build()
+    let (ax, ay, az) = axis;
+    let (x, y, z) = desc.anchor;
+    let back = nalgebra::UnitQuaternion::from_axis_angle(&nalgebra::Vector3::y_axis(), PI);
+    let rotation = nalgebra::UnitQuaternion::rotation_between(
+        &nalgebra::Vector3::x(),
+        &nalgebra::Vector3::new(ax, ay, az),
+    )
+    .unwrap_or(back);
+    let child_frame =
+        nalgebra::Isometry3::from_parts(nalgebra::Translation3::new(x, y, z), rotation);
+    let parent_frame = offset * child_frame;
+    let mut builder = GenericJointBuilder::new(locked)
+        .local_frame1(parent_frame.into())
+        .local_frame2(child_frame.into())
+        .contacts_enabled(false);
+    if let Some((joint_axis, limits)) = limits {
+        builder = builder.limits(joint_axis, [limits.start, limits.end]);
+    }
+    builder.build()
 }
 
 impl ObjectTemplate {
+    /// Make an object, with its script and, for a prefab, its children,
+    /// looked up in `templates`.
     pub fn instantiate(
         &self,
-        content_pack: &ContentPack,
+        templates: &HashMap<String, ObjectTemplate>,
+        content_pack: &PackSet,
+        physics: &mut Physics,
+        scripts: &ScriptRuntime,
+        transform: nalgebra::Isometry3<f32>,
+    ) -> Object {
+        let mut object = self.build(content_pack, physics, transform);
+        object.script = self.script.as_ref().and_then(|source| {
+            scripts
+                .instantiate(&self.desc.id, source)
+                .map_err(|e| log::warn!("Unable to run the script of {}: {e}", self.desc.id))
+                .ok()
+        });
+        self.attach_children(
+            templates,
+            content_pack,
+            physics,
+            &mut object,
+            &mut vec![self.desc.id.as_str()],
+        );
+        object
+    }
+
+    /// The object on its own: model, body and trigger.
+    fn build(
+        &self,
+        content_pack: &PackSet,
         physics: &mut Physics,
         transform: nalgebra::Isometry3<f32>,
     ) -> Object {
@@ -51,27 +262,126 @@ impl ObjectTemplate {
             transform,
             geometry_filter: None,
         });
-        let body = self.desc.physics.as_ref().map(|p| {
-            let colliders = p
+        let mut body = self.desc.physics.as_ref().map(|p| {
+            let mut colliders: Vec<_> = p
                 .colliders
                 .iter()
-                .map(|c| create_collider(content_pack, c))
+                .filter_map(|c| create_collider(content_pack, &self.desc.id, c))
                 .collect();
             let body_type = match p.body {
                 PhysicsBodyDesc::RigidBody { .. } => rapier3d::dynamics::RigidBodyType::Dynamic,
                 PhysicsBodyDesc::StaticBody => rapier3d::dynamics::RigidBodyType::Fixed,
             };
-            let rigid_body = rapier3d::dynamics::RigidBodyBuilder::new(body_type)
+            let mut builder = rapier3d::dynamics::RigidBodyBuilder::new(body_type)
                 .pose(transform.into())
-                .gravity_scale(1.0f32)
-                .build();
-            physics.add_rigid_body(rigid_body, colliders)
+                .gravity_scale(1.0f32);
+            if let PhysicsBodyDesc::RigidBody { mass } = p.body {
+                // Scale the densities so the colliders add up to `mass`,
+                // keeping how it is spread between them.
+                let collider_mass: f32 = colliders.iter().map(|c| c.mass()).sum();
+                if collider_mass > 0.0 {
+                    for collider in &mut colliders {
+                        collider.set_density(collider.density() * mass / collider_mass);
+                    }
+                } else {
+                    // Trimeshes have no volume to spread it over.
+                    builder = builder.additional_mass(mass);
+                }
+            }
+            let rigid_body = builder.build();
+            let handle = physics.add_rigid_body(rigid_body, colliders);
+            if let PhysicsBodyDesc::RigidBody { .. } = p.body {
+                let area = p.colliders.iter().map(drag_area).sum();
+                physics.set_drag_area(handle.rigid_body_handle, area);
+            }
+            handle
+        });
+        let trigger = self.desc.trigger.as_ref().and_then(|desc| {
+            let sensor = create_collider(content_pack, &self.desc.id, &desc.collider())?;
+            let collider = match body {
+                Some(ref mut handle) => {
+                    let collider = physics.add_collider(handle.rigid_body_handle, sensor);
+                    handle.collider_handles.push(collider);
+                    collider
+                }
+                None => {
+                    let fixed = rapier3d::dynamics::RigidBodyBuilder::fixed()
+                        .pose(transform.into())
+                        .build();
+                    let handle = physics.add_rigid_body(fixed, vec![sensor]);
+                    let collider = handle.collider_handles[0];
+                    body = Some(handle);
+                    collider
+                }
+            };
+            Some(Trigger {
+                collider,
+                on_enter: desc.on_enter.clone(),
+                on_exit: desc.on_exit.clone(),
+                inside: Vec::new(),
+            })
         });
 
         Object {
             model_instance,
             body,
             transform,
+            script: None,
+            children: Vec::new(),
+            trigger,
+        }
+    }
+
+    /// Make the children of `parent`, and theirs, skipping any that would
+    /// contain one of its `ancestors` again.
+    fn attach_children<'a>(
+        &'a self,
+        templates: &'a HashMap<String, ObjectTemplate>,
+        content_pack: &PackSet,
+        physics: &mut Physics,
+        parent: &mut Object,
+        ancestors: &mut Vec<&'a str>,
+    ) {
+        for child in &self.desc.children {
+            let id = child.entity_id.as_str();
+            if ancestors.contains(&id) {
+                log::warn!("Prefab {} contains itself through {id}", self.desc.id);
+                continue;
+            }
+            let Some(template) = templates.get(id) else {
+                log::warn!("Cannot find entity with id: {id}");
+                continue;
+            };
+            let offset: nalgebra::Isometry3<f32> = child.transform.clone().into();
+            let mut object = template.build(content_pack, physics, parent.transform * offset);
+            let bodies = (
+                child.joint.as_ref(),
+                parent.body.as_ref(),
+                object.body.as_ref(),
+            );
+            let joint = match bodies {
+                (Some(joint), Some(parent_body), Some(body)) => Some(physics.add_generic_joint(
+                    parent_body.rigid_body_handle,
+                    body.rigid_body_handle,
+                    create_joint(joint, &offset),
+                )),
+                (Some(_), _, _) => {
+                    log::warn!(
+                        "Joint between {} and {id} needs a body on both",
+                        self.desc.id
+                    );
+                    None
+                }
+                (None, _, _) => None,
+            };
+            ancestors.push(id);
+            template.attach_children(templates, content_pack, physics, &mut object, ancestors);
+            ancestors.pop();
+            parent.children.push(Child {
+                object,
+                offset,
+                joint,
+            });
         }
     }
 
@@ -81,3 +391,215 @@ impl ObjectTemplate {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instances::body_owners;
+    use rapier3d::dynamics::RigidBodyHandle;
+    use std::path::Path;
+
+    fn packs() -> PackSet {
+        PackSet::new(Path::new("../../data/packs")).unwrap()
+    }
+
+    /// A template of `desc` with neither model nor script.
+    fn template(desc: ObjectDesc) -> ObjectTemplate {
+        ObjectTemplate {
+            model: None,
+            script: None,
+            desc,
+        }
+    }
+
+    /// Templates of the entities `texts` write out in RON, by id.
+    fn templates<'a>(texts: impl IntoIterator<Item = &'a str>) -> HashMap<String, ObjectTemplate> {
+        (texts.into_iter())
+            .map(|text| template(ron::from_str(text).unwrap()))
+            .map(|template| (template.desc.id.clone(), template))
+            .collect()
+    }
+
+    /// An object of `templates[id]` at `pose`.
+    fn instantiate(
+        templates: &HashMap<String, ObjectTemplate>,
+        packs: &PackSet,
+        physics: &mut Physics,
+        id: &str,
+        pose: nalgebra::Isometry3<f32>,
+    ) -> Object {
+        let scripts = ScriptRuntime::default();
+        templates[id].instantiate(templates, packs, physics, &scripts, pose)
+    }
+
+    /// Every body of each of `objects`, one list per object.
+    fn bodies(objects: &[Object]) -> Vec<Vec<RigidBodyHandle>> {
+        (objects.iter())
+            .map(|object| {
+                let mut bodies = Vec::new();
+                object.bodies(&mut bodies);
+                bodies
+            })
+            .collect()
+    }
+
+    const GATE: &str = r#"(id: "gate",
+        trigger: Some((shape: Box(size: (1.0, 0.2, 1.0)),
+            on_enter: Some("entered"), on_exit: Some("left"))))"#;
+
+    #[test]
+    fn rigid_body_gets_its_mass() {
+        let packs = packs();
+        let cube = template(packs.get_entity_by_id("cube").unwrap().clone());
+        let PhysicsBodyDesc::RigidBody { mass } = cube.desc.physics.as_ref().unwrap().body else {
+            panic!("cube is not a rigid body");
+        };
+        let templates = HashMap::from([("cube".to_string(), cube)]);
+        let mut physics = Physics::default();
+        let pose = nalgebra::Isometry3::identity();
+        let object = instantiate(&templates, &packs, &mut physics, "cube", pose);
+        let body = object.body.unwrap().rigid_body_handle;
+        let actual = physics.body_mass(body);
+        assert!((actual - mass).abs() < 1e-4, "mass {actual}, not {mass}");
+    }
+
+    #[test]
+    fn gate_swings_on_its_hinge() {
+        let packs = packs();
+        let templates = templates([
+            r#"(id: "post",
+                physics: Some((body: StaticBody,
+                    colliders: [(shape: Box(size: (0.1, 1.0, 0.1)), transform: ())])),
+                children: [(entity_id: "gate", transform: (position: (0.6, 0.0, 0.0)),
+                    joint: Some((kind: Revolute(axis: (0, 1, 0)), anchor: (-0.5, 0, 0))))])"#,
+            r#"(id: "gate",
+                physics: Some((body: RigidBody(mass: 20),
+                    colliders: [(shape: Box(size: (0.5, 0.9, 0.05)), transform: ())])),
+                children: [(entity_id: "latch", transform: (position: (0.5, 0.0, 0.0)))])"#,
+            r#"(id: "latch")"#,
+        ]);
+
+        let mut physics = Physics::default();
+        let pose = nalgebra::Isometry3::identity();
+        let mut post = instantiate(&templates, &packs, &mut physics, "post", pose);
+        let hinged = &post.children[0];
+        assert!(hinged.joint.is_some());
+        let body = hinged.object.body.as_ref().unwrap().rigid_body_handle;
+        physics.apply_impulse(body, Vec3::new(0.0, 0.0, 10.0));
+        for _ in 0..30 {
+            physics.step();
+        }
+        post.sync(&physics);
+
+        let gate = &post.children[0].object;
+        let hinge = gate.transform * nalgebra::Point3::new(-0.5, 0.0, 0.0);
+        let expected = nalgebra::Point3::new(0.1, 0.0, 0.0);
+        assert!((hinge - expected).norm() < 0.01, "hinge at {hinge}");
+        let (axis, angle) = gate.transform.rotation.axis_angle().unwrap();
+        assert!(angle > 0.1, "gate did not swing");
+        assert!(axis.y.abs() > 0.99, "gate turned about {axis:?}");
+        let latch = gate.children[0].object.transform.translation.vector;
+        let end = (gate.transform * nalgebra::Point3::new(0.5, 0.0, 0.0)).coords;
+        assert!((latch - end).norm() < 1e-4, "latch left behind at {latch}");
+    }
+
+    #[test]
+    fn cube_falls_through_trigger() {
+        let packs = packs();
+        let mut templates = templates([GATE]);
+        let cube = template(packs.get_entity_by_id("cube").unwrap().clone());
+        templates.insert("cube".to_string(), cube);
+
+        let mut physics = Physics::default();
+        let mut objects = vec![
+            instantiate(
+                &templates,
+                &packs,
+                &mut physics,
+                "gate",
+                nalgebra::Isometry3::identity(),
+            ),
+            instantiate(
+                &templates,
+                &packs,
+                &mut physics,
+                "cube",
+                nalgebra::Isometry3::translation(0.0, 1.0, 0.0),
+            ),
+        ];
+        let cube = objects[1].body.as_ref().unwrap().rigid_body_handle;
+        physics.set_linvel(cube, Vec3::new(0.0, -3.0, 0.0));
+
+        let owners = body_owners(bodies(&objects));
+        let mut events = Vec::new();
+        let mut inside_at = None;
+        for step in 0..60 {
+            physics.step();
+            let before = events.len();
+            for object in &mut objects {
+                object.trigger_events(&physics, &owners, &mut events);
+            }
+            if events.len() > before && inside_at.is_none() {
+                inside_at = Some(step);
+                assert!(objects[0].trigger.as_ref().unwrap().inside.contains(&cube));
+            }
+        }
+        assert_eq!(events, ["entered", "left"]);
+        // Falling 3 m/s from 1 m up, the cube's bottom meets the gate's top
+        // a quarter of a second in.
+        let inside_at = inside_at.unwrap();
+        assert!(
+            (11..=17).contains(&inside_at),
+            "entered at step {inside_at}"
+        );
+    }
+
+    #[test]
+    fn prefab_fires_trigger_once() {
+        let packs = packs();
+        let templates = templates([
+            GATE,
+            r#"(id: "dumbbell",
+                physics: Some((body: RigidBody(mass: 1),
+                    colliders: [(shape: Sphere(radius: 0.1), transform: ())])),
+                children: [(entity_id: "weight", transform: (position: (0.3, 0.0, 0.0)),
+                    joint: Some((kind: Fixed)))])"#,
+            r#"(id: "weight",
+                physics: Some((body: RigidBody(mass: 1),
+                    colliders: [(shape: Sphere(radius: 0.1), transform: ())])))"#,
+        ]);
+
+        let mut physics = Physics::default();
+        let mut objects = vec![
+            instantiate(
+                &templates,
+                &packs,
+                &mut physics,
+                "gate",
+                nalgebra::Isometry3::identity(),
+            ),
+            instantiate(
+                &templates,
+                &packs,
+                &mut physics,
+                "dumbbell",
+                nalgebra::Isometry3::translation(0.0, 1.0, 0.0),
+            ),
+        ];
+        let owners = body_owners(bodies(&objects));
+        let dumbbell = bodies(&objects).remove(1);
+        assert_eq!(dumbbell.len(), 2);
+        for body in dumbbell {
+            physics.set_linvel(body, Vec3::new(0.0, -3.0, 0.0));
+        }
+
+        let mut events = Vec::new();
+        for _ in 0..60 {
+            physics.step();
+            for object in &mut objects {
+                object.trigger_events(&physics, &owners, &mut events);
+            }
+        }
+        assert_eq!(events, ["entered", "left"]);
+    }
+}
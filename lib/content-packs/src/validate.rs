@@ -0,0 +1,670 @@
+//! Checking a pack stack without starting the game. `validate` reads every
+//! pack `packs.ron` lists, carrying on past problems instead of stopping at
+//! the first, and reports each one against the file (and, where it can be
+//! pinned down, the line) it comes from. The checks run on the packs as
+//! mounted, so an override that fixes an earlier pack's mistake counts.
+
+use crate::content_pack::{ContentPack, Diagnostic, PackSet, Report, Source};
+use crate::definitions::{
+    EventActionDesc, JointDesc, JointKind, LevelDesc, ObjectDesc, PhysicsBodyDesc, ShapeDesc,
+    SurfaceDesc,
+};
+use crate::templates::check_hulls;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::TAU;
+use std::path::Path;
+use vandals_and_heroes::config::{self, WorldShape};
+
+impl ContentPack {
+    /// Ids given twice within this pack: entities, levels, and objects
+    /// within a level.
+    fn check_duplicates(&self, report: &mut Report) {
+        if let Some((ref source, ref entities)) = self.entities {
+            let mut seen = HashMap::new();
+            for entity in entities {
+                let count: &mut usize = seen.entry(entity.id.as_str()).or_default();
+                if *count > 0 {
+                    report.error(
+                        &source.path,
+                        source.line_of(&entity.id, *count),
+                        format!("entity `{}` is defined more than once", entity.id),
+                    );
+                }
+                *count += 1;
+            }
+        }
+
+        let mut level_files: HashMap<&str, &Path> = HashMap::new();
+        for (source, level) in &self.levels {
+            if let Some(first) = level_files.insert(&level.id, &source.path) {
+                report.error(
+                    &source.path,
+                    source.line_of(&level.id, 0),
+                    format!(
+                        "level `{}` is also defined in {}",
+                        level.id,
+                        first.display()
+                    ),
+                );
+            }
+            let mut seen = HashMap::new();
+            for object in &level.objects {
+                let count: &mut usize = seen.entry(object.id.as_str()).or_default();
+                if *count > 0 {
+                    report.error(
+                        &source.path,
+                        source.line_of(&object.id, *count),
+                        format!("object `{}` appears more than once", object.id),
+                    );
+                }
+                *count += 1;
+            }
+        }
+    }
+}
+
+fn positive(value: f32) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+fn check_shape(shape: &ShapeDesc) -> Result<(), String> {
+    match *shape {
+        ShapeDesc::Box { size: (x, y, z) } if !(positive(x) && positive(y) && positive(z)) => {
+            Err(format!("box size ({x}, {y}, {z}) must be positive"))
+        }
+        ShapeDesc::Sphere { radius } if !positive(radius) => {
+            Err(format!("sphere radius {radius} must be positive"))
+        }
+        ShapeDesc::Capsule {
+            half_height,
+            radius,
+        } if !(half_height.is_finite() && half_height >= 0.0 && positive(radius)) => Err(format!(
+            "capsule half height {half_height} must not be negative and radius {radius} must be positive"
+        )),
+        ShapeDesc::Cylinder {
+            half_height,
+            radius,
+        } if !(positive(half_height) && positive(radius)) => Err(format!(
+            "cylinder half height {half_height} and radius {radius} must be positive"
+        )),
+        ShapeDesc::ConvexDecomposition { max_hulls: 0, .. } => {
+            Err("convex decomposition needs at least one hull".to_string())
+        }
+        _ => match shape.path() {
+            Some(path)
+                if path
+                    .extension()
+                    .is_none_or(|ext| ext != "glb" && ext != "gltf") =>
+            {
+                Err(format!("mesh `{}` is not a glTF file", path.display()))
+            }
+            _ => Ok(()),
+        },
+    }
+}
+
+fn check_joint(joint: &JointDesc) -> Result<(), String> {
+    match joint.kind {
+        JointKind::Revolute {
+            axis: (x, y, z),
+            ref limits,
+        }
+        | JointKind::Prismatic {
+            axis: (x, y, z),
+            ref limits,
+        } => {
+            if !(x * x + y * y + z * z).is_normal() {
+                return Err(format!("joint axis ({x}, {y}, {z}) has no direction"));
+            }
+            match *limits {
+                // `start == end` locks the joint, which is odd but works.
+                Some(ref limits) if limits.is_empty() && limits.start != limits.end => {
+                    Err(format!(
+                        "joint limits {}..{} are backwards",
+                        limits.start, limits.end
+                    ))
+                }
+                _ => Ok(()),
+            }
+        }
+        JointKind::Fixed | JointKind::Spherical => Ok(()),
+    }
+}
+
+fn check_surface(surface: &SurfaceDesc, shape: WorldShape) -> Result<(), String> {
+    // Only the torus wraps along `v`.
+    let wraps = shape == WorldShape::Torus;
+    let (x, z) = surface.offset;
+    let finite = [
+        surface.u,
+        surface.v,
+        surface.altitude,
+        surface.heading,
+        x,
+        z,
+    ]
+    .iter()
+    .all(|value| value.is_finite());
+    if finite && (wraps || (0.0..=1.0).contains(&surface.v)) {
+        Ok(())
+    } else {
+        Err(format!(
+            "surface ({}, {}) must be finite with v within 0..1",
+            surface.u, surface.v
+        ))
+    }
+}
+
+/// Whether prefab `id` turns up among its own descendants.
+fn contains_itself(entities: &HashMap<&str, (&ObjectDesc, &Source)>, id: &str) -> bool {
+    let mut seen = HashSet::new();
+    let mut pending = vec![id];
+    while let Some(next) = pending.pop() {
+        let Some(&(entity, _)) = entities.get(next) else {
+            continue;
+        };
+        for child in &entity.children {
+            if child.entity_id == id {
+                return true;
+            }
+            if seen.insert(child.entity_id.as_str()) {
+                pending.push(&child.entity_id);
+            }
+        }
+    }
+    false
+}
+
+/// Check the packs listed in `directory/packs.ron`.
+pub fn validate(directory: &Path) -> Vec<Diagnostic> {
+    let mut report = Report::default();
+    let set = PackSet::load(directory, &mut report);
+    for pack in &set.packs {
+        pack.check_duplicates(&mut report);
+    }
+    check_contents(&set, &mut report);
+    report.0
+}
+
+/// Everything that depends on the packs as mounted: references between
+/// entities and levels, assets, shapes, and entities nothing uses.
+fn check_contents(set: &PackSet, report: &mut Report) {
+    let asset = |path: &Path| set.get_resource_path(path);
+    let entities: HashMap<&str, (&ObjectDesc, &Source)> = set
+        .entities()
+        .map(|(entity, source)| (entity.id.as_str(), (entity, source)))
+        .collect();
+    let levels: Vec<(&LevelDesc, &Source)> = set.levels().collect();
+    let mut ids: Vec<&str> = entities.keys().copied().collect();
+    ids.sort_unstable();
+
+    let mut reachable = HashSet::new();
+    for &(level, source) in &levels {
+        let map = &level.height_map;
+        if !asset(&map.image_path).exists() {
+            report.error(
+                &source.path,
+                source.line_of(&map.image_path.to_string_lossy(), 0),
+                format!("missing height map {}", map.image_path.display()),
+            );
+        }
+        if !positive(map.radius.start) || map.radius.start >= map.radius.end {
+            report.error(
+                &source.path,
+                source.line_of(&map.id, 0),
+                format!(
+                    "height map radius {}..{} must be positive and not empty",
+                    map.radius.start, map.radius.end
+                ),
+            );
+        }
+        if !positive(map.density) {
+            report.error(
+                &source.path,
+                source.line_of(&map.id, 0),
+                format!("height map density {} must be positive", map.density),
+            );
+        }
+        match map.length {
+            Some(length) if !positive(length) => report.error(
+                &source.path,
+                source.line_of(&map.id, 0),
+                format!("height map length {length} must be positive"),
+            ),
+            Some(length) if map.shape == WorldShape::Torus && length / TAU <= map.radius.end => {
+                report.error(
+                    &source.path,
+                    source.line_of(&map.id, 0),
+                    format!(
+                        "torus length {length} is too short for radius {}: the tube would cross itself",
+                        map.radius.end
+                    ),
+                )
+            }
+            _ => {}
+        }
+        match map.environment_path {
+            Some(ref path) if !asset(path).exists() => report.error(
+                &source.path,
+                source.line_of(&path.to_string_lossy(), 0),
+                format!("missing environment {}", path.display()),
+            ),
+            _ => {}
+        }
+
+        let mut seen = HashMap::new();
+        for object in &level.objects {
+            let count: &mut usize = seen.entry(object.entity_id.as_str()).or_default();
+            let line = source.line_of(&object.entity_id, *count);
+            match entities.get(object.entity_id.as_str()) {
+                None => report.error(
+                    &source.path,
+                    line,
+                    format!(
+                        "object `{}` uses entity `{}`, which no pack defines",
+                        object.id, object.entity_id
+                    ),
+                ),
+                Some(&(entity, _)) if entity.vehicle.is_some() => report.error(
+                    &source.path,
+                    line,
+                    format!(
+                        "object `{}`: vehicle `{}` can only be placed by the player start",
+                        object.id, object.entity_id
+                    ),
+                ),
+                Some(_) => {}
+            }
+            *count += 1;
+            reachable.insert(object.entity_id.as_str());
+            let surface = object.surface.as_ref();
+            if let Some(Err(message)) = surface.map(|s| check_surface(s, map.shape)) {
+                report.error(
+                    &source.path,
+                    source.line_of(&object.id, 0),
+                    format!("object `{}`: {message}", object.id),
+                );
+            }
+        }
+
+        if let Some(ref start) = level.player_start {
+            let line = source.line_of(
+                &start.entity_id,
+                seen.get(start.entity_id.as_str()).copied().unwrap_or(0),
+            );
+            reachable.insert(start.entity_id.as_str());
+            match entities.get(start.entity_id.as_str()) {
+                None => report.error(
+                    &source.path,
+                    line,
+                    format!(
+                        "player start uses entity `{}`, which no pack defines",
+                        start.entity_id
+                    ),
+                ),
+                Some(&(entity, _)) if entity.vehicle.is_none() => report.error(
+                    &source.path,
+                    line,
+                    format!("player start entity `{}` is not a vehicle", start.entity_id),
+                ),
+                Some(_) => {}
+            }
+            let surface = start.surface.as_ref();
+            if let Some(Err(message)) = surface.map(|s| check_surface(s, map.shape)) {
+                report.error(&source.path, line, format!("player start: {message}"));
+            }
+        }
+
+        let mut names: Vec<&str> = level.events.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        // Despawns may name the level's objects or anything an event spawns.
+        let object_ids: HashSet<&str> = (level.objects.iter().map(|o| o.id.as_str()))
+            .chain(
+                level
+                    .events
+                    .values()
+                    .flatten()
+                    .filter_map(|action| match *action {
+                        EventActionDesc::Spawn(ref object) => Some(object.id.as_str()),
+                        _ => None,
+                    }),
+            )
+            .collect();
+        for name in names {
+            let line = source.line_of(name, 0);
+            let fired = entities.values().any(|&(entity, _)| {
+                (entity.trigger.as_ref()).is_some_and(|trigger| {
+                    trigger.on_enter.as_deref() == Some(name)
+                        || trigger.on_exit.as_deref() == Some(name)
+                })
+            });
+            if !fired {
+                report.warning(
+                    &source.path,
+                    line,
+                    format!("event `{name}` is fired by no trigger"),
+                );
+            }
+            for action in &level.events[name] {
+                match *action {
+                    EventActionDesc::Spawn(ref object) => {
+                        reachable.insert(object.entity_id.as_str());
+                        match entities.get(object.entity_id.as_str()) {
+                            None => report.error(
+                                &source.path,
+                                line,
+                                format!(
+                                    "event `{name}` spawns entity `{}`, which no pack defines",
+                                    object.entity_id
+                                ),
+                            ),
+                            Some(&(entity, _)) if entity.vehicle.is_some() => report.error(
+                                &source.path,
+                                line,
+                                format!(
+                                    "event `{name}`: vehicle `{}` can only be placed by the player start",
+                                    object.entity_id
+                                ),
+                            ),
+                            Some(_) => {}
+                        }
+                        let surface = object.surface.as_ref();
+                        if let Some(Err(message)) = surface.map(|s| check_surface(s, map.shape)) {
+                            report.error(
+                                &source.path,
+                                line,
+                                format!("event `{name}`: object `{}`: {message}", object.id),
+                            );
+                        }
+                    }
+                    EventActionDesc::Despawn(ref id) if !object_ids.contains(id.as_str()) => {
+                        report.error(
+                            &source.path,
+                            line,
+                            format!("event `{name}` despawns `{id}`, which is not in the level"),
+                        );
+                    }
+                    EventActionDesc::Despawn(_)
+                    | EventActionDesc::Message(_)
+                    | EventActionDesc::EndLevel { .. } => {}
+                }
+            }
+        }
+    }
+
+    for &id in &ids {
+        let (entity, source) = entities[id];
+        let line = source.line_of(id, 0);
+        let paths = [&entity.scene_path, &entity.script_path];
+        for path in paths.into_iter().flatten() {
+            if !asset(path).exists() {
+                report.error(
+                    &source.path,
+                    source.line_of(&path.to_string_lossy(), 0).or(line),
+                    format!("entity `{id}`: missing {}", path.display()),
+                );
+            }
+        }
+
+        if let Some(ref vehicle) = entity.vehicle {
+            let config_path = asset(&vehicle.config_path);
+            let config_line = source
+                .line_of(&vehicle.config_path.to_string_lossy(), 0)
+                .or(line);
+            match std::fs::read_to_string(&config_path) {
+                Err(_) => report.error(
+                    &source.path,
+                    config_line,
+                    format!("entity `{id}`: missing {}", vehicle.config_path.display()),
+                ),
+                Ok(text) => {
+                    if let Err(e) = ron::from_str::<config::Car>(&text) {
+                        report.error(
+                            &config_path,
+                            Some(e.span.start.line),
+                            format!("entity `{id}`: {}", e.code),
+                        );
+                    }
+                }
+            }
+            if entity.scene_path.is_none() {
+                report.error(
+                    &source.path,
+                    line,
+                    format!("entity `{id}`: a vehicle needs a scene for its body"),
+                );
+            }
+            let ignored = [
+                ("physics", entity.physics.is_some()),
+                ("children", !entity.children.is_empty()),
+                ("script", entity.script_path.is_some()),
+                ("trigger", entity.trigger.is_some()),
+            ];
+            for (name, present) in ignored {
+                if present {
+                    report.warning(
+                        &source.path,
+                        line,
+                        format!("entity `{id}`: a vehicle's {name} is ignored"),
+                    );
+                }
+            }
+        }
+
+        if let Some(ref physics) = entity.physics {
+            match physics.body {
+                PhysicsBodyDesc::RigidBody { mass } if !positive(mass) => report.error(
+                    &source.path,
+                    line,
+                    format!("entity `{id}`: mass {mass} must be positive"),
+                ),
+                _ => {}
+            }
+            for collider in &physics.colliders {
+                let shape = check_shape(&collider.shape);
+                if let Err(ref message) = shape {
+                    report.error(&source.path, line, format!("entity `{id}`: {message}"));
+                }
+                let materials = [
+                    ("density", collider.density),
+                    ("friction", collider.friction),
+                    ("restitution", collider.restitution),
+                ];
+                for (name, value) in materials {
+                    if !value.is_finite() || value < 0.0 {
+                        report.error(
+                            &source.path,
+                            line,
+                            format!("entity `{id}`: {name} {value} must not be negative"),
+                        );
+                    }
+                }
+                let is_hull = matches!(
+                    collider.shape,
+                    ShapeDesc::ConvexHull { .. } | ShapeDesc::ConvexDecomposition { .. }
+                );
+                match collider.shape.path() {
+                    Some(path) if !asset(path).exists() => report.error(
+                        &source.path,
+                        source.line_of(&path.to_string_lossy(), 0).or(line),
+                        format!("entity `{id}`: missing {}", path.display()),
+                    ),
+                    Some(path) if is_hull && shape.is_ok() => {
+                        if let Err(message) = check_hulls(set, &collider.shape) {
+                            report.error(
+                                &source.path,
+                                source.line_of(&path.to_string_lossy(), 0).or(line),
+                                format!("entity `{id}`: {message}"),
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+                let moving = matches!(physics.body, PhysicsBodyDesc::RigidBody { .. });
+                if moving && matches!(collider.shape, ShapeDesc::Mesh { .. }) {
+                    report.warning(
+                        &source.path,
+                        line,
+                        format!(
+                            "entity `{id}`: a moving mesh collider barely collides; \
+                             use ConvexHull or ConvexDecomposition"
+                        ),
+                    );
+                }
+            }
+        }
+
+        if let Some(ref trigger) = entity.trigger {
+            if let Err(message) = check_shape(&trigger.collider().shape) {
+                report.error(
+                    &source.path,
+                    line,
+                    format!("entity `{id}`: trigger {message}"),
+                );
+            }
+            let events = [&trigger.on_enter, &trigger.on_exit];
+            if events.iter().all(|event| event.is_none()) {
+                report.warning(
+                    &source.path,
+                    line,
+                    format!("entity `{id}`: the trigger fires no events"),
+                );
+            }
+            for name in events.into_iter().flatten() {
+                if !levels
+                    .iter()
+                    .any(|(level, _)| level.events.contains_key(name))
+                {
+                    report.warning(
+                        &source.path,
+                        source.line_of(name, 0).or(line),
+                        format!("entity `{id}`: no level has the event `{name}`"),
+                    );
+                }
+            }
+        }
+
+        for child in &entity.children {
+            let Some(&(child_entity, _)) = entities.get(child.entity_id.as_str()) else {
+                report.error(
+                    &source.path,
+                    line,
+                    format!(
+                        "entity `{id}`: child `{}` is not defined by any pack",
+                        child.entity_id
+                    ),
+                );
+                continue;
+            };
+            reachable.insert(child_entity.id.as_str());
+            if child_entity.vehicle.is_some() {
+                report.error(
+                    &source.path,
+                    line,
+                    format!(
+                        "entity `{id}`: vehicle `{}` can only be placed by the player start",
+                        child.entity_id
+                    ),
+                );
+            }
+            if child_entity.script_path.is_some() {
+                report.warning(
+                    &source.path,
+                    line,
+                    format!(
+                        "entity `{id}`: the script of child `{}` will not run",
+                        child.entity_id
+                    ),
+                );
+            }
+            let Some(ref joint) = child.joint else {
+                continue;
+            };
+            if entity.physics.is_none() || child_entity.physics.is_none() {
+                report.error(
+                    &source.path,
+                    line,
+                    format!(
+                        "entity `{id}`: the joint to child `{}` needs a body on both",
+                        child.entity_id
+                    ),
+                );
+            }
+            if let Err(message) = check_joint(joint) {
+                report.error(&source.path, line, format!("entity `{id}`: {message}"));
+            }
+        }
+        if contains_itself(&entities, id) {
+            report.error(&source.path, line, format!("entity `{id}` contains itself"));
+        }
+
+        // Scripts can spawn whatever they name.
+        let script = (entity.script_path.as_ref())
+            .and_then(|path| std::fs::read_to_string(asset(path)).ok());
+        if let Some(script) = script {
+            for &other in &ids {
+                if script.contains(&format!("\"{other}\""))
+                    || script.contains(&format!("'{other}'"))
+                {
+                    reachable.insert(other);
+                }
+            }
+        }
+    }
+
+    for &id in &ids {
+        if !reachable.contains(id) {
+            let (_, source) = entities[id];
+            report.warning(
+                &source.path,
+                source.line_of(id, 0),
+                format!("entity `{id}` is not used by any level or script"),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content_pack::Severity;
+    use std::path::PathBuf;
+
+    #[test]
+    fn repo_packs_are_valid() {
+        let errors: Vec<String> = validate(Path::new("../../data/packs"))
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .map(ToString::to_string)
+            .collect();
+        assert!(errors.is_empty(), "{errors:#?}");
+    }
+
+    #[test]
+    fn problems_point_at_their_lines() {
+        let root = Path::new("fixtures/broken");
+        let found: Vec<(PathBuf, Option<usize>, Severity)> = validate(root)
+            .into_iter()
+            .map(|d| {
+                (
+                    d.file.strip_prefix(root).unwrap().to_path_buf(),
+                    d.line,
+                    d.severity,
+                )
+            })
+            .collect();
+        let expected = [
+            // A missing comma, found where the next field starts.
+            ("base/levels/typo.ron", 4, Severity::Error),
+            // The second `crate`.
+            ("base/entities.ron", 7, Severity::Error),
+            // `lamp.lua` and `statue.glb` are not there.
+            ("base/entities.ron", 11, Severity::Error),
+            ("base/entities.ron", 15, Severity::Error),
+            // Nothing places `orphan`.
+            ("base/entities.ron", 18, Severity::Warning),
+        ]
+        .map(|(file, line, severity)| (PathBuf::from(file), Some(line), severity));
+        assert_eq!(found, expected);
+    }
+}
@@ -3,8 +3,8 @@
 //! The web build has no filesystem, and fetching assets over HTTP would push
 //! async plumbing through the whole loading path — so the demo's data set is
 //! compiled straight into the wasm binary instead. The embedded config
-//! (`web_config.ron`) mirrors `data/config.ron` minus the state recorder
-//! (nowhere to write a file on the web).
+//! (`bin/game/web_config.ron`) mirrors `data/config.ron` minus the state
+//! recorder (nowhere to write a file on the web).
 
 use std::borrow::Cow;
 use std::path::Path;
@@ -24,20 +24,21 @@ pub fn read(path: &Path) -> Cow<'static, [u8]> {
         .expect("asset path must be utf-8")
         .replace('\\', "/");
     let bytes: &'static [u8] = match key.as_str() {
-        "data/config.ron" => include_bytes!("web_config.ron"),
-        "data/maps/fostral/map.ron" => include_bytes!("../../data/maps/fostral/map.ron"),
-        "data/maps/fostral/map.png" => include_bytes!("../../data/maps/fostral/map.png"),
+        "data/config.ron" => include_bytes!("../bin/game/web_config.ron"),
+        "data/bindings.ron" => include_bytes!("../data/bindings.ron"),
+        "data/maps/fostral/map.ron" => include_bytes!("../data/maps/fostral/map.ron"),
+        "data/maps/fostral/map.png" => include_bytes!("../data/maps/fostral/map.png"),
         "data/maps/fostral-torus/map.ron" => {
-            include_bytes!("../../data/maps/fostral-torus/map.ron")
+            include_bytes!("../data/maps/fostral-torus/map.ron")
         }
         // Symlink to the fostral height map; include_bytes! follows it, so
         // the pixels embed once per referencing path.
         "data/maps/fostral-torus/map.png" => {
-            include_bytes!("../../data/maps/fostral-torus/map.png")
+            include_bytes!("../data/maps/fostral-torus/map.png")
         }
-        "data/envs/Fostral.png" => include_bytes!("../../data/envs/Fostral.png"),
-        "data/cars/OxidizeMonk/car.ron" => include_bytes!("../../data/cars/OxidizeMonk/car.ron"),
-        "data/cars/OxidizeMonk/body.glb" => include_bytes!("../../data/cars/OxidizeMonk/body.glb"),
+        "data/envs/Fostral.png" => include_bytes!("../data/envs/Fostral.png"),
+        "data/cars/OxidizeMonk/car.ron" => include_bytes!("../data/cars/OxidizeMonk/car.ron"),
+        "data/cars/OxidizeMonk/body.glb" => include_bytes!("../data/cars/OxidizeMonk/body.glb"),
         other => panic!("asset {other} is not embedded in the web build"),
     };
     Cow::Borrowed(bytes)
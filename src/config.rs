@@ -19,6 +19,52 @@ fn default_terrain_quality() -> f32 {
     0.75
 }
 
+fn default_inverted_time() -> f32 {
+    3.0
+}
+
+fn default_stuck_time() -> f32 {
+    4.0
+}
+
+fn default_stuck_distance() -> f32 {
+    1.0
+}
+
+fn default_invulnerability() -> f32 {
+    2.0
+}
+
+/// When a car is taken back to the last good ground it stood on, and how
+/// long it is spared after. Falling off the end of the world or through
+/// the ground always counts.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Recovery {
+    /// Seconds on its roof or side before the car is put back.
+    #[serde(default = "default_inverted_time")]
+    pub inverted_time: f32,
+    /// Seconds of throttle that get the car no further than
+    /// `stuck_distance` metres before it counts as stuck.
+    #[serde(default = "default_stuck_time")]
+    pub stuck_time: f32,
+    #[serde(default = "default_stuck_distance")]
+    pub stuck_distance: f32,
+    /// Seconds after a respawn during which hits do no damage.
+    #[serde(default = "default_invulnerability")]
+    pub invulnerability: f32,
+}
+
+impl Default for Recovery {
+    fn default() -> Self {
+        Self {
+            inverted_time: default_inverted_time(),
+            stuck_time: default_stuck_time(),
+            stuck_distance: default_stuck_distance(),
+            invulnerability: default_invulnerability(),
+        }
+    }
+}
+
 #[derive(serde::Deserialize)]
 pub struct Config {
     pub map: String,
@@ -27,6 +73,10 @@ pub struct Config {
     pub environment: Option<String>,
     #[serde(default)]
     pub record: Option<Recorder>,
+    /// Name of a `Timeline` under `data/demos/` to play in a loop, in place
+    /// of the keyboard, until a drive key is pressed: an attract mode.
+    #[serde(default)]
+    pub demo: Option<String>,
     /// Debug-snow density: one particle per `snow_area_per_particle_m2` m² of
     /// world surface. Smaller = denser snow = slower frame. `0` (the default)
     /// disables snow entirely — set a positive value in `data/config.ron`
@@ -38,10 +88,12 @@ pub struct Config {
     /// tolerance (and roughly halves the triangles).
     #[serde(default = "default_terrain_quality")]
     pub terrain_quality: f32,
+    #[serde(default)]
+    pub recovery: Recovery,
 }
 
 /// The topology the height map is wrapped onto.
-#[derive(serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum WorldShape {
     /// A cylinder around the Z axis: `u` wraps around it (θ), `v` runs along
     /// it. The world ends at `z = ±length/2`.
@@ -58,7 +110,103 @@ pub enum WorldShape {
     Torus,
 }
 
-#[derive(serde::Deserialize)]
+/// Air flow over the world, sampled per body by `Physics::update_aerodynamics`.
+#[derive(serde::Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Wind {
+    /// Still air: bodies only feel drag from their own motion.
+    #[default]
+    Calm,
+    /// The same world-space velocity (m/s) everywhere.
+    Uniform { velocity: [f32; 3] },
+    /// Air circulating around the world Z axis at `speed` m/s — the
+    /// cylinder's axis and the torus's ring axis. Positive spins
+    /// counter-clockwise looking down +Z.
+    Vortex { speed: f32 },
+    /// A base `velocity` whose magnitude swings by up to `strength` times
+    /// itself, on a cycle of roughly `period` seconds.
+    Gusts {
+        velocity: [f32; 3],
+        strength: f32,
+        period: f32,
+    },
+}
+
+fn default_air_density() -> f32 {
+    1.2
+}
+
+#[derive(serde::Deserialize, Clone, Copy, Debug)]
+pub struct Atmosphere {
+    /// Air density (kg/m³) in the quadratic drag formula.
+    #[serde(default = "default_air_density")]
+    pub density: f32,
+    #[serde(default)]
+    pub wind: Wind,
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self {
+            density: default_air_density(),
+            wind: Wind::default(),
+        }
+    }
+}
+
+fn default_max_gravity() -> f32 {
+    // Above the effective gravity the legacy synthetic tests see (~10 m/s²
+    // near the axis) so their settling dynamics are preserved, yet low enough
+    // that the sphere's inflated virtual ball can't pin a car in place.
+    12.0
+}
+
+/// How the strength of gravity varies with distance `r` from the world's
+/// core (see `TerrainBody::gravity_anchor`). The direction is always
+/// toward the core.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum GravityModel {
+    /// `G·M/r²` from the terrain's analytic mass, clamped to `max_accel`
+    /// (m/s²).
+    Newtonian {
+        #[serde(default = "default_max_gravity")]
+        max_accel: f32,
+    },
+    /// The same `accel` (m/s²) at every distance.
+    Constant { accel: f32 },
+    /// `accel` (m/s²) at the mean ground radius, falling off as `1/r` —
+    /// the field of an infinitely long rod, a natural fit for the cylinder.
+    InverseLinear { accel: f32 },
+}
+
+impl Default for GravityModel {
+    fn default() -> Self {
+        Self::Newtonian {
+            max_accel: default_max_gravity(),
+        }
+    }
+}
+
+/// Where a `GravityZone` applies.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub enum GravityRegion {
+    /// A ball in world space.
+    Sphere { center: [f32; 3], radius: f32 },
+    /// Everything whose distance from the world's core falls in `radius`:
+    /// e.g. `(start: 0.0, end: 10.0)` on a cylinder is the air inside the
+    /// ground surface.
+    Band { radius: Range<f32> },
+}
+
+/// A local override of the gravity model: inside `region` gravity is
+/// multiplied by `scale`. `0` is weightless, negative values push away from
+/// the core. Overlapping zones multiply.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct GravityZone {
+    pub region: GravityRegion,
+    pub scale: f32,
+}
+
+#[derive(serde::Deserialize, Default)]
 pub struct Map {
     pub radius: Range<f32>,
     #[serde(default)]
@@ -66,6 +214,12 @@ pub struct Map {
     pub density: f32,
     #[serde(default)]
     pub shape: WorldShape,
+    #[serde(default)]
+    pub atmosphere: Atmosphere,
+    #[serde(default)]
+    pub gravity: GravityModel,
+    #[serde(default)]
+    pub gravity_zones: Vec<GravityZone>,
 }
 
 #[derive(serde::Deserialize)]
@@ -94,11 +248,235 @@ fn default_body_color() -> [f32; 4] {
     [0.55, 0.35, 0.20, 1.0]
 }
 
+fn default_weapon_direction() -> [f32; 3] {
+    // Cars face chassis -X; see `Camera::chase_car`.
+    [-1.0, 0.0, 0.0]
+}
+
+fn default_health() -> f32 {
+    100.0
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum WeaponKind {
+    /// Launches a shell — a small CCD ball of `shell_radius` m and
+    /// `shell_mass` kg — at `muzzle_velocity` m/s on top of the vehicle's
+    /// own velocity. Shells fall under the map's gravity like any body.
+    Cannon {
+        muzzle_velocity: f32,
+        shell_radius: f32,
+        shell_mass: f32,
+    },
+    /// Instantly strikes the first thing along the barrel within `range` m.
+    Beam { range: f32 },
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct Weapon {
+    /// Muzzle in chassis-local coordinates. Keep it clear of the chassis
+    /// colliders, or shells are born touching their own vehicle.
+    pub position: [f32; 3],
+    /// Barrel direction in chassis-local coordinates.
+    #[serde(default = "default_weapon_direction")]
+    pub direction: [f32; 3],
+    pub kind: WeaponKind,
+    /// Health taken from whatever the round strikes.
+    pub damage: f32,
+    /// Seconds between shots.
+    pub cooldown: f32,
+    /// Rounds carried; `None` (the default) never runs dry.
+    #[serde(default)]
+    pub ammo: Option<u32>,
+    /// Bowl dug into the ground where a round lands: radius and centre
+    /// depth in metres. A zero radius (the default) leaves the ground be.
+    #[serde(default)]
+    pub crater_radius: f32,
+    #[serde(default)]
+    pub crater_depth: f32,
+}
+
+fn default_steer_rate() -> f32 {
+    6.0
+}
+
+/// How the front wheels follow the steer input.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Steering {
+    /// Fastest the wheels swing toward the asked-for angle, in rad/s.
+    #[serde(default = "default_steer_rate")]
+    pub rate: f32,
+    /// Chassis speed (m/s) at which full input only reaches half lock, so
+    /// the same stick deflection turns less the faster the car goes. `0`
+    /// (the default) keeps the full lock at any speed.
+    #[serde(default)]
+    pub fade_speed: f32,
+}
+
+impl Default for Steering {
+    fn default() -> Self {
+        Self {
+            rate: default_steer_rate(),
+            fade_speed: 0.0,
+        }
+    }
+}
+
+fn default_brake_torque() -> f32 {
+    16.0
+}
+
+fn default_front_bias() -> f32 {
+    0.5
+}
+
+fn default_handbrake_torque() -> f32 {
+    10.0
+}
+
+fn default_engine_brake() -> f32 {
+    1.0
+}
+
+/// The brake pedal and the handbrake. Each brakes a wheel by pulling its
+/// spin toward zero with no more than its share of torque, so a wheel
+/// locks only once the brake outgrips the tyre.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Brakes {
+    /// Torque of a full pedal over all the wheels together, in N·m.
+    #[serde(default = "default_brake_torque")]
+    pub torque: f32,
+    /// Share of `torque` the front axle gets, split evenly between its
+    /// wheels; the rest goes to the rear. More than half keeps the rear
+    /// from locking first and swinging the car round.
+    #[serde(default = "default_front_bias")]
+    pub front_bias: f32,
+    /// Torque the handbrake holds each rear wheel with, in N·m.
+    #[serde(default = "default_handbrake_torque")]
+    pub handbrake_torque: f32,
+    /// Torque each wheel is held with when neither throttle nor brake is
+    /// applied, like an engine left in gear: enough to stop the car
+    /// rolling away on a gentle slope, not enough to stop it short.
+    #[serde(default = "default_engine_brake")]
+    pub engine_brake: f32,
+}
+
+impl Default for Brakes {
+    fn default() -> Self {
+        Self {
+            torque: default_brake_torque(),
+            front_bias: default_front_bias(),
+            handbrake_torque: default_handbrake_torque(),
+            engine_brake: default_engine_brake(),
+        }
+    }
+}
+
+fn default_boost() -> f32 {
+    1.0
+}
+
+fn default_front_share() -> f32 {
+    0.5
+}
+
+/// Torque the engine makes against its speed.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct Engine {
+    /// `(rpm, N·m)` points in rising RPM order; linear between them and
+    /// flat past either end.
+    pub torque_curve: Vec<(f32, f32)>,
+    /// The engine never turns slower than this: below it, a slipping
+    /// clutch makes up the difference, so the car pulls away from rest.
+    pub idle_rpm: f32,
+    /// The rev limiter cuts all torque from here on.
+    pub redline_rpm: f32,
+    /// Torque multiplier while turbo is held.
+    #[serde(default = "default_boost")]
+    pub boost: f32,
+}
+
+/// An automatic gearbox: it always runs the forward gear that puts the
+/// most torque on the wheels at the current speed.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct Gearbox {
+    /// Forward ratios, first gear first.
+    pub ratios: Vec<f32>,
+    pub reverse: f32,
+    /// Fixed reduction between the gearbox and every wheel.
+    pub final_drive: f32,
+}
+
+/// How an axle shares its torque between its wheels.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Differential {
+    /// Equal torque to every wheel, so a wheel spinning in the air gets
+    /// as little as the one with grip can use.
+    #[default]
+    Open,
+    /// A wheel turning slower than the rest of its axle gets `bias` times
+    /// the torque of a faster one (the torque bias ratio; `1` is open).
+    LimitedSlip { bias: f32 },
+}
+
+/// Engine, gearbox and differentials, turning throttle into torque at the
+/// driven wheels.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct Drivetrain {
+    pub engine: Engine,
+    pub gearbox: Gearbox,
+    /// Share of the torque the front axle gets: `0` is rear-wheel drive,
+    /// `1` front-wheel drive, anything between all-wheel drive through a
+    /// centre differential.
+    #[serde(default = "default_front_share")]
+    pub front_share: f32,
+    #[serde(default)]
+    pub differential: Differential,
+}
+
+/// How much grip a tyre finds at a given slip, as a multiple of the load
+/// on it.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TireCurve {
+    /// Pacejka's "magic formula", `d·sin(c·atan(b·x − e·(b·x − atan(b·x))))`:
+    /// `b` sets the stiffness, `c` the shape, `d` the peak and `e` how the
+    /// grip falls away past it.
+    Pacejka { b: f32, c: f32, d: f32, e: f32 },
+    /// Brush model: grip rises `stiffness` per unit of slip until the whole
+    /// contact patch slides at `friction`.
+    Brush { stiffness: f32, friction: f32 },
+}
+
+/// A slip-driven tyre model in place of the wheels' plain friction.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Tires {
+    /// Against slip ratio: how much faster the tread turns than the wheel
+    /// travels, relative to that travel.
+    pub longitudinal: TireCurve,
+    /// Against slip angle, in radians: the angle between where the wheel
+    /// points and where it goes.
+    pub lateral: TireCurve,
+}
+
+/// How a car's wheels meet the ground.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VehicleKind {
+    /// Every wheel a body of its own, sprung, steered and driven through
+    /// joints to the chassis.
+    #[default]
+    Jointed,
+    /// Every wheel a ray cast down from the chassis; suspension, drive and
+    /// grip are forces on the chassis alone, and the wheel bodies are only
+    /// moved along for drawing.
+    Raycast,
+}
+
 #[derive(serde::Deserialize)]
 pub struct Car {
     pub scale: f32,
     pub density: f32,
     #[serde(default)]
+    pub kind: VehicleKind,
+    #[serde(default)]
     pub wheels: Vec<Wheel>,
     /// Wheel rotation axis in chassis-local coordinates (the axle direction).
     #[serde(default = "default_wheel_axis")]
@@ -107,9 +485,35 @@ pub struct Car {
     pub motor_max_velocity: f32,
     #[serde(default = "default_motor_max_force")]
     pub motor_max_force: f32,
+    /// Engine and gearbox behind the wheels. Without one each wheel motor
+    /// runs flat out to `motor_max_velocity` with `motor_max_force`, and
+    /// turbo raises that velocity.
+    #[serde(default)]
+    pub drivetrain: Option<Drivetrain>,
+    /// Tyre model for the wheels. Without one they are balls with plain
+    /// Coulomb friction, which either grip or slide.
+    #[serde(default)]
+    pub tires: Option<Tires>,
+    #[serde(default)]
+    pub steering: Steering,
+    #[serde(default)]
+    pub brakes: Brakes,
     /// Multiplicative tint applied to every loaded material's base_color_factor.
     /// Lets a `car.ron` override the GLB's default white materials without
     /// re-authoring the model.
     #[serde(default = "default_body_color")]
     pub body_color: [f32; 4],
+    /// Aerodynamic drag coefficient of the chassis. Together with
+    /// `frontal_area` it scales the quadratic air drag; `0` (the default)
+    /// leaves the car in vacuum.
+    #[serde(default)]
+    pub drag_coefficient: f32,
+    /// Cross-section the air sees head-on, in m².
+    #[serde(default)]
+    pub frontal_area: f32,
+    #[serde(default)]
+    pub weapons: Vec<Weapon>,
+    /// Damage the car absorbs before it is wrecked.
+    #[serde(default = "default_health")]
+    pub health: f32,
 }
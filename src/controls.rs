@@ -0,0 +1,305 @@
+//! Action mapping: keys and gamepad controls are bound to game actions in
+//! `data/bindings.ron`, so nothing else matches on a `KeyCode`. Axis
+//! actions (throttle, brake, steer) add up every control bound to them, so
+//! keys and a stick mix freely; any action counts as pressed once its
+//! control is past half deflection, which is what triggers like jump and
+//! roll listen for.
+//!
+//! The driving axes are shaped before they reach the car: gamepad travel
+//! goes through a response curve, and keys sweep the axis at a set rate
+//! rather than snapping straight to full lock.
+
+use crate::DriveInput;
+use std::collections::HashMap;
+use winit::keyboard::KeyCode;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize)]
+pub enum Action {
+    /// Axis: drive ahead (positive) or reverse.
+    Throttle,
+    /// Axis: how hard to brake, positive only; reverses once stopped.
+    Brake,
+    Handbrake,
+    /// Axis: steer right (positive) or left.
+    Steer,
+    Turbo,
+    Fire,
+    /// Hold to charge, release to jump.
+    Jump,
+    /// Flip the car about its forward axis, rightward for a positive scale.
+    Roll,
+    TogglePause,
+    QuickSave,
+    QuickLoad,
+    /// Print the camera as a ready-to-use `snapshot.ron` block.
+    DumpCamera,
+    Quit,
+    /// Free-camera steps while paused, camera-local.
+    FlyRight,
+    FlyUp,
+    FlyForward,
+    /// Free-camera turn about the view direction while paused.
+    FlyTurn,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+fn default_dead_zone() -> f32 {
+    0.15
+}
+
+fn default_exponent() -> f32 {
+    1.0
+}
+
+fn default_key_rate() -> f32 {
+    5.0
+}
+
+#[derive(Clone, Copy, Debug, serde::Deserialize)]
+pub struct Binding {
+    pub action: Action,
+    /// What full deflection contributes. The sign is the direction on
+    /// axes: `-1` for reverse, left, or a leftward roll.
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+}
+
+/// How a driving axis answers its controls.
+#[derive(Clone, Copy, Debug, serde::Deserialize)]
+pub struct Response {
+    /// Power a gamepad's deflection is raised to, sign kept: `1` is
+    /// linear, higher gives finer control around the centre.
+    #[serde(default = "default_exponent")]
+    pub exponent: f32,
+    /// How fast keys move the axis, in full deflections per second.
+    #[serde(default = "default_key_rate")]
+    pub key_rate: f32,
+}
+
+impl Default for Response {
+    fn default() -> Self {
+        Self {
+            exponent: default_exponent(),
+            key_rate: default_key_rate(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct Bindings {
+    /// Keys in every mode.
+    pub keys: HashMap<KeyCode, Binding>,
+    /// Keys while paused, ahead of `keys`.
+    #[serde(default)]
+    pub fly_keys: HashMap<KeyCode, Binding>,
+    /// Gamepad buttons; triggers report how far they are pulled.
+    #[serde(default)]
+    pub gamepad_buttons: HashMap<gilrs::Button, Binding>,
+    #[serde(default)]
+    pub gamepad_axes: HashMap<gilrs::Axis, Binding>,
+    /// Stick deflection that still reads as centred.
+    #[serde(default = "default_dead_zone")]
+    pub dead_zone: f32,
+    /// Shaping of the driving axes; unlisted ones are linear and ramp at
+    /// the default rate.
+    #[serde(default)]
+    pub axes: HashMap<Action, Response>,
+}
+
+/// A bound control crossing half deflection, either way.
+#[derive(Clone, Copy, Debug)]
+pub struct Press {
+    pub action: Action,
+    pub pressed: bool,
+    /// `1` or `-1`: which way the control moved, after its binding's scale.
+    pub direction: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Source {
+    Key(KeyCode),
+    Button(gilrs::GamepadId, gilrs::Button),
+    Axis(gilrs::GamepadId, gilrs::Axis),
+}
+
+impl Source {
+    fn gamepad(&self) -> Option<gilrs::GamepadId> {
+        match *self {
+            Self::Key(_) => None,
+            Self::Button(id, _) | Self::Axis(id, _) => Some(id),
+        }
+    }
+}
+
+pub struct Controls {
+    bindings: Bindings,
+    /// Every control off its rest position, with the action it was bound to
+    /// when it moved (a key keeps its action across a mode switch until it
+    /// is released) and its scaled deflection.
+    held: HashMap<Source, (Action, f32)>,
+    /// Where the keys have swept each driving axis so far.
+    ramps: HashMap<Action, f32>,
+    gilrs: Option<gilrs::Gilrs>,
+}
+
+impl Controls {
+    pub fn new(bindings: Bindings) -> Self {
+        let gilrs = match gilrs::Gilrs::new() {
+            Ok(gilrs) => {
+                for (_, gamepad) in gilrs.gamepads() {
+                    log::info!("Gamepad: {}", gamepad.name());
+                }
+                Some(gilrs)
+            }
+            Err(e) => {
+                log::warn!("Gamepads unavailable: {e}");
+                None
+            }
+        };
+        Self {
+            bindings,
+            held: HashMap::new(),
+            ramps: HashMap::new(),
+            gilrs,
+        }
+    }
+
+    /// A key went down or up; `paused` puts the fly keys first. Auto-repeat
+    /// and unbound keys give nothing.
+    pub fn key(&mut self, code: KeyCode, pressed: bool, paused: bool) -> Option<Press> {
+        let source = Source::Key(code);
+        if !pressed {
+            return self.update(source, None, 0.0);
+        }
+        if self.held.contains_key(&source) {
+            return None;
+        }
+        let binding = paused
+            .then(|| self.bindings.fly_keys.get(&code))
+            .flatten()
+            .or_else(|| self.bindings.keys.get(&code))
+            .copied();
+        self.update(source, binding, 1.0)
+    }
+
+    /// Take in everything the gamepads did since the last call.
+    pub fn poll_gamepads(&mut self) -> Vec<Press> {
+        let Some(gilrs) = self.gilrs.as_mut() else {
+            return Vec::new();
+        };
+        let events: Vec<_> = std::iter::from_fn(|| gilrs.next_event()).collect();
+        let mut presses = Vec::new();
+        for gilrs::Event { id, event, .. } in events {
+            match event {
+                gilrs::EventType::ButtonChanged(button, value, _) => {
+                    let binding = self.bindings.gamepad_buttons.get(&button).copied();
+                    presses.extend(self.update(Source::Button(id, button), binding, value));
+                }
+                gilrs::EventType::AxisChanged(axis, value, _) => {
+                    let binding = self.bindings.gamepad_axes.get(&axis).copied();
+                    let value = self.dead_zone(value);
+                    presses.extend(self.update(Source::Axis(id, axis), binding, value));
+                }
+                gilrs::EventType::Connected => log::info!("Gamepad {id} connected"),
+                gilrs::EventType::Disconnected => {
+                    log::info!("Gamepad {id} disconnected");
+                    let gone: Vec<_> = self
+                        .held
+                        .keys()
+                        .filter(|source| source.gamepad() == Some(id))
+                        .copied()
+                        .collect();
+                    for source in gone {
+                        presses.extend(self.update(source, None, 0.0));
+                    }
+                }
+                _ => {}
+            }
+        }
+        presses
+    }
+
+    /// Whether any control bound to `action` is pressed.
+    pub fn active(&self, action: Action) -> bool {
+        self.held
+            .values()
+            .any(|&(a, value)| a == action && value.abs() >= 0.5)
+    }
+
+    /// The driving controls for a tick of `dt` seconds, with the keys'
+    /// ramps moved along by that much.
+    pub fn drive_input(&mut self, dt: f32) -> DriveInput {
+        DriveInput {
+            throttle: self.shaped(Action::Throttle, dt),
+            brake: self.shaped(Action::Brake, dt).max(0.0),
+            steer: self.shaped(Action::Steer, dt),
+            handbrake: self.active(Action::Handbrake),
+            turbo: self.active(Action::Turbo),
+            fire: self.active(Action::Fire),
+        }
+    }
+
+    /// An axis action after its `Response`: keys ramped toward where they
+    /// point, gamepad travel curved, the two added up.
+    fn shaped(&mut self, action: Action, dt: f32) -> f32 {
+        let response = self.bindings.axes.get(&action).copied().unwrap_or_default();
+        let (mut keys, mut analog) = (0.0f32, 0.0f32);
+        for (source, &(a, value)) in &self.held {
+            if a != action {
+                continue;
+            }
+            match *source {
+                Source::Key(_) => keys += value,
+                Source::Button(..) | Source::Axis(..) => analog += value,
+            }
+        }
+        let ramp = self.ramps.entry(action).or_default();
+        let step = response.key_rate * dt;
+        *ramp += (keys.clamp(-1.0, 1.0) - *ramp).clamp(-step, step);
+        let analog = analog.clamp(-1.0, 1.0);
+        let curved = analog.signum() * analog.abs().powf(response.exponent);
+        (*ramp + curved).clamp(-1.0, 1.0)
+    }
+
+    fn dead_zone(&self, value: f32) -> f32 {
+        let dead = self.bindings.dead_zone;
+        if value.abs() <= dead {
+            0.0
+        } else {
+            value.signum() * (value.abs() - dead) / (1.0 - dead)
+        }
+    }
+
+    /// Move `source` to `value` and report whether it crossed half
+    /// deflection. Unbound controls are only ever let go.
+    fn update(&mut self, source: Source, binding: Option<Binding>, value: f32) -> Option<Press> {
+        let was = self.held.get(&source).copied();
+        let action = was.map(|(action, _)| action);
+        let now = match binding {
+            _ if value == 0.0 => None,
+            Some(binding) => Some((action.unwrap_or(binding.action), value * binding.scale)),
+            None => None,
+        };
+        match now {
+            Some(entry) => self.held.insert(source, entry),
+            None => self.held.remove(&source),
+        };
+        let pressed = |entry: Option<(Action, f32)>| entry.filter(|&(_, v)| v.abs() >= 0.5);
+        match (pressed(was), pressed(now)) {
+            (None, Some((action, v))) => Some(Press {
+                action,
+                pressed: true,
+                direction: v.signum(),
+            }),
+            (Some((action, v)), None) => Some(Press {
+                action,
+                pressed: false,
+                direction: v.signum(),
+            }),
+            _ => None,
+        }
+    }
+}
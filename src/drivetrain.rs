@@ -0,0 +1,157 @@
+//! The engine, gearbox and differentials of a `config::Drivetrain`, worked
+//! out afresh every tick from how fast the driven wheels turn. Nothing is
+//! remembered between ticks — the gearbox picks its gear from the speed
+//! alone — so snapshots, saves and client prediction need nothing extra.
+
+use crate::config::{Differential, Drivetrain, Engine};
+use std::f32::consts::TAU;
+
+/// Spin difference (rad/s) a limited-slip differential lets pass, so
+/// wheels rolling together don't flicker between torque shares.
+const SLIP_TOLERANCE: f32 = 0.5;
+
+/// A wheel as the drivetrain sees it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DrivenWheel {
+    /// On the front axle.
+    pub front: bool,
+    /// Spin about the axle in rad/s, positive rolling forward.
+    pub spin: f32,
+}
+
+/// What the drivetrain puts on the wheels for one tick.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Output {
+    /// `1` and up forward, `-1` reverse, `0` neutral with the throttle off.
+    pub gear: i32,
+    pub rpm: f32,
+    /// Wheel spin (rad/s) at which the engine reaches its redline in
+    /// `gear`, signed the way the car is being driven.
+    pub top_spin: f32,
+    /// Torque (N·m) for each wheel, in the order they were given.
+    pub torques: Vec<f32>,
+}
+
+/// Torque the engine makes at `rpm` with the throttle wide open, before
+/// boost; nothing from the redline up.
+pub fn engine_torque(engine: &Engine, rpm: f32) -> f32 {
+    if rpm >= engine.redline_rpm {
+        return 0.0;
+    }
+    let curve = &engine.torque_curve;
+    let Some(&(first_rpm, first_torque)) = curve.first() else {
+        return 0.0;
+    };
+    if rpm <= first_rpm {
+        return first_torque;
+    }
+    for pair in curve.windows(2) {
+        let ((rpm0, torque0), (rpm1, torque1)) = (pair[0], pair[1]);
+        if rpm <= rpm1 {
+            let t = (rpm - rpm0) / (rpm1 - rpm0).max(f32::EPSILON);
+            return torque0 + (torque1 - torque0) * t;
+        }
+    }
+    curve.last().map_or(0.0, |&(_, torque)| torque)
+}
+
+/// Run `train` for a tick with `throttle` in `-1..=1`, turbo `boost` and
+/// the car's `wheels`.
+pub fn run(train: &Drivetrain, throttle: f32, boost: bool, wheels: &[DrivenWheel]) -> Output {
+    let engine = &train.engine;
+    let gearbox = &train.gearbox;
+    let throttle = throttle.clamp(-1.0, 1.0);
+    if throttle == 0.0 {
+        return Output {
+            rpm: engine.idle_rpm,
+            torques: vec![0.0; wheels.len()],
+            ..Default::default()
+        };
+    }
+
+    // An axle with no wheels hands its share to the other.
+    let has_front = wheels.iter().any(|w| w.front);
+    let has_rear = wheels.iter().any(|w| !w.front);
+    let front_share = match (has_front, has_rear) {
+        (true, false) => 1.0,
+        (false, true) => 0.0,
+        _ => train.front_share.clamp(0.0, 1.0),
+    };
+    let share = |front: bool| {
+        if front {
+            front_share
+        } else {
+            1.0 - front_share
+        }
+    };
+
+    // The engine turns with the driven wheels, counted in the direction
+    // the car is being driven.
+    let direction = throttle.signum();
+    let driven: Vec<f32> = wheels
+        .iter()
+        .filter(|w| share(w.front) > 0.0)
+        .map(|w| w.spin * direction)
+        .collect();
+    let spin = if driven.is_empty() {
+        0.0
+    } else {
+        (driven.iter().sum::<f32>() / driven.len() as f32).max(0.0)
+    };
+    let rpm_per_spin = |ratio: f32| ratio * gearbox.final_drive * 60.0 / TAU;
+    let rpm_at = |ratio: f32| (spin * rpm_per_spin(ratio)).max(engine.idle_rpm);
+
+    let (gear, ratio) = if throttle > 0.0 {
+        let pull = |ratio: f32| engine_torque(engine, rpm_at(ratio)) * ratio;
+        let top = (gearbox.ratios.len() as i32, gearbox.ratios.last().copied());
+        gearbox
+            .ratios
+            .iter()
+            .enumerate()
+            .map(|(i, &ratio)| (i as i32 + 1, ratio))
+            .filter(|&(_, ratio)| rpm_at(ratio) < engine.redline_rpm)
+            .max_by(|&(_, a), &(_, b)| pull(a).total_cmp(&pull(b)))
+            .map(|(gear, ratio)| (gear, Some(ratio)))
+            // Every gear over-revs: top gear, on the limiter.
+            .unwrap_or(top)
+    } else {
+        (-1, Some(gearbox.reverse))
+    };
+    let Some(ratio) = ratio else {
+        return Output {
+            rpm: engine.idle_rpm,
+            torques: vec![0.0; wheels.len()],
+            ..Default::default()
+        };
+    };
+
+    let rpm = rpm_at(ratio);
+    let boost = if boost { engine.boost } else { 1.0 };
+    let total = engine_torque(engine, rpm) * boost * throttle.abs() * ratio * gearbox.final_drive;
+
+    let torques = wheels
+        .iter()
+        .map(|wheel| {
+            let axle: Vec<&DrivenWheel> =
+                wheels.iter().filter(|w| w.front == wheel.front).collect();
+            let mean = axle.iter().map(|w| w.spin * direction).sum::<f32>() / axle.len() as f32;
+            let weight = |w: &DrivenWheel| match train.differential {
+                Differential::LimitedSlip { bias }
+                    if w.spin * direction < mean - SLIP_TOLERANCE =>
+                {
+                    bias.max(1.0)
+                }
+                Differential::Open | Differential::LimitedSlip { .. } => 1.0,
+            };
+            let weights: f32 = axle.iter().map(|&w| weight(w)).sum();
+            total * share(wheel.front) * weight(wheel) / weights
+        })
+        .collect();
+
+    Output {
+        gear,
+        rpm,
+        top_spin: direction * engine.redline_rpm / rpm_per_spin(ratio),
+        torques,
+    }
+}
@@ -14,17 +14,27 @@
     clippy::pattern_type_mismatch
 )]
 
+pub mod assets;
 mod camera;
 pub mod config;
+pub mod controls;
+pub mod drivetrain;
 mod loader;
 mod model;
+pub mod net;
 mod physics;
 mod recorder;
+mod recovery;
 mod render;
 mod submission;
 mod terrain;
 mod texture;
+pub mod timeline;
 pub mod tin;
+pub mod tires;
+pub mod vehicle;
+mod weapons;
+mod world;
 
 pub use camera::Camera;
 use config::Map as MapConfig;
@@ -32,9 +42,17 @@ pub use loader::Loader;
 pub use model::{
     Geometry, GeometryDesc, Material, MaterialDesc, Model, ModelDesc, ModelInstance, VertexDesc,
 };
-pub use physics::{Kinematics, Physics, PhysicsBodyHandle, TerrainBody};
+pub use physics::{
+    FNV_OFFSET, GroundContact, Kinematics, Physics, PhysicsBodyHandle, PhysicsSnapshot, RayHit,
+    TerrainBody, fnv1a,
+};
 pub use recorder::{ObjectSnapshot, Recorder, Snapshot};
+pub use recovery::{Recovery, Trouble};
 pub use render::{Render, TerrainVertex, Vertex};
 use submission::Submission;
 pub use terrain::{Terrain, TerrainChunk};
 pub use texture::Texture;
+pub use timeline::{Timeline, TimelinePlayer};
+pub use vehicle::{DriveInput, Vehicle, Wheel};
+pub use weapons::{Damage, Impact, Shells, Weapons};
+pub use world::{World, load_world};
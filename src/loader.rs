@@ -430,83 +430,19 @@ impl<'a> Loader<'a> {
     /// for the terrain-mesh pipeline.
     pub fn load_terrain_mesh(&mut self, mesh: &crate::tin::TerrainMesh) -> Vec<super::TerrainChunk> {
         profiling::scope!("Loader::load_terrain_mesh");
-        let mut total_bytes = 0u64;
+        let total_bytes: usize = mesh
+            .chunks
+            .iter()
+            .map(|chunk| {
+                mem::size_of_val(chunk.vertices.as_slice())
+                    + mem::size_of_val(chunk.indices.as_slice())
+            })
+            .sum();
         let chunks = mesh
             .chunks
             .iter()
             .enumerate()
-            .map(|(i, chunk)| {
-                let vertex_bytes: &[u8] = bytemuck::cast_slice(&chunk.vertices);
-                let index_bytes: &[u8] = bytemuck::cast_slice(&chunk.indices);
-                let total_size = (vertex_bytes.len() + index_bytes.len()) as u64;
-                total_bytes += total_size;
-                let name = format!("terrain chunk {i}");
-                // Separate buffers per class; see load_model for the WebGL2
-                // reasoning behind the split and the post-creation syncs.
-                let vertex_buffer = self.context.create_buffer(gpu::BufferDesc {
-                    name: &name,
-                    size: vertex_bytes.len() as u64,
-                    memory: gpu::Memory::Device,
-                });
-                self.context
-                    .sync_buffer(vertex_buffer, gpu::BufferTarget::Data);
-                let index_buffer = self.context.create_buffer(gpu::BufferDesc {
-                    name: &format!("{name}/index"),
-                    size: index_bytes.len() as u64,
-                    memory: gpu::Memory::Device,
-                });
-                self.context
-                    .sync_buffer(index_buffer, gpu::BufferTarget::Index);
-                let stage_buffer = self.context.create_buffer(gpu::BufferDesc {
-                    name: &name,
-                    size: vertex_bytes.len() as u64,
-                    memory: gpu::Memory::Upload,
-                });
-                // Index staging is a separate element-class buffer; see
-                // load_model for the copyBufferSubData class rule.
-                let stage_index = self.context.create_buffer(gpu::BufferDesc {
-                    name: &format!("{name}/index stage"),
-                    size: index_bytes.len() as u64,
-                    memory: gpu::Memory::Upload,
-                });
-                unsafe {
-                    ptr::copy_nonoverlapping(
-                        vertex_bytes.as_ptr(),
-                        stage_buffer.data(),
-                        vertex_bytes.len(),
-                    );
-                    ptr::copy_nonoverlapping(
-                        index_bytes.as_ptr(),
-                        stage_index.data(),
-                        index_bytes.len(),
-                    );
-                }
-                self.context
-                    .sync_buffer(stage_buffer, gpu::BufferTarget::Data);
-                self.context
-                    .sync_buffer(stage_index, gpu::BufferTarget::Index);
-                let mut transfer = self.encoder.transfer("load terrain chunk");
-                transfer.copy_buffer_to_buffer(
-                    stage_buffer.into(),
-                    vertex_buffer.into(),
-                    vertex_bytes.len() as u64,
-                );
-                transfer.copy_buffer_to_buffer(
-                    stage_index.into(),
-                    index_buffer.into(),
-                    index_bytes.len() as u64,
-                );
-                self.temp_buffers.push(stage_buffer);
-                self.temp_buffers.push(stage_index);
-                super::TerrainChunk {
-                    vertex_buffer,
-                    index_buffer,
-                    lods: chunk.lods.clone(),
-                    center: chunk.center(),
-                    min: chunk.min,
-                    max: chunk.max,
-                }
-            })
+            .map(|(i, chunk)| self.upload_terrain_chunk(i, chunk))
             .collect();
         log::info!(
             "Terrain mesh uploaded: {} chunks, {} MiB",
@@ -516,6 +452,134 @@ impl<'a> Loader<'a> {
         chunks
     }
 
+    /// Re-upload the listed chunks after a terrain edit. The buffers they
+    /// replace are released together with this submission's staging
+    /// buffers, once no earlier frame can still be reading them.
+    pub fn reload_terrain_chunks(
+        &mut self,
+        mesh: &crate::tin::TerrainMesh,
+        indices: &[usize],
+        chunks: &mut [super::TerrainChunk],
+    ) {
+        profiling::scope!("Loader::reload_terrain_chunks");
+        for &index in indices {
+            let fresh = self.upload_terrain_chunk(index, &mesh.chunks[index]);
+            let old = mem::replace(&mut chunks[index], fresh);
+            self.temp_buffers.push(old.vertex_buffer);
+            self.temp_buffers.push(old.index_buffer);
+        }
+    }
+
+    fn upload_terrain_chunk(
+        &mut self,
+        index: usize,
+        chunk: &crate::tin::ChunkBuffers,
+    ) -> super::TerrainChunk {
+        let vertex_bytes: &[u8] = bytemuck::cast_slice(&chunk.vertices);
+        let index_bytes: &[u8] = bytemuck::cast_slice(&chunk.indices);
+        let name = format!("terrain chunk {index}");
+        // Separate buffers per class; see load_model for the WebGL2
+        // reasoning behind the split and the post-creation syncs.
+        let vertex_buffer = self.context.create_buffer(gpu::BufferDesc {
+            name: &name,
+            size: vertex_bytes.len() as u64,
+            memory: gpu::Memory::Device,
+        });
+        self.context
+            .sync_buffer(vertex_buffer, gpu::BufferTarget::Data);
+        let index_buffer = self.context.create_buffer(gpu::BufferDesc {
+            name: &format!("{name}/index"),
+            size: index_bytes.len() as u64,
+            memory: gpu::Memory::Device,
+        });
+        self.context
+            .sync_buffer(index_buffer, gpu::BufferTarget::Index);
+        let stage_buffer = self.context.create_buffer(gpu::BufferDesc {
+            name: &name,
+            size: vertex_bytes.len() as u64,
+            memory: gpu::Memory::Upload,
+        });
+        // Index staging is a separate element-class buffer; see
+        // load_model for the copyBufferSubData class rule.
+        let stage_index = self.context.create_buffer(gpu::BufferDesc {
+            name: &format!("{name}/index stage"),
+            size: index_bytes.len() as u64,
+            memory: gpu::Memory::Upload,
+        });
+        unsafe {
+            ptr::copy_nonoverlapping(
+                vertex_bytes.as_ptr(),
+                stage_buffer.data(),
+                vertex_bytes.len(),
+            );
+            ptr::copy_nonoverlapping(index_bytes.as_ptr(), stage_index.data(), index_bytes.len());
+        }
+        self.context
+            .sync_buffer(stage_buffer, gpu::BufferTarget::Data);
+        self.context
+            .sync_buffer(stage_index, gpu::BufferTarget::Index);
+        let mut transfer = self.encoder.transfer("load terrain chunk");
+        transfer.copy_buffer_to_buffer(
+            stage_buffer.into(),
+            vertex_buffer.into(),
+            vertex_bytes.len() as u64,
+        );
+        transfer.copy_buffer_to_buffer(
+            stage_index.into(),
+            index_buffer.into(),
+            index_bytes.len() as u64,
+        );
+        self.temp_buffers.push(stage_buffer);
+        self.temp_buffers.push(stage_index);
+        super::TerrainChunk {
+            vertex_buffer,
+            index_buffer,
+            lods: chunk.lods.clone(),
+            center: chunk.center(),
+            min: chunk.min,
+            max: chunk.max,
+        }
+    }
+
+    /// Re-upload full rows `rows` of the terrain texture from `rgba`, the
+    /// whole map image, after its heights were edited.
+    pub fn update_terrain_rows(
+        &mut self,
+        texture: &Texture,
+        extent: Extent,
+        rgba: &[u8],
+        rows: std::ops::Range<u32>,
+    ) {
+        let row_bytes = extent.width as usize * 4;
+        let bytes = &rgba[rows.start as usize * row_bytes..rows.end as usize * row_bytes];
+        let stage_buffer = self.context.create_buffer(gpu::BufferDesc {
+            name: "stage terrain rows",
+            size: bytes.len() as u64,
+            memory: gpu::Memory::Upload,
+        });
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), stage_buffer.data(), bytes.len());
+        }
+        self.context.sync_buffer(stage_buffer, gpu::BufferTarget::Data);
+        let mut transfer = self.encoder.transfer("update terrain rows");
+        transfer.copy_buffer_to_texture(
+            stage_buffer.into(),
+            extent.width * 4,
+            gpu::TexturePiece {
+                texture: texture.raw(),
+                mip_level: 0,
+                array_layer: 0,
+                origin: [0, rows.start, 0],
+            },
+            Extent {
+                width: extent.width,
+                height: rows.end - rows.start,
+                depth: 1,
+            },
+        );
+        self.temp_buffers.push(stage_buffer);
+    }
+
     pub fn load_png(&mut self, path: &Path) -> (Texture, Extent, Vec<u8>) {
         self.load_png_data(&fs::read(path).unwrap(), 1)
     }
@@ -526,6 +590,15 @@ impl<'a> Loader<'a> {
     /// shrinking them keeps the single-threaded TIN build, the GPU buffers,
     /// and the shadow map inside browser budgets.
     pub fn load_png_data(&mut self, data: &[u8], downsample: u32) -> (Texture, Extent, Vec<u8>) {
+        let (extent, rgba) = Self::decode_png(data, downsample);
+        let alpha = Self::height_alpha(&rgba);
+        let texture = self.load_terrain(extent, rgba.as_slice());
+        (texture, extent, alpha)
+    }
+
+    /// The CPU half of `load_png_data`: decode (and downsample) a map PNG
+    /// into RGBA8 bytes, for callers that keep the image around.
+    pub fn decode_png(data: &[u8], downsample: u32) -> (Extent, Vec<u8>) {
         let decoder = png::Decoder::new(std::io::Cursor::new(data));
         let mut reader = decoder.read_info().unwrap();
         let mut vec = vec![0u8; reader.output_buffer_size().unwrap()];
@@ -550,12 +623,14 @@ impl<'a> Loader<'a> {
             extent.width = w;
             extent.height = h;
         }
-        // Pull the alpha channel out for CPU-side use (heightmap collision).
-        // Map is laid out as RGBA8 — see shaders/terrain-mesh.wgsl: ground_radius is mixed by texel.a.
-        let pixel_count = (extent.width as usize) * (extent.height as usize);
-        let alpha: Vec<u8> = (0..pixel_count).map(|i| vec[i * 4 + 3]).collect();
-        let texture = self.load_terrain(extent, vec.as_slice());
-        (texture, extent, alpha)
+        (extent, vec)
+    }
+
+    /// Pull the alpha channel out of an RGBA map for CPU-side use
+    /// (heightmap collision). Map is laid out as RGBA8 — see
+    /// shaders/terrain-mesh.wgsl: ground_radius is mixed by texel.a.
+    pub fn height_alpha(rgba: &[u8]) -> Vec<u8> {
+        rgba.chunks_exact(4).map(|texel| texel[3]).collect()
     }
 }
 
@@ -0,0 +1,672 @@
+//! Multiplayer over UDP. The server owns the one true `Physics`; clients
+//! send the `DriveInput` they hold each tick and get the state of every
+//! car back after each server step. A client predicts its own car by
+//! simulating it locally, then reconciles that prediction with what the
+//! server reports. Other cars are drawn a few ticks in the past,
+//! interpolated between the server updates around that moment.
+//!
+//! Messages are bincode, one per datagram. Car state is compressed: the
+//! rotation as the three smallest quaternion components and velocities
+//! in steps of 1 cm/s, each as an `i16` that bincode's varints shrink
+//! further while the car is slow.
+
+use crate::config;
+use crate::model::ModelDesc;
+use crate::physics::{Kinematics, Physics, TerrainBody};
+use crate::recovery::Recovery;
+use crate::vehicle::{self, DriveInput, Vehicle};
+use rapier3d::math::{Pose, Rotation, Vec3};
+use std::collections::{BTreeMap, VecDeque};
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+
+pub const DEFAULT_PORT: u16 = 7878;
+/// Bumped whenever a message changes shape.
+const PROTOCOL_VERSION: u32 = 4;
+const MAX_PLAYERS: u8 = 8;
+/// Receive buffer size; comfortably above the largest state update.
+const MAX_DATAGRAM: usize = 64 * 1024;
+/// Players silent for this many ticks (5 s) are dropped.
+const TIMEOUT_TICKS: u32 = 300;
+/// Axial distance between neighbouring spawn slots (m).
+const SPAWN_SPACING: f32 = 3.0;
+/// Ticks between hellos while a client waits to be welcomed.
+const HELLO_INTERVAL: u32 = 30;
+/// How far behind the newest server update other cars are drawn, in ticks.
+/// Enough to bridge a lost or late datagram without extrapolating.
+const INTERPOLATION_DELAY: f32 = 3.0;
+/// Playback further than this from its target (ticks) jumps straight to it.
+const MAX_PLAYBACK_DRIFT: f32 = 10.0;
+/// Fraction of the playback clock's drift taken out per tick.
+const PLAYBACK_CATCH_UP: f32 = 0.05;
+/// Server updates kept for interpolation.
+const STATE_BUFFER: usize = 32;
+/// Predictions kept while waiting for the server to acknowledge them.
+const MAX_PREDICTIONS: usize = 120;
+/// Prediction errors beyond these put the local car straight onto the
+/// server's, instead of easing it over.
+const SNAP_DISTANCE: f32 = 1.0;
+const SNAP_ANGLE: f32 = 0.5;
+/// Fraction of a small prediction error corrected per server update.
+const CORRECTION: f32 = 0.25;
+/// Quaternion components other than the largest lie within ±1/√2.
+const ROTATION_SCALE: f32 = 32767.0 * std::f32::consts::SQRT_2;
+/// Velocity quantum (m/s or rad/s): ±327 at the `i16` limits.
+const VELOCITY_STEP: f32 = 0.01;
+
+pub type PlayerId = u8;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+enum ClientMessage {
+    Hello {
+        version: u32,
+    },
+    /// The controls for input number `seq`; the numbers only grow.
+    Input {
+        seq: u32,
+        input: DriveInput,
+    },
+    Bye,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+enum ServerMessage {
+    Welcome { player: PlayerId },
+    Refused { reason: String },
+    State(StateUpdate),
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct StateUpdate {
+    tick: u32,
+    cars: Vec<CarState>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CarState {
+    player: PlayerId,
+    /// Last input of this player's applied before the step.
+    ack: u32,
+    /// In `Vehicle::bodies` order.
+    bodies: Vec<PackedBody>,
+}
+
+/// `Kinematics` as sent over the wire.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PackedBody {
+    translation: [f32; 3],
+    /// Index of the quaternion component left out of `rotation`; it is
+    /// rebuilt from the unit length.
+    largest: u8,
+    rotation: [i16; 3],
+    linvel: [i16; 3],
+    angvel: [i16; 3],
+}
+
+fn pack_velocity(v: f32) -> i16 {
+    (v / VELOCITY_STEP).round().clamp(-32768.0, 32767.0) as i16
+}
+
+fn unpack_velocity(v: i16) -> f32 {
+    f32::from(v) * VELOCITY_STEP
+}
+
+impl PackedBody {
+    pub fn pack(kinematics: &Kinematics) -> Self {
+        let q = kinematics.rotation;
+        let largest = (0..4)
+            .max_by(|&a, &b| q[a].abs().total_cmp(&q[b].abs()))
+            .unwrap_or(3);
+        // q and -q are the same rotation: flip so the dropped component
+        // is positive and its square root rebuilds it.
+        let sign = if q[largest] < 0.0 { -1.0 } else { 1.0 };
+        let mut rotation = [0; 3];
+        for (slot, i) in rotation.iter_mut().zip((0..4).filter(|&i| i != largest)) {
+            *slot = (q[i] * sign * ROTATION_SCALE).round() as i16;
+        }
+        Self {
+            translation: kinematics.translation,
+            largest: largest as u8,
+            rotation,
+            linvel: kinematics.linvel.map(pack_velocity),
+            angvel: kinematics.angvel.map(pack_velocity),
+        }
+    }
+
+    pub fn unpack(&self) -> Kinematics {
+        let largest = usize::from(self.largest.min(3));
+        let mut rotation = [0.0; 4];
+        let mut sum = 0.0;
+        for (&v, i) in self.rotation.iter().zip((0..4).filter(|&i| i != largest)) {
+            rotation[i] = f32::from(v) / ROTATION_SCALE;
+            sum += rotation[i] * rotation[i];
+        }
+        rotation[largest] = (1.0 - sum).max(0.0).sqrt();
+        Kinematics {
+            translation: self.translation,
+            rotation,
+            linvel: self.linvel.map(unpack_velocity),
+            angvel: self.angvel.map(unpack_velocity),
+        }
+    }
+}
+
+fn encode<T: serde::Serialize>(message: &T) -> Vec<u8> {
+    bincode::serde::encode_to_vec(message, bincode::config::standard())
+        .expect("net messages always encode")
+}
+
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Option<T> {
+    bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+        .ok()
+        .map(|(message, _)| message)
+}
+
+/// Read one datagram, `None` once the socket has nothing more.
+fn receive(socket: &UdpSocket, buf: &mut [u8]) -> io::Result<Option<(usize, SocketAddr)>> {
+    loop {
+        match socket.recv_from(buf) {
+            Ok(received) => return Ok(Some(received)),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(None),
+            // Windows reports a peer's closed port on the next read; that
+            // is the peer's problem, not the socket's.
+            Err(e) if e.kind() == io::ErrorKind::ConnectionReset => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn rotation(kinematics: &Kinematics) -> Rotation {
+    let [x, y, z, w] = kinematics.rotation;
+    Rotation::from_xyzw(x, y, z, w)
+}
+
+fn pose(kinematics: &Kinematics) -> Pose {
+    Pose::from_parts(Vec3::from(kinematics.translation), rotation(kinematics))
+}
+
+fn lerp_kinematics(a: &Kinematics, b: &Kinematics, t: f32) -> Kinematics {
+    let lerp = |a: [f32; 3], b: [f32; 3]| Vec3::from(a).lerp(Vec3::from(b), t).to_array();
+    let q = rotation(a).slerp(rotation(b), t);
+    Kinematics {
+        translation: lerp(a.translation, b.translation),
+        rotation: [q.x, q.y, q.z, q.w],
+        linvel: lerp(a.linvel, b.linvel),
+        angvel: lerp(a.angvel, b.angvel),
+    }
+}
+
+/// What a server needs to put new players into its world.
+pub struct Arena {
+    pub map: config::Map,
+    pub car: config::Car,
+    pub car_model: ModelDesc,
+    /// Radial spawn height; see `vehicle::spawn_radius`.
+    pub spawn_radius: f32,
+    pub recovery: config::Recovery,
+}
+
+struct Player {
+    addr: SocketAddr,
+    vehicle: Vehicle,
+    recovery: Recovery,
+    input: DriveInput,
+    /// Sequence number of `input`.
+    ack: u32,
+    /// Server tick the player was last heard from.
+    heard: u32,
+}
+
+/// The authoritative side: steps the world and tells every client how it
+/// turned out. Weapons are not networked yet, so `DriveInput::fire` is
+/// ignored here.
+pub struct Server {
+    socket: UdpSocket,
+    physics: Physics,
+    terrain: TerrainBody,
+    arena: Arena,
+    players: BTreeMap<PlayerId, Player>,
+    tick: u32,
+}
+
+impl Server {
+    pub fn new(
+        socket: UdpSocket,
+        physics: Physics,
+        terrain: TerrainBody,
+        arena: Arena,
+    ) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            physics,
+            terrain,
+            arena,
+            players: BTreeMap::new(),
+            tick: 0,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub fn physics(&self) -> &Physics {
+        &self.physics
+    }
+
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn vehicle(&self, player: PlayerId) -> Option<&Vehicle> {
+        self.players.get(&player).map(|p| &p.vehicle)
+    }
+
+    /// One server tick: take in what the clients sent, step the world with
+    /// their latest inputs and send everyone the result.
+    pub fn tick(&mut self) -> io::Result<()> {
+        profiling::scope!("Server::tick");
+        let mut buf = vec![0; MAX_DATAGRAM];
+        while let Some((len, addr)) = receive(&self.socket, &mut buf)? {
+            match decode(&buf[..len]) {
+                Some(message) => self.handle(addr, message),
+                None => log::warn!("undecodable datagram from {addr}"),
+            }
+        }
+        let silent: Vec<PlayerId> = self
+            .players
+            .iter()
+            .filter(|&(_, p)| self.tick - p.heard > TIMEOUT_TICKS)
+            .map(|(&id, _)| id)
+            .collect();
+        for id in silent {
+            log::info!("player {id} timed out");
+            self.leave(id);
+        }
+
+        self.physics.update_gravity(&self.terrain);
+        self.physics.update_aerodynamics(&self.arena.map.atmosphere);
+        for player in self.players.values() {
+            player
+                .vehicle
+                .drive(&mut self.physics, &self.terrain, &player.input);
+        }
+        self.physics.step();
+        self.tick += 1;
+        for (id, player) in self.players.iter_mut() {
+            let trouble = player.recovery.check(
+                &self.physics,
+                &self.terrain,
+                &self.arena.map,
+                &player.vehicle,
+                &player.input,
+            );
+            if let Some(trouble) = trouble {
+                log::info!("player {id} respawned: {trouble:?}");
+                player
+                    .recovery
+                    .respawn(&mut self.physics, &self.terrain, &player.vehicle);
+            }
+        }
+
+        let update = ServerMessage::State(StateUpdate {
+            tick: self.tick,
+            cars: self
+                .players
+                .iter()
+                .map(|(&player, p)| CarState {
+                    player,
+                    ack: p.ack,
+                    bodies: p
+                        .vehicle
+                        .bodies()
+                        .into_iter()
+                        .filter_map(|body| self.physics.body_kinematics(body))
+                        .map(|k| PackedBody::pack(&k))
+                        .collect(),
+                })
+                .collect(),
+        });
+        let bytes = encode(&update);
+        for player in self.players.values() {
+            self.send_bytes(player.addr, &bytes);
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, addr: SocketAddr, message: ClientMessage) {
+        let known = self
+            .players
+            .iter()
+            .find(|&(_, p)| p.addr == addr)
+            .map(|(&id, _)| id);
+        match message {
+            ClientMessage::Hello { version } if version != PROTOCOL_VERSION => {
+                let reason = format!("protocol {version}, server speaks {PROTOCOL_VERSION}");
+                self.send(addr, &ServerMessage::Refused { reason });
+            }
+            ClientMessage::Hello { .. } => match known.or_else(|| self.join(addr)) {
+                // A repeated hello means our welcome got lost; resend it.
+                Some(player) => self.send(addr, &ServerMessage::Welcome { player }),
+                None => {
+                    let reason = "server is full".to_string();
+                    self.send(addr, &ServerMessage::Refused { reason });
+                }
+            },
+            ClientMessage::Input { seq, input } => {
+                if let Some(player) = known.and_then(|id| self.players.get_mut(&id)) {
+                    player.heard = self.tick;
+                    // Datagrams may overtake each other; older inputs are stale.
+                    if seq > player.ack {
+                        player.input = input;
+                        player.ack = seq;
+                    }
+                }
+            }
+            ClientMessage::Bye => {
+                if let Some(id) = known {
+                    log::info!("player {id} left");
+                    self.leave(id);
+                }
+            }
+        }
+    }
+
+    /// Give `addr` the lowest free slot and a car at that slot's spawn.
+    fn join(&mut self, addr: SocketAddr) -> Option<PlayerId> {
+        let id = (0..MAX_PLAYERS).find(|id| !self.players.contains_key(id))?;
+        let map = &self.arena.map;
+        let axial = vehicle::spawn_axial(map) + SPAWN_SPACING * f32::from(id);
+        let pose = vehicle::spawn_pose(map, self.arena.spawn_radius, axial);
+        let vehicle = Vehicle::spawn(
+            &mut self.physics,
+            &self.arena.car,
+            &self.arena.car_model,
+            pose.into(),
+        );
+        log::info!("player {id} joined from {addr}");
+        self.players.insert(
+            id,
+            Player {
+                addr,
+                vehicle,
+                recovery: Recovery::new(self.arena.recovery, pose.into()),
+                input: DriveInput::default(),
+                ack: 0,
+                heard: self.tick,
+            },
+        );
+        Some(id)
+    }
+
+    fn leave(&mut self, id: PlayerId) {
+        if let Some(player) = self.players.remove(&id) {
+            player.vehicle.despawn(&mut self.physics);
+        }
+    }
+
+    fn send(&self, addr: SocketAddr, message: &ServerMessage) {
+        self.send_bytes(addr, &encode(message));
+    }
+
+    fn send_bytes(&self, addr: SocketAddr, bytes: &[u8]) {
+        // One unreachable client must not take the server down.
+        if let Err(e) = self.socket.send_to(bytes, addr) {
+            log::warn!("send to {addr} failed: {e}");
+        }
+    }
+}
+
+struct RemoteState {
+    tick: u32,
+    cars: Vec<(PlayerId, Vec<Kinematics>)>,
+}
+
+/// A player's end of the connection. Call `poll` once per physics tick,
+/// then `send_input`, step the local world and `record` the outcome;
+/// `reconcile` may go anywhere in between.
+pub struct Client {
+    socket: UdpSocket,
+    server: SocketAddr,
+    player: Option<PlayerId>,
+    /// Ticks spent waiting for a welcome.
+    waiting: u32,
+    seq: u32,
+    /// Our chassis as predicted right after each input the server has not
+    /// acknowledged yet, oldest first.
+    predictions: VecDeque<(u32, Kinematics)>,
+    /// The newest server word on our car, waiting for `reconcile`.
+    authority: Option<(u32, Vec<Kinematics>)>,
+    /// Whether our car has been put onto the server's yet.
+    synced: bool,
+    /// Recent server updates, oldest first, for drawing the other cars.
+    states: VecDeque<RemoteState>,
+    /// Server tick the other cars are drawn at.
+    playback: f32,
+}
+
+impl Client {
+    /// Open a socket and say hello to `server`.
+    pub fn connect(server: SocketAddr) -> io::Result<Self> {
+        let local: SocketAddr = if server.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            server,
+            player: None,
+            waiting: 0,
+            seq: 0,
+            predictions: VecDeque::new(),
+            authority: None,
+            synced: false,
+            states: VecDeque::new(),
+            playback: 0.0,
+        })
+    }
+
+    /// Our slot on the server, once welcomed.
+    pub fn player(&self) -> Option<PlayerId> {
+        self.player
+    }
+
+    /// Take in everything the server sent and advance the playback clock
+    /// by a tick.
+    pub fn poll(&mut self) -> io::Result<()> {
+        profiling::scope!("Client::poll");
+        if self.player.is_none() {
+            if self.waiting % HELLO_INTERVAL == 0 {
+                let hello = ClientMessage::Hello {
+                    version: PROTOCOL_VERSION,
+                };
+                self.send(&hello)?;
+            }
+            self.waiting += 1;
+        }
+        let mut buf = vec![0; MAX_DATAGRAM];
+        while let Some((len, addr)) = receive(&self.socket, &mut buf)? {
+            if addr != self.server {
+                continue;
+            }
+            match decode(&buf[..len]) {
+                Some(ServerMessage::Welcome { player }) => {
+                    if self.player.is_none() {
+                        log::info!("joined {} as player {player}", self.server);
+                        self.player = Some(player);
+                    }
+                }
+                Some(ServerMessage::Refused { reason }) => {
+                    return Err(io::Error::other(format!("server refused: {reason}")));
+                }
+                Some(ServerMessage::State(update)) => self.accept(update),
+                None => log::warn!("undecodable datagram from the server"),
+            }
+        }
+
+        self.playback += 1.0;
+        if let Some(newest) = self.states.back() {
+            let target = newest.tick as f32 - INTERPOLATION_DELAY;
+            let drift = target - self.playback;
+            if drift.abs() > MAX_PLAYBACK_DRIFT {
+                self.playback = target;
+            } else {
+                self.playback += drift * PLAYBACK_CATCH_UP;
+            }
+        }
+        Ok(())
+    }
+
+    fn accept(&mut self, update: StateUpdate) {
+        if self
+            .states
+            .back()
+            .is_some_and(|state| state.tick >= update.tick)
+        {
+            // Late or duplicated.
+            return;
+        }
+        let mut cars = Vec::with_capacity(update.cars.len());
+        for car in update.cars {
+            let bodies = car.bodies.iter().map(PackedBody::unpack).collect();
+            if Some(car.player) == self.player {
+                self.authority = Some((car.ack, bodies));
+            } else {
+                cars.push((car.player, bodies));
+            }
+        }
+        self.states.push_back(RemoteState {
+            tick: update.tick,
+            cars,
+        });
+        if self.states.len() > STATE_BUFFER {
+            self.states.pop_front();
+        }
+    }
+
+    /// Send the controls for the next tick, returning their sequence
+    /// number. Before the welcome they only count up.
+    pub fn send_input(&mut self, input: DriveInput) -> io::Result<u32> {
+        self.seq += 1;
+        if self.player.is_some() {
+            let message = ClientMessage::Input {
+                seq: self.seq,
+                input,
+            };
+            self.send(&message)?;
+        }
+        Ok(self.seq)
+    }
+
+    /// Remember where `vehicle` ended up after the local step for the last
+    /// input sent.
+    pub fn record(&mut self, physics: &Physics, vehicle: &Vehicle) {
+        if let Some(kinematics) = physics.body_kinematics(vehicle.chassis) {
+            self.predictions.push_back((self.seq, kinematics));
+            if self.predictions.len() > MAX_PREDICTIONS {
+                self.predictions.pop_front();
+            }
+        }
+    }
+
+    /// Compare the newest server state of our car with what we predicted
+    /// for the same input. Small errors are eased out a fraction at a time;
+    /// large ones, or no matching prediction, put the car straight onto
+    /// the server's state. Returns the position error (m) when there was a
+    /// server state to compare against.
+    pub fn reconcile(&mut self, physics: &mut Physics, vehicle: &Vehicle) -> Option<f32> {
+        let (ack, bodies) = self.authority.take()?;
+        let server = *bodies.first()?;
+        while self.predictions.front().is_some_and(|&(seq, _)| seq < ack) {
+            self.predictions.pop_front();
+        }
+        let predicted = self
+            .predictions
+            .front()
+            .filter(|&&(seq, _)| seq == ack)
+            .map(|&(_, kinematics)| kinematics);
+
+        if let Some(predicted) = predicted.filter(|_| self.synced) {
+            let error = Vec3::from(server.translation) - Vec3::from(predicted.translation);
+            let turn = rotation(&server).angle_between(rotation(&predicted));
+            if error.length() <= SNAP_DISTANCE && turn <= SNAP_ANGLE {
+                let step = error * CORRECTION;
+                vehicle.shift(physics, step);
+                let dv = (Vec3::from(server.linvel) - Vec3::from(predicted.linvel)) * CORRECTION;
+                let linvel = physics.body_linvel(vehicle.chassis) + dv;
+                physics.set_linvel(vehicle.chassis, linvel);
+                // Later predictions were made before the shift; move them
+                // along so the next comparison sees only fresh error.
+                for &mut (_, ref mut kinematics) in self.predictions.iter_mut() {
+                    kinematics.translation = (Vec3::from(kinematics.translation) + step).to_array();
+                }
+                return Some(error.length());
+            }
+        }
+
+        let error = predicted.map_or(f32::INFINITY, |p| {
+            Vec3::from(server.translation).distance(Vec3::from(p.translation))
+        });
+        vehicle.place(physics, pose(&server));
+        for (body, kinematics) in vehicle.bodies().into_iter().zip(bodies.iter()) {
+            physics.set_body_kinematics(body, kinematics);
+        }
+        // Everything predicted so far started from the old state.
+        self.predictions.clear();
+        if self.synced {
+            log::info!("prediction off by {error:.2} m, snapped to the server");
+        }
+        self.synced = true;
+        Some(error)
+    }
+
+    /// The other players' cars at the playback tick, each interpolated
+    /// between the server updates around it. Bodies come in
+    /// `Vehicle::bodies` order.
+    pub fn remote_cars(&self) -> Vec<(PlayerId, Vec<Kinematics>)> {
+        let Some(newest) = self.states.back() else {
+            return Vec::new();
+        };
+        let later = self
+            .states
+            .iter()
+            .position(|state| state.tick as f32 > self.playback);
+        let (a, b) = match later {
+            // Past the newest update: hold it rather than guess ahead.
+            None => return newest.cars.clone(),
+            Some(0) => return self.states[0].cars.clone(),
+            Some(i) => (&self.states[i - 1], &self.states[i]),
+        };
+        let t = (self.playback - a.tick as f32) / (b.tick - a.tick) as f32;
+        b.cars
+            .iter()
+            .map(|&(player, ref later)| {
+                let earlier = a.cars.iter().find(|&&(p, _)| p == player);
+                let bodies = match earlier {
+                    Some(&(_, ref earlier)) if earlier.len() == later.len() => earlier
+                        .iter()
+                        .zip(later)
+                        .map(|(ka, kb)| lerp_kinematics(ka, kb, t))
+                        .collect(),
+                    // Just joined: nothing to blend from.
+                    _ => later.clone(),
+                };
+                (player, bodies)
+            })
+            .collect()
+    }
+
+    /// Tell the server we are leaving.
+    pub fn disconnect(&self) -> io::Result<()> {
+        self.send(&ClientMessage::Bye)
+    }
+
+    fn send(&self, message: &ClientMessage) -> io::Result<()> {
+        self.socket.send_to(&encode(message), self.server)?;
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
-use crate::config::WorldShape;
+use crate::config::{self, WorldShape};
 use rapier3d::math::{Vec3, Vector};
-use std::default::Default;
+use std::{collections::HashMap, default::Default};
 
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct TerrainBody {
     pub(crate) body: rapier3d::dynamics::RigidBodyHandle,
     pub shape: WorldShape,
@@ -11,6 +12,12 @@ pub struct TerrainBody {
     /// analytically from the map config — the terrain colliders are open
     /// triangle meshes, which have no meaningful volume of their own.
     gravity_mass: f32,
+    /// Mean ground radius; the reference distance for `InverseLinear`.
+    surface_radius: f32,
+    gravity: config::GravityModel,
+    gravity_zones: Vec<config::GravityZone>,
+    /// Collider of each mesh chunk, `None` where the chunk is empty.
+    chunk_colliders: Vec<Option<rapier3d::geometry::ColliderHandle>>,
 }
 
 impl TerrainBody {
@@ -35,6 +42,12 @@ impl TerrainBody {
         }
     }
 
+    /// The fixed body all terrain chunk colliders hang off, for telling
+    /// ground hits apart from everything else.
+    pub fn body(&self) -> rapier3d::dynamics::RigidBodyHandle {
+        self.body
+    }
+
     /// Unit "up" (radially away from the gravity anchor) at `pos`. Falls
     /// back to +Y when `pos` is degenerate (on the anchor itself).
     pub fn up(&self, pos: Vec3) -> Vec3 {
@@ -46,6 +59,53 @@ impl TerrainBody {
             d / len
         }
     }
+
+    /// Gravitational acceleration at `pos`: the map's gravity model scaled
+    /// by every zone containing `pos`.
+    fn gravity_accel(&self, pos: Vec3) -> Vec3 {
+        let to_body = pos - self.gravity_anchor(pos);
+        let r = to_body.length();
+        if r < 1e-3 {
+            return Vec3::ZERO;
+        }
+        let magnitude = match self.gravity {
+            config::GravityModel::Newtonian { max_accel } => {
+                (GRAVITY * self.gravity_mass / (r * r)).min(max_accel)
+            }
+            config::GravityModel::Constant { accel } => accel,
+            config::GravityModel::InverseLinear { accel } => accel * self.surface_radius / r,
+        };
+        let scale: f32 = self
+            .gravity_zones
+            .iter()
+            .filter(|zone| match zone.region {
+                config::GravityRegion::Sphere { center, radius } => {
+                    (pos - Vec3::from(center)).length_squared() <= radius * radius
+                }
+                config::GravityRegion::Band { ref radius } => radius.contains(&r),
+            })
+            .map(|zone| zone.scale)
+            .product();
+        to_body * (-magnitude * scale / r)
+    }
+}
+
+/// First thing a ray ran into; see `Physics::cast_ray`.
+#[derive(Clone, Copy, Debug)]
+pub struct RayHit {
+    /// Body owning the struck collider — the terrain body for the ground.
+    pub body: Option<rapier3d::dynamics::RigidBodyHandle>,
+    pub point: Vec3,
+    /// Surface normal at `point`, facing back along the ray.
+    pub normal: Vec3,
+    pub distance: f32,
+}
+
+/// A body resting on the ground; see `Physics::ground_contact`.
+#[derive(Clone, Copy, Debug)]
+pub struct GroundContact {
+    pub normal: Vec3,
+    pub force: f32,
 }
 
 pub struct PhysicsBodyHandle {
@@ -61,7 +121,39 @@ pub struct Kinematics {
     pub angvel: [f32; 3],
 }
 
-#[derive(Default)]
+/// A copy of the whole simulation, taken by `Physics::snapshot`.
+#[derive(Clone)]
+pub struct PhysicsSnapshot {
+    bytes: Vec<u8>,
+}
+
+impl PhysicsSnapshot {
+    /// Size of the encoded state, in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+/// Where an FNV-1a hash starts, before any bytes are folded in.
+pub const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Fold `bytes` into an FNV-1a hash. Spelled out rather than taken from
+/// `std::hash` so the values stay comparable across toolchains and runs.
+pub fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |h, &b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The whole simulation. It serializes complete — bodies, colliders
+/// (terrain edits included), joints with their motor targets, and the
+/// broad- and narrow-phase caches — so a deserialized world continues
+/// exactly where the saved one was, with every handle still valid.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
 pub struct Physics {
     rigid_bodies: rapier3d::dynamics::RigidBodySet,
     integration_params: rapier3d::dynamics::IntegrationParameters,
@@ -72,10 +164,45 @@ pub struct Physics {
     colliders: rapier3d::geometry::ColliderSet,
     broad_phase: rapier3d::geometry::DefaultBroadPhase,
     narrow_phase: rapier3d::geometry::NarrowPhase,
+    /// Scratch space only; nothing in it outlives a step.
+    #[serde(skip)]
     pipeline: rapier3d::pipeline::PhysicsPipeline,
+    /// Drag area (`C_d · A`, m²) of every body that feels the air. Bodies
+    /// not listed here are unaffected by `update_aerodynamics`.
+    drag_areas: HashMap<rapier3d::dynamics::RigidBodyHandle, f32>,
     last_time: f32,
 }
 
+//Note: real world power is -11, but our scales are different
+const GRAVITY: f32 = 1e-3;
+
+/// Air velocity of `wind` at `pos` and simulation time `time`.
+fn wind_velocity(wind: &config::Wind, pos: Vec3, time: f32) -> Vec3 {
+    match *wind {
+        config::Wind::Calm => Vec3::ZERO,
+        config::Wind::Uniform { velocity } => Vec3::from(velocity),
+        config::Wind::Vortex { speed } => {
+            let rxy = (pos.x * pos.x + pos.y * pos.y).sqrt();
+            if rxy < 1e-6 {
+                Vec3::ZERO
+            } else {
+                Vec3::new(-pos.y / rxy, pos.x / rxy, 0.0) * speed
+            }
+        }
+        config::Wind::Gusts {
+            velocity,
+            strength,
+            period,
+        } => {
+            use std::f32::consts::TAU;
+            // Two incommensurate sines so the gusts don't read as a metronome.
+            let t = time / period.max(1e-3);
+            let gust = 0.6 * (TAU * t).sin() + 0.4 * (TAU * 2.7 * t + 1.3).sin();
+            Vec3::from(velocity) * (1.0 + strength * gust)
+        }
+    }
+}
+
 impl Physics {
     /// Attach the terrain TIN as one fixed body with a trimesh collider per
     /// chunk (finest LOD) — the *same* mesh the renderer draws, so the
@@ -85,7 +212,6 @@ impl Physics {
         config: &super::MapConfig,
         mesh: &super::tin::TerrainMesh,
     ) -> TerrainBody {
-        use rapier3d::geometry::TriMeshFlags;
         use std::f32::consts::PI;
 
         let body =
@@ -94,44 +220,18 @@ impl Physics {
         let body_handle = self.rigid_bodies.insert(body);
 
         let mut triangles = 0usize;
+        let mut chunk_colliders = Vec::with_capacity(mesh.chunks.len());
         for chunk in &mesh.chunks {
-            let (vertices, indices) = chunk.lod0();
-            if indices.is_empty() {
-                continue;
-            }
-            triangles += indices.len() / 3;
-            let vertices: Vec<Vec3> = vertices
-                .iter()
-                .map(|v| Vec3::new(v[0], v[1], v[2]))
-                .collect();
-            let indices: Vec<[u32; 3]> = indices
-                .chunks_exact(3)
-                .map(|t| [t[0], t[1], t[2]])
-                .collect();
-            // FIX_INTERNAL_EDGES keeps wheels from snagging on the shared
-            // edges between coplanar-ish triangles as they roll across;
-            // DELETE_DEGENERATE_TRIANGLES drops the zero-area slivers the
-            // sphere's pole rows produce.
-            let collider = rapier3d::geometry::ColliderBuilder::trimesh_with_flags(
-                vertices,
-                indices,
-                TriMeshFlags::MERGE_DUPLICATE_VERTICES
-                    | TriMeshFlags::DELETE_DEGENERATE_TRIANGLES
-                    | TriMeshFlags::FIX_INTERNAL_EDGES,
-            )
-            .expect("degenerate terrain chunk trimesh")
-            .friction(1.0)
-            .build();
-            self.colliders
-                .insert_with_parent(collider, body_handle, &mut self.rigid_bodies);
+            triangles += chunk.lod0().1.len() / 3;
+            chunk_colliders.push(self.insert_terrain_chunk(body_handle, chunk));
         }
 
         // The Newtonian gravity formula (see `update_gravity`) wants a mass
         // for the terrain. The meshes are open surfaces, so derive it from
         // an equivalent solid instead. The sphere keeps its deliberately
         // inflated virtual ball (see the git history of sphere gravity
-        // tuning): near the surface it saturates the MAX_ACCEL cap, which is
-        // what makes driving feel rooted.
+        // tuning): near the surface it saturates the Newtonian `max_accel`
+        // cap, which is what makes driving feel rooted.
         let r_mid = 0.5 * (config.radius.start + config.radius.end);
         let major_radius = config.length / std::f32::consts::TAU;
         let volume = match config.shape {
@@ -155,6 +255,70 @@ impl Physics {
             shape: config.shape,
             major_radius,
             gravity_mass: volume * config.density,
+            surface_radius: r_mid,
+            gravity: config.gravity,
+            gravity_zones: config.gravity_zones.clone(),
+            chunk_colliders,
+        }
+    }
+
+    /// Trimesh collider for one terrain chunk at its finest LOD, attached to
+    /// the terrain body. `None` for a chunk with no triangles.
+    fn insert_terrain_chunk(
+        &mut self,
+        body: rapier3d::dynamics::RigidBodyHandle,
+        chunk: &super::tin::ChunkBuffers,
+    ) -> Option<rapier3d::geometry::ColliderHandle> {
+        use rapier3d::geometry::TriMeshFlags;
+        let (vertices, indices) = chunk.lod0();
+        if indices.is_empty() {
+            return None;
+        }
+        let vertices: Vec<Vec3> = vertices
+            .iter()
+            .map(|v| Vec3::new(v[0], v[1], v[2]))
+            .collect();
+        let indices: Vec<[u32; 3]> = indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect();
+        // FIX_INTERNAL_EDGES keeps wheels from snagging on the shared
+        // edges between coplanar-ish triangles as they roll across;
+        // DELETE_DEGENERATE_TRIANGLES drops the zero-area slivers the
+        // sphere's pole rows produce.
+        let collider = rapier3d::geometry::ColliderBuilder::trimesh_with_flags(
+            vertices,
+            indices,
+            TriMeshFlags::MERGE_DUPLICATE_VERTICES
+                | TriMeshFlags::DELETE_DEGENERATE_TRIANGLES
+                | TriMeshFlags::FIX_INTERNAL_EDGES,
+        )
+        .expect("degenerate terrain chunk trimesh")
+        .friction(1.0)
+        .build();
+        Some(
+            self.colliders
+                .insert_with_parent(collider, body, &mut self.rigid_bodies),
+        )
+    }
+
+    /// Swap the colliders of the given chunks for ones built from their
+    /// current geometry in `mesh` — the physics half of a terrain edit
+    /// (see `TerrainMesh::dig_crater`).
+    pub fn rebuild_terrain_chunks(
+        &mut self,
+        terrain: &mut TerrainBody,
+        mesh: &super::tin::TerrainMesh,
+        chunks: &[usize],
+    ) {
+        profiling::scope!("Physics::rebuild_terrain_chunks");
+        for &index in chunks {
+            if let Some(old) = terrain.chunk_colliders[index].take() {
+                self.colliders
+                    .remove(old, &mut self.island_manager, &mut self.rigid_bodies, true);
+            }
+            terrain.chunk_colliders[index] =
+                self.insert_terrain_chunk(terrain.body, &mesh.chunks[index]);
         }
     }
 
@@ -193,6 +357,29 @@ impl Physics {
         }
     }
 
+    /// Add `collider` to a body that is already in the world.
+    pub fn add_collider(
+        &mut self,
+        rb_handle: rapier3d::dynamics::RigidBodyHandle,
+        collider: rapier3d::geometry::Collider,
+    ) -> rapier3d::geometry::ColliderHandle {
+        self.colliders
+            .insert_with_parent(collider, rb_handle, &mut self.rigid_bodies)
+    }
+
+    /// Remove a body together with its colliders and joints.
+    pub fn remove_rigid_body(&mut self, rb_handle: rapier3d::dynamics::RigidBodyHandle) {
+        self.drag_areas.remove(&rb_handle);
+        self.rigid_bodies.remove(
+            rb_handle,
+            &mut self.island_manager,
+            &mut self.colliders,
+            &mut self.impulse_joints,
+            &mut self.multibody_joints,
+            true,
+        );
+    }
+
     pub fn add_revolute_joint(
         &mut self,
         body1: rapier3d::dynamics::RigidBodyHandle,
@@ -233,6 +420,24 @@ impl Physics {
         }
     }
 
+    /// Caps the torque of the motor `set_joint_motor_velocity` drives, so
+    /// the same motor can push a wheel round or brake it with a set force.
+    pub fn set_joint_motor_max_force(
+        &mut self,
+        handle: rapier3d::dynamics::ImpulseJointHandle,
+        max_force: f32,
+    ) {
+        if let Some(joint) = self.impulse_joints.get_mut(handle, true) {
+            if let Some(rev) = joint.data.as_revolute_mut() {
+                rev.set_motor_max_force(max_force);
+            } else {
+                joint
+                    .data
+                    .set_motor_max_force(rapier3d::dynamics::JointAxis::AngZ, max_force);
+            }
+        }
+    }
+
     /// Sets a position-target spring motor on the given joint axis. Used by
     /// front-wheel steering: the wheel's AngY joint axis is free, and a motor
     /// pulls it toward the steer-input angle with the given spring constants.
@@ -251,6 +456,19 @@ impl Physics {
         }
     }
 
+    /// The target angle `set_joint_motor_position` last gave the joint's
+    /// `axis`, or `0` before it was ever set.
+    pub fn joint_motor_position(
+        &self,
+        handle: rapier3d::dynamics::ImpulseJointHandle,
+        axis: rapier3d::dynamics::JointAxis,
+    ) -> f32 {
+        self.impulse_joints
+            .get(handle)
+            .and_then(|joint| joint.data.motor(axis))
+            .map_or(0.0, |motor| motor.target_pos)
+    }
+
     /// Split the chassis's angular velocity into a "yaw" component (about the
     /// world up axis at its current position — i.e. the direction gravity
     /// points away from) and a "tumble" component (everything else), then
@@ -286,35 +504,65 @@ impl Physics {
         rb.set_angvel(omega_yaw * f_yaw + omega_tumble * f_tumble, true);
     }
 
-    /// Apply radial gravity (toward the terrain's gravity anchor) to every
-    /// dynamic body.
+    /// Gravitational acceleration (m/s²) a body would feel at `pos`, for
+    /// AI and projectile prediction. Matches what `update_gravity` applies.
+    pub fn gravity_at(&self, terrain: &TerrainBody, pos: Vec3) -> Vec3 {
+        terrain.gravity_accel(pos)
+    }
+
+    /// Apply the map's gravity (toward the terrain's gravity anchor, see
+    /// `config::GravityModel`) to every dynamic body.
     pub fn update_gravity(&mut self, terrain: &TerrainBody) {
         profiling::scope!("Physics::update_gravity");
-        //Note: real world power is -11, but our scales are different
-        const GRAVITY: f32 = 1e-3;
-        /// Cap on the effective radial acceleration (m/s²). Without it the Newtonian
-        /// G·M_terrain/r² spikes well past the wheel motor's friction cap on larger
-        /// maps and pins the vehicle in place. Picked above the effective gravity
-        /// the legacy synthetic tests see (~10 m/s² near the axis) so their
-        /// settling dynamics are preserved.
-        const MAX_ACCEL: f32 = 12.0;
-        let terrain_mass = terrain.gravity_mass;
         for (_handle, rb) in self.rigid_bodies.iter_mut() {
+            if !rb.is_dynamic() {
+                continue;
+            }
+            let accel = terrain.gravity_accel(rb.position().translation);
+            rb.reset_forces(false);
+            if accel != Vec3::ZERO {
+                rb.add_force(accel * rb.mass(), true);
+            }
+        }
+    }
+
+    /// Register `body` for quadratic air drag with the given drag area
+    /// (`C_d · A`, m²). A non-positive area removes it again.
+    pub fn set_drag_area(&mut self, body: rapier3d::dynamics::RigidBodyHandle, drag_area: f32) {
+        if drag_area > 0.0 {
+            self.drag_areas.insert(body, drag_area);
+        } else {
+            self.drag_areas.remove(&body);
+        }
+    }
+
+    /// Air velocity at `pos` right now. Exposed so effects and AI can lean
+    /// into the same wind the bodies feel.
+    pub fn wind_at(&self, atmosphere: &config::Atmosphere, pos: Vec3) -> Vec3 {
+        wind_velocity(&atmosphere.wind, pos, self.last_time)
+    }
+
+    /// Apply `F = -½·ρ·C_d·A·|v_rel|·v_rel` to every body registered with
+    /// `set_drag_area`, where `v_rel` is the body's velocity relative to the
+    /// wind at its position. Must be called AFTER `update_gravity`, which
+    /// resets forces.
+    pub fn update_aerodynamics(&mut self, atmosphere: &config::Atmosphere) {
+        profiling::scope!("Physics::update_aerodynamics");
+        let half_rho = 0.5 * atmosphere.density;
+        for (&handle, &drag_area) in self.drag_areas.iter() {
+            let Some(rb) = self.rigid_bodies.get_mut(handle) else {
+                continue;
+            };
             if !rb.is_dynamic() {
                 continue;
             }
             let pos = rb.position().translation;
-            let to_body = pos - terrain.gravity_anchor(pos);
-            let radial_sq = to_body.length_squared();
-            if radial_sq < 1e-6 {
-                rb.reset_forces(false);
+            let v_rel = rb.linvel() - wind_velocity(&atmosphere.wind, pos, self.last_time);
+            let speed = v_rel.length();
+            if speed < 1e-4 {
                 continue;
             }
-            let mass = rb.mass();
-            let gravity_uncapped = GRAVITY * mass * terrain_mass / radial_sq;
-            let gravity = gravity_uncapped.min(MAX_ACCEL * mass);
-            rb.reset_forces(false);
-            rb.add_force(-to_body.normalize() * gravity, true);
+            rb.add_force(-v_rel * (half_rho * drag_area * speed), true);
         }
     }
 
@@ -346,6 +594,49 @@ impl Physics {
         }
     }
 
+    /// Move a body to `pose`, leaving its velocities alone.
+    pub fn set_body_pose(
+        &mut self,
+        rb_handle: rapier3d::dynamics::RigidBodyHandle,
+        pose: rapier3d::math::Pose,
+    ) {
+        if let Some(rb) = self.rigid_bodies.get_mut(rb_handle) {
+            rb.set_position(pose, true);
+        }
+    }
+
+    /// Where a kinematic body is to be moved by the next step; its
+    /// velocities follow from the move.
+    pub fn set_next_body_pose(
+        &mut self,
+        rb_handle: rapier3d::dynamics::RigidBodyHandle,
+        pose: rapier3d::math::Pose,
+    ) {
+        if let Some(rb) = self.rigid_bodies.get_mut(rb_handle) {
+            rb.set_next_kinematic_position(pose);
+            rb.wake_up(true);
+        }
+    }
+
+    /// Put a body into the state `kinematics` describes: pose and both
+    /// velocities.
+    pub fn set_body_kinematics(
+        &mut self,
+        rb_handle: rapier3d::dynamics::RigidBodyHandle,
+        kinematics: &Kinematics,
+    ) {
+        if let Some(rb) = self.rigid_bodies.get_mut(rb_handle) {
+            let [x, y, z, w] = kinematics.rotation;
+            let pose = rapier3d::math::Pose::from_parts(
+                Vec3::from(kinematics.translation),
+                rapier3d::math::Rotation::from_xyzw(x, y, z, w),
+            );
+            rb.set_position(pose, true);
+            rb.set_linvel(Vec3::from(kinematics.linvel), true);
+            rb.set_angvel(Vec3::from(kinematics.angvel), true);
+        }
+    }
+
     pub fn apply_impulse(
         &mut self,
         rb_handle: rapier3d::dynamics::RigidBodyHandle,
@@ -405,6 +696,135 @@ impl Physics {
         false
     }
 
+    /// Where `rb_handle` rests on the terrain as of the last step: the
+    /// ground normal (pointing out of the ground) and the force the ground
+    /// pushed back with, in N. `None` while it is off the ground.
+    pub fn ground_contact(
+        &self,
+        rb_handle: rapier3d::dynamics::RigidBodyHandle,
+        terrain: &TerrainBody,
+    ) -> Option<GroundContact> {
+        let rb = self.rigid_bodies.get(rb_handle)?;
+        let mut normal = Vec3::ZERO;
+        let mut impulse = 0.0;
+        for &c in rb.colliders() {
+            for pair in self.narrow_phase.contact_pairs_with(c) {
+                if !pair.has_any_active_contact() {
+                    continue;
+                }
+                let (other, sign) = if pair.collider1 == c {
+                    (pair.collider2, -1.0)
+                } else {
+                    (pair.collider1, 1.0)
+                };
+                if self.colliders.get(other).and_then(|col| col.parent()) != Some(terrain.body) {
+                    continue;
+                }
+                // Manifold normals point from collider 1 to collider 2.
+                for manifold in &pair.manifolds {
+                    let pushed: f32 = manifold.points.iter().map(|p| p.data.impulse).sum();
+                    normal += manifold.data.normal * (sign * pushed.max(1e-6));
+                    impulse += pushed;
+                }
+            }
+        }
+        (normal != Vec3::ZERO).then(|| GroundContact {
+            normal: normal.normalize(),
+            force: impulse / self.integration_params.dt,
+        })
+    }
+
+    /// Every other body that one of `rb_handle`'s colliders is in active
+    /// contact with, as of the last step.
+    pub fn touching_bodies(
+        &self,
+        rb_handle: rapier3d::dynamics::RigidBodyHandle,
+    ) -> Vec<rapier3d::dynamics::RigidBodyHandle> {
+        let mut bodies = Vec::new();
+        let Some(rb) = self.rigid_bodies.get(rb_handle) else {
+            return bodies;
+        };
+        for &c in rb.colliders() {
+            for pair in self.narrow_phase.contact_pairs_with(c) {
+                if !pair.has_any_active_contact() {
+                    continue;
+                }
+                let other = if pair.collider1 == c {
+                    pair.collider2
+                } else {
+                    pair.collider1
+                };
+                if let Some(body) = self.colliders.get(other).and_then(|col| col.parent()) {
+                    if body != rb_handle && !bodies.contains(&body) {
+                        bodies.push(body);
+                    }
+                }
+            }
+        }
+        bodies
+    }
+
+    /// Every body other than its own that the sensor `collider` overlaps,
+    /// as of the last step.
+    pub fn sensor_overlaps(
+        &self,
+        collider: rapier3d::geometry::ColliderHandle,
+    ) -> Vec<rapier3d::dynamics::RigidBodyHandle> {
+        let own = self.colliders.get(collider).and_then(|col| col.parent());
+        let mut bodies = Vec::new();
+        for (collider1, collider2, intersecting) in
+            self.narrow_phase.intersection_pairs_with(collider)
+        {
+            if !intersecting {
+                continue;
+            }
+            let other = if collider1 == collider {
+                collider2
+            } else {
+                collider1
+            };
+            match self.colliders.get(other).and_then(|col| col.parent()) {
+                Some(body) if Some(body) != own && !bodies.contains(&body) => bodies.push(body),
+                _ => {}
+            }
+        }
+        bodies
+    }
+
+    /// Cast a ray from `origin` along `dir` against every solid collider
+    /// except those on the `exclude` bodies, up to `max_distance` metres.
+    /// Sensors let it through.
+    pub fn cast_ray(
+        &self,
+        origin: Vec3,
+        dir: Vec3,
+        max_distance: f32,
+        exclude: &[rapier3d::dynamics::RigidBodyHandle],
+    ) -> Option<RayHit> {
+        use rapier3d::geometry::{Collider, ColliderHandle, Ray};
+        let keep = |_: ColliderHandle, collider: &Collider| {
+            !collider
+                .parent()
+                .is_some_and(|body| exclude.contains(&body))
+        };
+        let query = self.broad_phase.as_query_pipeline(
+            self.narrow_phase.query_dispatcher(),
+            &self.rigid_bodies,
+            &self.colliders,
+            rapier3d::pipeline::QueryFilter::default()
+                .exclude_sensors()
+                .predicate(&keep),
+        );
+        let ray = Ray::new(origin, dir.normalize());
+        let (collider, hit) = query.cast_ray_and_get_normal(&ray, max_distance, true)?;
+        Some(RayHit {
+            body: self.colliders.get(collider).and_then(|col| col.parent()),
+            point: ray.point_at(hit.time_of_impact),
+            normal: hit.normal,
+            distance: hit.time_of_impact,
+        })
+    }
+
     /// Adds a continuous force to a body (applied for the duration of one physics
     /// step, then cleared on the next `reset_forces`). Must be called AFTER
     /// `update_gravity` since `update_gravity` resets forces.
@@ -440,6 +860,13 @@ impl Physics {
         }
     }
 
+    pub fn body_pose(
+        &self,
+        rb_handle: rapier3d::dynamics::RigidBodyHandle,
+    ) -> Option<rapier3d::math::Pose> {
+        self.rigid_bodies.get(rb_handle).map(|rb| *rb.position())
+    }
+
     pub fn body_linvel(
         &self,
         rb_handle: rapier3d::dynamics::RigidBodyHandle,
@@ -458,6 +885,17 @@ impl Physics {
             .map_or(rapier3d::math::Vec3::ZERO, |rb| rb.angvel())
     }
 
+    /// Velocity of the point of the body at world-space `point`.
+    pub fn velocity_at_point(
+        &self,
+        rb_handle: rapier3d::dynamics::RigidBodyHandle,
+        point: rapier3d::math::Vec3,
+    ) -> rapier3d::math::Vec3 {
+        self.rigid_bodies
+            .get(rb_handle)
+            .map_or(rapier3d::math::Vec3::ZERO, |rb| rb.velocity_at_point(point))
+    }
+
     pub fn set_linvel(
         &mut self,
         rb_handle: rapier3d::dynamics::RigidBodyHandle,
@@ -518,4 +956,62 @@ impl Physics {
     pub fn last_time(&self) -> f32 {
         self.last_time
     }
+
+    /// Seconds each `step` advances.
+    pub fn dt(&self) -> f32 {
+        self.integration_params.dt
+    }
+
+    /// Capture the whole simulation — bodies, colliders, joints, islands,
+    /// broad and narrow phase — for a later `restore`.
+    pub fn snapshot(&self) -> PhysicsSnapshot {
+        profiling::scope!("Physics::snapshot");
+        let bytes = bincode::serde::encode_to_vec(self, bincode::config::standard())
+            .expect("physics state serializes");
+        PhysicsSnapshot { bytes }
+    }
+
+    /// Roll back to `snapshot`. Handles issued before it was taken are valid
+    /// again; ones issued since are not. Stepping on from here reproduces,
+    /// bit for bit, what stepping on from the snapshot did the first time.
+    pub fn restore(&mut self, snapshot: &PhysicsSnapshot) {
+        profiling::scope!("Physics::restore");
+        let (physics, _) =
+            bincode::serde::decode_from_slice(&snapshot.bytes, bincode::config::standard())
+                .expect("physics snapshot decodes");
+        *self = physics;
+    }
+
+    /// Hash of the dynamic state: the clock and every body's handle, pose
+    /// and velocities, bit for bit. Two runs that agree on it every tick
+    /// have stayed in lockstep.
+    pub fn state_hash(&self) -> u64 {
+        let mut hash = fnv1a(FNV_OFFSET, &self.last_time.to_bits().to_le_bytes());
+        for (handle, rb) in self.rigid_bodies.iter() {
+            let (index, generation) = handle.into_raw_parts();
+            let p = rb.position();
+            let (lv, av) = (rb.linvel(), rb.angvel());
+            let values = [
+                p.translation.x,
+                p.translation.y,
+                p.translation.z,
+                p.rotation.x,
+                p.rotation.y,
+                p.rotation.z,
+                p.rotation.w,
+                lv.x,
+                lv.y,
+                lv.z,
+                av.x,
+                av.y,
+                av.z,
+            ];
+            hash = fnv1a(hash, &index.to_le_bytes());
+            hash = fnv1a(hash, &generation.to_le_bytes());
+            for value in values {
+                hash = fnv1a(hash, &value.to_bits().to_le_bytes());
+            }
+        }
+        hash
+    }
 }
@@ -0,0 +1,134 @@
+//! Getting a car back into play when it leaves the world, falls through the
+//! ground, wedges itself or ends up on its roof. `Recovery` watches one car
+//! tick by tick, remembers the last good ground it stood on and, once
+//! something has been wrong for long enough, puts it back there upright.
+
+use crate::config::{self, WorldShape};
+use crate::physics::{Physics, TerrainBody};
+use crate::vehicle::{self, DriveInput, Vehicle};
+use rapier3d::math::{Pose, Vec3};
+
+/// Cosine of the largest tilt off the local up (60°) the car still counts
+/// as upright at.
+const UPRIGHT_COS: f32 = 0.5;
+/// Height (m) above its remembered ground a car is put back at, so the
+/// wheels drop onto the ground instead of starting inside it.
+const RESPAWN_LIFT: f32 = 0.5;
+
+/// What sent a car back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trouble {
+    /// Past either end of a cylinder world.
+    OutOfBounds,
+    /// Below the lowest ground the map can have.
+    Fallen,
+    /// Throttle held without getting anywhere.
+    Stuck,
+    /// On its roof or side.
+    Inverted,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Recovery {
+    settings: config::Recovery,
+    /// Chassis position the last time it stood upright on the ground
+    /// and was getting somewhere.
+    safe_position: [f32; 3],
+    /// Where its nose pointed then.
+    safe_forward: [f32; 3],
+    /// Seconds spent not upright.
+    inverted: f32,
+    /// Seconds of throttle spent within `stuck_distance` of `stuck_from`.
+    stuck: f32,
+    stuck_from: [f32; 3],
+    /// Seconds of invulnerability left.
+    invulnerable: f32,
+}
+
+impl Recovery {
+    /// Watch a car that starts out at `pose`, which counts as good ground
+    /// until it finds some.
+    pub fn new(settings: config::Recovery, pose: Pose) -> Self {
+        let position = pose.translation.to_array();
+        Self {
+            settings,
+            safe_position: position,
+            safe_forward: (pose.rotation * Vec3::NEG_X).to_array(),
+            inverted: 0.0,
+            stuck: 0.0,
+            stuck_from: position,
+            invulnerable: 0.0,
+        }
+    }
+
+    /// True for a short while after a respawn: the car takes no damage.
+    pub fn invulnerable(&self) -> bool {
+        self.invulnerable > 0.0
+    }
+
+    /// Look the car over after a step taken with `input`. Returns what is
+    /// wrong once it calls for a respawn; leaving the world does at once,
+    /// being stuck or inverted only after the configured time.
+    pub fn check(
+        &mut self,
+        physics: &Physics,
+        terrain: &TerrainBody,
+        map: &config::Map,
+        vehicle: &Vehicle,
+        input: &DriveInput,
+    ) -> Option<Trouble> {
+        let dt = physics.dt();
+        self.invulnerable = (self.invulnerable - dt).max(0.0);
+        let pose = physics.body_pose(vehicle.chassis)?;
+        let position = pose.translation;
+        if map.shape == WorldShape::Cylinder && position.z.abs() > 0.5 * map.length {
+            return Some(Trouble::OutOfBounds);
+        }
+        if (position - terrain.gravity_anchor(position)).length() < map.radius.start {
+            return Some(Trouble::Fallen);
+        }
+
+        let upright = (pose.rotation * Vec3::Y).dot(terrain.up(position)) > UPRIGHT_COS;
+        self.inverted = if upright { 0.0 } else { self.inverted + dt };
+        if self.inverted >= self.settings.inverted_time {
+            return Some(Trouble::Inverted);
+        }
+
+        let moved = (position - Vec3::from(self.stuck_from)).length();
+        if input.throttle == 0.0 || moved > self.settings.stuck_distance {
+            self.stuck = 0.0;
+            self.stuck_from = position.to_array();
+        } else {
+            self.stuck += dt;
+            if self.stuck >= self.settings.stuck_time {
+                return Some(Trouble::Stuck);
+            }
+        }
+
+        // Not while it might be wedged: that is no ground to come back to.
+        if upright && self.stuck == 0.0 && vehicle.grounded(physics, terrain) {
+            self.safe_position = position.to_array();
+            self.safe_forward = (pose.rotation * Vec3::NEG_X).to_array();
+        }
+        None
+    }
+
+    /// Put the car back on its last good ground, upright and at rest,
+    /// facing the way it was going, and start the invulnerability window.
+    pub fn respawn(&mut self, physics: &mut Physics, terrain: &TerrainBody, vehicle: &Vehicle) {
+        let ground = Vec3::from(self.safe_position);
+        let up = terrain.up(ground);
+        let position = ground + up * RESPAWN_LIFT;
+        let to_na = |v: Vec3| nalgebra::Vector3::new(v.x, v.y, v.z);
+        let pose = vehicle::upright_pose(
+            to_na(position),
+            to_na(up),
+            to_na(Vec3::from(self.safe_forward)),
+        );
+        vehicle.place(physics, pose.into());
+        self.inverted = 0.0;
+        self.stuck = 0.0;
+        self.stuck_from = position.to_array();
+        self.invulnerable = self.settings.invulnerability;
+    }
+}
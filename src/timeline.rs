@@ -0,0 +1,198 @@
+//! Scripted driving: a timeline of held keys and one-off events, written
+//! in RON and played back a physics tick at a time. Tests use it to script
+//! their runs, `sim` to drive its headless car and the game for its
+//! attract-mode demo.
+//!
+//! ```ron
+//! Timeline(
+//!     steps: [
+//!         Hold(keys: [], secs: 1.0),
+//!         Hold(keys: [Forward], secs: 2.0),
+//!         Hold(keys: [Forward, Left], secs: 1.0),
+//!         Event(Jump(charge: 0.0)),
+//!         Hold(keys: [], secs: 2.0),
+//!     ],
+//!     looping: false,
+//! )
+//! ```
+
+use crate::vehicle::DriveInput;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Key {
+    Forward,
+    Backward,
+    Brake,
+    Handbrake,
+    Left,
+    Right,
+    Turbo,
+    Fire,
+}
+
+impl Key {
+    fn press(self, input: &mut DriveInput) {
+        match self {
+            Self::Forward => input.throttle += 1.0,
+            Self::Backward => input.throttle -= 1.0,
+            Self::Brake => input.brake = 1.0,
+            Self::Handbrake => input.handbrake = true,
+            Self::Left => input.steer -= 1.0,
+            Self::Right => input.steer += 1.0,
+            Self::Turbo => input.turbo = true,
+            Self::Fire => input.fire = true,
+        }
+    }
+}
+
+/// Something that happens once rather than being held.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Event {
+    /// Jump, `charge` in `0..=1` picking between a tap and a full jump.
+    Jump {
+        #[serde(default)]
+        charge: f32,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Step {
+    /// Hold exactly `keys` for `secs`, rounded to whole ticks.
+    Hold { keys: Vec<Key>, secs: f32 },
+    /// Fire on the first tick of the next hold.
+    Event(Event),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Timeline {
+    pub steps: Vec<Step>,
+    /// Start over after the last step instead of finishing.
+    #[serde(default)]
+    pub looping: bool,
+}
+
+impl Timeline {
+    /// Hold `keys` for `secs` after everything so far.
+    pub fn hold(mut self, keys: &[Key], secs: f32) -> Self {
+        self.steps.push(Step::Hold {
+            keys: keys.to_vec(),
+            secs,
+        });
+        self
+    }
+
+    /// Fire `event` at the start of the next hold.
+    pub fn event(mut self, event: Event) -> Self {
+        self.steps.push(Step::Event(event));
+        self
+    }
+}
+
+/// What a timeline asks for on one tick.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tick {
+    pub input: DriveInput,
+    /// Events to fire before this tick's input is applied.
+    pub events: Vec<Event>,
+}
+
+/// Plays a `Timeline` back one physics tick of `dt` seconds per `next`,
+/// ending with it (or never, if it loops).
+pub struct TimelinePlayer {
+    timeline: Timeline,
+    dt: f32,
+    /// The step being played.
+    step: usize,
+    /// Ticks already spent in the current hold.
+    elapsed: u32,
+    /// Events passed over since the last tick.
+    pending: Vec<Event>,
+}
+
+impl TimelinePlayer {
+    pub fn new(timeline: Timeline, dt: f32) -> Self {
+        Self {
+            timeline,
+            dt,
+            step: 0,
+            elapsed: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Ticks one pass through the timeline takes.
+    pub fn duration(&self) -> u32 {
+        self.timeline
+            .steps
+            .iter()
+            .map(|step| match *step {
+                Step::Hold { secs, .. } => self.ticks(secs),
+                Step::Event(_) => 0,
+            })
+            .sum()
+    }
+
+    /// Back to the first step.
+    pub fn rewind(&mut self) {
+        self.step = 0;
+        self.elapsed = 0;
+        self.pending.clear();
+    }
+
+    fn ticks(&self, secs: f32) -> u32 {
+        (secs.max(0.0) / self.dt).round() as u32
+    }
+}
+
+impl Iterator for TimelinePlayer {
+    type Item = Tick;
+
+    fn next(&mut self) -> Option<Tick> {
+        // A looping timeline with no held time would spin here forever.
+        let mut restarts_left = if self.timeline.looping && self.duration() > 0 {
+            1
+        } else {
+            0
+        };
+        loop {
+            let Some(step) = self.timeline.steps.get(self.step) else {
+                if restarts_left > 0 {
+                    restarts_left -= 1;
+                    self.step = 0;
+                    self.elapsed = 0;
+                    continue;
+                }
+                // Events at the very end still get a tick of their own.
+                if self.pending.is_empty() {
+                    return None;
+                }
+                return Some(Tick {
+                    input: DriveInput::default(),
+                    events: std::mem::take(&mut self.pending),
+                });
+            };
+            match *step {
+                Step::Event(event) => {
+                    self.pending.push(event);
+                    self.step += 1;
+                }
+                Step::Hold { ref keys, secs } => {
+                    if self.elapsed >= self.ticks(secs) {
+                        self.step += 1;
+                        self.elapsed = 0;
+                        continue;
+                    }
+                    self.elapsed += 1;
+                    let mut input = DriveInput::default();
+                    for &key in keys {
+                        key.press(&mut input);
+                    }
+                    return Some(Tick {
+                        input,
+                        events: std::mem::take(&mut self.pending),
+                    });
+                }
+            }
+        }
+    }
+}
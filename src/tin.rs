@@ -109,6 +109,28 @@ impl Mapping {
         }
     }
 
+    /// Inverse of [`Mapping::embed`] in the horizontal: the continuous
+    /// texel-space `(x, y)` whose column passes through world point `pos`.
+    pub fn locate(&self, pos: [f32; 3]) -> [f32; 2] {
+        use std::f32::consts::TAU;
+        let [px, py, pz] = pos;
+        let (u, v) = match self.shape {
+            WorldShape::Cylinder => (py.atan2(px) / TAU, pz / self.length + 0.5),
+            WorldShape::Sphere => {
+                let r = (px * px + py * py + pz * pz).sqrt().max(1e-6);
+                (py.atan2(px) / TAU, 0.5 * (pz / r + 1.0))
+            }
+            WorldShape::Torus => {
+                let ring = px.hypot(py) - self.major_radius();
+                (pz.atan2(ring) / TAU, py.atan2(px) / TAU + 0.5)
+            }
+        };
+        [
+            u.rem_euclid(1.0) * self.width as f32,
+            v * self.height as f32,
+        ]
+    }
+
     /// Whether the `v` (axial) direction wraps around.
     fn wrap_y(&self) -> bool {
         matches!(self.shape, WorldShape::Torus)
@@ -191,6 +213,21 @@ impl Mapping {
         };
         alpha[y as usize * self.width as usize + x as usize] as f32
     }
+
+    /// Height byte at texel-space `(x, y)`, bilinear between the texel
+    /// centres around it.
+    pub fn height_at(&self, alpha: &[u8], x: f32, y: f32) -> f32 {
+        let (x, y) = (x - 0.5, y - 0.5);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (ix, iy) = (x0 as i32, y0 as i32);
+        let row = |y| {
+            let a = self.sample(alpha, ix, y);
+            a + (self.sample(alpha, ix + 1, y) - a) * fx
+        };
+        let top = row(iy);
+        top + (row(iy + 1) - top) * fy
+    }
 }
 
 /// The single quality knob, in `0..=1`, mapping to a vertical tolerance in
@@ -819,7 +856,197 @@ pub struct Stats {
 pub struct TerrainMesh {
     pub mapping: Mapping,
     pub chunks: Vec<ChunkBuffers>,
+    /// Stats of the initial build; edits don't update them.
     pub stats: Stats,
+    /// Texel coverage of each entry of `chunks`.
+    origins: Vec<ChunkOrigin>,
+}
+
+/// What a height-map edit touched, for refreshing the derived copies.
+pub struct Deformation {
+    /// Indices into `TerrainMesh::chunks` that were refitted.
+    pub chunks: Vec<usize>,
+    /// Height-map rows that changed, merged into runs. Two runs when the
+    /// edit straddles the torus seam.
+    pub rows: Vec<std::ops::Range<u32>>,
+}
+
+impl TerrainMesh {
+    /// Dig a bowl into the height map: `depth` metres at the world point
+    /// `center`, easing to nothing at `radius` metres. The chunks covering
+    /// the changed texels are refitted at the original tolerance.
+    pub fn dig_crater(
+        &mut self,
+        alpha: &mut [u8],
+        center: [f32; 3],
+        radius: f32,
+        depth: f32,
+    ) -> Deformation {
+        use std::collections::BTreeSet;
+        profiling::scope!("TerrainMesh::dig_crater");
+        let m = self.mapping;
+        let (width, height) = (m.width as i32, m.height as i32);
+        let distance = |a: [f32; 3], b: [f32; 3]| {
+            let d = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+            (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt()
+        };
+
+        // How many texels the bowl can reach in each direction, from the
+        // world size of one texel step at the centre. Capped short of the
+        // whole map so no column is visited twice.
+        let [cx, cy] = m.locate(center);
+        let (ix, iy) = (cx.floor() as i32, cy.floor() as i32);
+        let h_center = m.sample(alpha, ix, iy);
+        let texel_size = |dx: f32, dy: f32| {
+            let here = m.embed(cx, cy, h_center);
+            distance(m.embed(cx + dx, cy + dy, h_center), here).max(1e-4)
+        };
+        let reach_x = ((radius / texel_size(1.0, 0.0)).ceil() as i32 + 1).min((width - 1) / 2);
+        let reach_y = ((radius / texel_size(0.0, 1.0)).ceil() as i32 + 1).min((height - 1) / 2);
+
+        let bytes_per_metre = 255.0 / (m.radius_end - m.radius_start);
+        let mut cols = BTreeSet::new();
+        let mut rows = BTreeSet::new();
+        for ty in iy - reach_y..=iy + reach_y {
+            let y = if m.wrap_y() {
+                ty.rem_euclid(height)
+            } else if (0..height).contains(&ty) {
+                ty
+            } else {
+                continue;
+            };
+            for tx in ix - reach_x..=ix + reach_x {
+                let x = tx.rem_euclid(width);
+                let index = (y * width + x) as usize;
+                let old = alpha[index];
+                let pos = m.embed(x as f32 + 0.5, y as f32 + 0.5, old as f32);
+                let t = distance(pos, center) / radius;
+                if t >= 1.0 {
+                    continue;
+                }
+                let dig = depth * (1.0 - t * t) * bytes_per_metre;
+                let new = (old as f32 - dig).round().max(0.0) as u8;
+                if new != old {
+                    alpha[index] = new;
+                    cols.insert(x as u32);
+                    rows.insert(y as u32);
+                }
+            }
+        }
+
+        // A chunk owns `[start ..= start + len]`; the far border of the last
+        // chunk in a wrapping direction re-samples texel 0.
+        let touches = |set: &BTreeSet<u32>, start: i32, len: u32, total: i32, wraps: bool| {
+            let (start, end) = (start as u32, start as u32 + len);
+            set.range(start..=end).next().is_some()
+                || (wraps && end == total as u32 && set.contains(&0))
+        };
+        let mut chunks = Vec::new();
+        for (index, &origin) in self.origins.iter().enumerate() {
+            let (x0, y0, w, h) = origin;
+            if touches(&cols, x0, w, width, true) && touches(&rows, y0, h, height, m.wrap_y()) {
+                self.chunks[index] = build_chunk(&m, alpha, self.stats.max_error, origin);
+                chunks.push(index);
+            }
+        }
+
+        let mut runs: Vec<std::ops::Range<u32>> = Vec::new();
+        for row in rows {
+            match runs.last_mut() {
+                Some(run) if run.end == row => run.end += 1,
+                _ => runs.push(row..row + 1),
+            }
+        }
+        Deformation { chunks, rows: runs }
+    }
+}
+
+/// A build chunk's texel origin and extent: `(x0, y0, w, h)` covers texels
+/// `[x0 ..= x0 + w] × [y0 ..= y0 + h]`.
+type ChunkOrigin = (i32, i32, u32, u32);
+
+/// Fit one chunk, every LOD, at the given height tolerance.
+fn build_chunk(
+    mapping: &Mapping,
+    alpha: &[u8],
+    max_error: f32,
+    (x, y, w, h): ChunkOrigin,
+) -> ChunkBuffers {
+    let grid = Grid::new(mapping, alpha, x, y, w, h);
+    let tol_world = mapping.tol_world(max_error);
+
+    // Curvature lattice, as sorted grid indices, for one LOD.
+    //
+    // The border lines are always populated at the *finest* spacing —
+    // the same rule the height fit uses for `border_error`, and for the
+    // same reason: two neighbours drawn at different LODs must derive
+    // the identical vertex set on their shared line or the seam cracks.
+    // (The interior spacings of different LODs are not nested subsets of
+    // each other, so pinning the borders is what makes mixing safe.)
+    // Only the interior coarsens with the LOD's tolerance: doubling the
+    // tolerance widens the spacing by √2.
+    let (finest_x, finest_y) = {
+        let (us, vs) = mapping.curvature_steps(tol_world, y, h);
+        (lattice_positions(w, us), lattice_positions(h, vs))
+    };
+    let lattice_for_lod = |k: usize| -> Vec<u32> {
+        let tol_k = mapping.tol_world(max_error * (1 << k) as f32);
+        let (us, vs) = mapping.curvature_steps(tol_k, y, h);
+        let inner_x = lattice_positions(w, us);
+        let inner_y = lattice_positions(h, vs);
+        let mut points = Vec::new();
+        for &lx in &finest_x {
+            points.push(grid.index(lx, 0));
+            points.push(grid.index(lx, h));
+        }
+        for &ly in &finest_y {
+            points.push(grid.index(0, ly));
+            points.push(grid.index(w, ly));
+        }
+        for &ly in &inner_y {
+            if ly == 0 || ly == h {
+                continue;
+            }
+            for &lx in &inner_x {
+                if lx == 0 || lx == w {
+                    continue;
+                }
+                points.push(grid.index(lx, ly));
+            }
+        }
+        // Deterministic order keeps the whole build reproducible.
+        points.sort_unstable();
+        points.dedup();
+        points
+    };
+
+    // Each LOD is an independent fit at a doubled tolerance. They could
+    // share work — the coarse vertex sets are prefixes of the fine one —
+    // but refitting from scratch is cheap (the coarse levels converge in
+    // a fraction of the insertions) and keeps every level a genuine
+    // Delaunay triangulation.
+    let mut per_lod = Vec::with_capacity(LOD_COUNT);
+    for k in 0..LOD_COUNT {
+        let mut chunk = Chunk::new(&grid);
+        refine(
+            &mut chunk,
+            &grid,
+            &lattice_for_lod(k),
+            max_error * (1 << k) as f32,
+            max_error,
+        );
+        per_lod.push(emit_chunk(&chunk, &grid, mapping));
+    }
+    // The mesh bulges between vertices by up to the curvature-lattice
+    // tolerance plus the coarsest LOD's height slack; pad the culling
+    // AABB by a conservative multiple of both.
+    let mut buffers = ChunkBuffers::new(per_lod);
+    let pad = 2.0 * tol_world + mapping.tol_world(max_error * (1 << (LOD_COUNT - 1)) as f32) + 0.05;
+    for k in 0..3 {
+        buffers.min[k] -= pad;
+        buffers.max[k] += pad;
+    }
+    buffers
 }
 
 /// Build the TIN for a height map.
@@ -864,86 +1091,7 @@ pub fn build(
         }
     }
 
-    let tol_world = mapping.tol_world(max_error);
-
-    let build_one = |&(x, y, w, h): &(i32, i32, u32, u32)| -> ChunkBuffers {
-        let grid = Grid::new(&mapping, alpha, x, y, w, h);
-
-        // Curvature lattice, as sorted grid indices, for one LOD.
-        //
-        // The border lines are always populated at the *finest* spacing —
-        // the same rule the height fit uses for `border_error`, and for the
-        // same reason: two neighbours drawn at different LODs must derive
-        // the identical vertex set on their shared line or the seam cracks.
-        // (The interior spacings of different LODs are not nested subsets of
-        // each other, so pinning the borders is what makes mixing safe.)
-        // Only the interior coarsens with the LOD's tolerance: doubling the
-        // tolerance widens the spacing by √2.
-        let (finest_x, finest_y) = {
-            let (us, vs) = mapping.curvature_steps(tol_world, y, h);
-            (lattice_positions(w, us), lattice_positions(h, vs))
-        };
-        let lattice_for_lod = |k: usize| -> Vec<u32> {
-            let tol_k = mapping.tol_world(max_error * (1 << k) as f32);
-            let (us, vs) = mapping.curvature_steps(tol_k, y, h);
-            let inner_x = lattice_positions(w, us);
-            let inner_y = lattice_positions(h, vs);
-            let mut points = Vec::new();
-            for &lx in &finest_x {
-                points.push(grid.index(lx, 0));
-                points.push(grid.index(lx, h));
-            }
-            for &ly in &finest_y {
-                points.push(grid.index(0, ly));
-                points.push(grid.index(w, ly));
-            }
-            for &ly in &inner_y {
-                if ly == 0 || ly == h {
-                    continue;
-                }
-                for &lx in &inner_x {
-                    if lx == 0 || lx == w {
-                        continue;
-                    }
-                    points.push(grid.index(lx, ly));
-                }
-            }
-            // Deterministic order keeps the whole build reproducible.
-            points.sort_unstable();
-            points.dedup();
-            points
-        };
-
-        // Each LOD is an independent fit at a doubled tolerance. They could
-        // share work — the coarse vertex sets are prefixes of the fine one —
-        // but refitting from scratch is cheap (the coarse levels converge in
-        // a fraction of the insertions) and keeps every level a genuine
-        // Delaunay triangulation.
-        let mut per_lod = Vec::with_capacity(LOD_COUNT);
-        for k in 0..LOD_COUNT {
-            let mut chunk = Chunk::new(&grid);
-            refine(
-                &mut chunk,
-                &grid,
-                &lattice_for_lod(k),
-                max_error * (1 << k) as f32,
-                max_error,
-            );
-            per_lod.push(emit_chunk(&chunk, &grid, &mapping));
-        }
-        // The mesh bulges between vertices by up to the curvature-lattice
-        // tolerance plus the coarsest LOD's height slack; pad the culling
-        // AABB by a conservative multiple of both.
-        let mut buffers = ChunkBuffers::new(per_lod);
-        let pad = 2.0 * tol_world
-            + mapping.tol_world(max_error * (1 << (LOD_COUNT - 1)) as f32)
-            + 0.05;
-        for k in 0..3 {
-            buffers.min[k] -= pad;
-            buffers.max[k] += pad;
-        }
-        buffers
-    };
+    let build_one = |origin: &ChunkOrigin| build_chunk(&mapping, alpha, max_error, *origin);
 
     #[cfg(not(target_arch = "wasm32"))]
     let chunks: Vec<ChunkBuffers> = {
@@ -998,6 +1146,7 @@ pub fn build(
         mapping,
         chunks,
         stats,
+        origins,
     }
 }
 
@@ -1011,6 +1160,7 @@ mod tests {
             length: 200.0,
             density: 1.0,
             shape,
+            ..Default::default()
         }
     }
 
@@ -1412,4 +1562,57 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn locate_inverts_embed() {
+        let (w, h) = (128u32, 256u32);
+        for shape in [WorldShape::Cylinder, WorldShape::Sphere, WorldShape::Torus] {
+            let mapping = Mapping::new(&map_config(shape), w, h);
+            for &(x, y) in &[(0.5, 128.5), (37.25, 40.5), (127.5, 200.0), (64.0, 12.75)] {
+                let [lx, ly] = mapping.locate(mapping.embed(x, y, 100.0));
+                let dx = (lx - x).abs().min(w as f32 - (lx - x).abs());
+                assert!(
+                    dx < 1e-2 && (ly - y).abs() < 1e-2,
+                    "{:?}: ({}, {}) came back as ({}, {})",
+                    shape,
+                    x,
+                    y,
+                    lx,
+                    ly
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn dig_crater_refits_only_the_touched_chunks() {
+        let (w, h) = (256u32, 192u32);
+        let mut alpha = hills(w, h);
+        let config = map_config(WorldShape::Cylinder);
+        let mut mesh = build(&alpha, w, h, &config, 0.75);
+        let before = alpha.clone();
+
+        let (cx, cy) = (130u32, 129u32);
+        let center_index = (cy * w + cx) as usize;
+        let center_height = alpha[center_index] as f32;
+        let center = mesh
+            .mapping
+            .embed(cx as f32 + 0.5, cy as f32 + 0.5, center_height);
+        let deformation = mesh.dig_crater(&mut alpha, center, 2.0, 0.5);
+
+        assert!(alpha[center_index] < before[center_index], "centre not dug");
+        assert_eq!(alpha[0], before[0], "a far texel changed");
+        assert!(!deformation.chunks.is_empty());
+        assert!(deformation.rows.iter().any(|run| run.contains(&cy)));
+        // The crater sits on a chunk corner, so it must touch several.
+        assert!(deformation.chunks.len() > 1, "{:?}", deformation.chunks);
+
+        // Every chunk, refitted or not, matches a fresh build of the edited
+        // map.
+        let fresh = build(&alpha, w, h, &config, 0.75);
+        for (index, (edited, rebuilt)) in mesh.chunks.iter().zip(&fresh.chunks).enumerate() {
+            assert_eq!(edited.vertices, rebuilt.vertices, "chunk {} stale", index);
+            assert_eq!(edited.indices, rebuilt.indices, "chunk {} stale", index);
+        }
+    }
 }
@@ -0,0 +1,69 @@
+//! Slip-based tyre forces for `config::Tires`. `Vehicle::drive` measures
+//! each grounded wheel's slip where it meets the terrain and pushes it
+//! with the grip the curves give for that slip, in place of the wheel
+//! colliders' own friction.
+
+use crate::config::{TireCurve, Tires};
+
+/// Ground speed (m/s) slip is measured against when the wheel moves slower
+/// than this, so a car at rest doesn't read huge slips off tiny jitters.
+pub const MIN_SPEED: f32 = 1.0;
+
+/// Grip `curve` gives at `slip`, as a multiple of the load. Odd in `slip`.
+pub fn grip(curve: &TireCurve, slip: f32) -> f32 {
+    match *curve {
+        TireCurve::Pacejka { b, c, d, e } => {
+            let x = b * slip;
+            d * (c * (x - e * (x - x.atan())).atan()).sin()
+        }
+        TireCurve::Brush {
+            stiffness,
+            friction,
+        } => (stiffness * slip).clamp(-friction, friction),
+    }
+}
+
+/// The most grip `curve` can give.
+pub fn peak(curve: &TireCurve) -> f32 {
+    match *curve {
+        TireCurve::Pacejka { d, .. } => d.abs(),
+        TireCurve::Brush { friction, .. } => friction,
+    }
+}
+
+/// How a wheel slides over the ground.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Slip {
+    /// Positive while the tread turns faster than the wheel travels.
+    pub ratio: f32,
+    /// Radians, positive while the wheel drifts toward its axle's +side.
+    pub angle: f32,
+}
+
+impl Slip {
+    /// From the wheel's ground speed along where it points (`forward`) and
+    /// along its axle (`lateral`), and the speed of its tread.
+    pub fn new(forward: f32, lateral: f32, tread: f32) -> Self {
+        let reference = forward.abs().max(MIN_SPEED);
+        Self {
+            ratio: (tread - forward) / reference,
+            angle: lateral.atan2(reference),
+        }
+    }
+}
+
+/// The forward and lateral forces for `slip`, as multiples of the load.
+/// Lateral force opposes the drift. A tyre can't give its full grip both
+/// ways at once, so the pair is kept inside the ellipse of the two peaks.
+pub fn forces(tires: &Tires, slip: Slip) -> (f32, f32) {
+    let forward = grip(&tires.longitudinal, slip.ratio);
+    let lateral = -grip(&tires.lateral, slip.angle);
+    let x = forward / peak(&tires.longitudinal).max(f32::EPSILON);
+    let y = lateral / peak(&tires.lateral).max(f32::EPSILON);
+    let usage = (x * x + y * y).sqrt();
+    if usage > 1.0 {
+        (forward / usage, lateral / usage)
+    } else {
+        (forward, lateral)
+    }
+}
@@ -0,0 +1,1252 @@
+//! The car as physics sees it: a chassis riding on sprung, motor-driven
+//! wheels, the front pair steered through knuckles — or, for a
+//! `VehicleKind::Raycast` car, on rays cast down from it that push the
+//! chassis alone. Shared by the game, which also draws it, and the server,
+//! which only simulates it — both must build and drive it identically for
+//! client prediction to hold.
+
+use crate::config::{self, VehicleKind, WorldShape};
+use crate::drivetrain::{self, DrivenWheel};
+use crate::model::{GeometryDesc, MaterialDesc, ModelDesc, VertexDesc};
+use crate::physics::{Physics, PhysicsBodyHandle, RayHit, TerrainBody};
+use crate::tires;
+use crate::weapons::Weapons;
+use rapier3d::dynamics::{
+    GenericJointBuilder, ImpulseJointHandle, JointAxesMask, JointAxis, MassProperties, MotorModel,
+    RigidBodyBuilder, RigidBodyHandle,
+};
+use rapier3d::geometry::{Collider, ColliderBuilder};
+use rapier3d::math::{Pose, Rotation, Vec3};
+use std::f32;
+// std::time::Instant panics on wasm32; web-time re-exports std on native.
+use web_time::{Duration, Instant};
+
+/// Multiplier applied to wheel target velocity while Left Shift is held, on
+/// a car without a drivetrain; with one, turbo boosts the engine instead.
+const TURBO_FACTOR: f32 = 2.5;
+/// Damping factor applied to wheel motors when no drive command is active. High
+/// enough that the motor brakes any wheel rotation toward zero, so the static
+/// wheel-ground friction holds the chassis still on slopes. The brakes use
+/// it too; what limits each of them is its torque cap.
+const IDLE_BRAKE_FACTOR: f32 = 50.0;
+/// Forward speed (m/s) below which the brake, held with no throttle,
+/// backs the car up instead — one key or trigger both stops and reverses.
+const REVERSE_BELOW_SPEED: f32 = 0.3;
+/// Maximum front-wheel steering angle in radians (~45°). Real cars top out
+/// at 30–35° but this is a small buggy on tight cylindrical maps — the
+/// extra range gives the chassis enough cross-track force to turn briskly
+/// at modest speeds, which is what makes the controls feel responsive.
+const MAX_STEER_ANGLE: f32 = f32::consts::FRAC_PI_4;
+/// Steering motor stiffness. With wheel inertia ~0.003 kg·m² and the
+/// damping below, the wheel reaches the target angle in about 100 ms.
+const STEER_STIFFNESS: f32 = 200.0;
+/// Steering motor damping. Already ~12× critical damping at the chosen
+/// stiffness (critical ≈ 2·√(k·I) ≈ 1.6), so there's no wheel oscillation —
+/// the straight-line wobble you saw came from elsewhere (suspension).
+const STEER_DAMPING: f32 = 20.0;
+/// Cap on the steering motor's force (N·m). Sized above the static-friction
+/// torque the steered wheels see against terrain so the motor can actually
+/// rotate them to the target.
+const STEER_MAX_FORCE: f32 = 50.0;
+/// Suspension spring stiffness (N/m). Higher → less body roll during cornering
+/// and less bounce on terrain. Sized to give ~0.01 m static compression under
+/// the chassis weight.
+const SUSPENSION_STIFFNESS: f32 = 300.0;
+/// Suspension damping coefficient (N·s/m). Critical for chassis mass ~1.67 kg
+/// is `2·√(stiffness·m) ≈ 45`; the old 30 gave ζ ≈ 0.67 (under-damped → the
+/// suspension oscillated → chassis pitched → straight-line wobble). At 50
+/// the suspension is *slightly* over-damped so bumps absorb without bouncing.
+const SUSPENSION_DAMPING: f32 = 50.0;
+/// Cap on the suspension spring force per wheel (N). Limits force the spring can
+/// transmit during hard impacts.
+const SUSPENSION_MAX_FORCE: f32 = 500.0;
+/// How far a wheel moves up or down from its rest position (m).
+const SUSPENSION_TRAVEL: f32 = 0.3;
+/// Suspension damping (N·s/m) of a raycast wheel. Its force lands on the
+/// chassis a tick at a time instead of going through the joint solver, and
+/// at 50 four wheels together would overshoot the ~1.7 kg chassis every
+/// tick; 20 is still about critical for the quarter each wheel carries.
+const RAYCAST_DAMPING: f32 = 20.0;
+/// Wheel-ground friction coefficient, for cars without a tyre model.
+const WHEEL_FRICTION: f32 = 3.0;
+/// Velocity for a tap-jump (Space pressed and immediately released). Sized
+/// to clear a low obstacle without much drama.
+const JUMP_MIN_VELOCITY: f32 = 4.0;
+/// Velocity for a fully-charged jump. At max gravity (12 m/s²) this clears
+/// ~8 m; on lighter worlds proportionally higher. Choose enough headroom
+/// that the player feels charge pays off.
+const JUMP_MAX_VELOCITY: f32 = 14.0;
+/// How long Jump has to be held for a full-strength jump. After this the
+/// jump goes off by itself, so a held button doesn't pin the chassis down.
+pub const JUMP_MAX_CHARGE: Duration = Duration::from_millis(800);
+/// Half-width of the procedural wheel mesh (so the visible cylinder is 2·
+/// this wide along the axle). Sized to match the GLB-baked rear wheels:
+/// inspecting body.glb's Wheel.001 primitive gives a per-wheel z half-
+/// extent of 0.08 m (full width 0.16 m). Matching this here makes the
+/// procedural front wheels the same thickness as the rear pair the
+/// player sees on the model.
+const WHEEL_HALF_WIDTH: f32 = 0.08;
+/// Scale applied to the procedural wheel mesh radius (which otherwise
+/// equals the physics collider radius from car.ron). 1.0 = render the
+/// mesh at the same radius the physics uses; the GLB-baked rear wheels
+/// are ~0.175 m across so a 0.15 m visible front wheel reads as roughly
+/// the right size for the chassis.
+const WHEEL_MESH_RADIUS_SCALE: f32 = 1.0;
+
+/// Controls held during one physics tick. It is all a network client
+/// sends the server, so it stays small and `Copy`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DriveInput {
+    /// `1` full ahead, `-1` full reverse; keys give the ends, a gamepad
+    /// anything between.
+    pub throttle: f32,
+    /// `0` off to `1` full brake. While applied it overrides the throttle;
+    /// held alone once the car has stopped, it reverses.
+    pub brake: f32,
+    /// `1` full lock right, `-1` full lock left.
+    pub steer: f32,
+    /// Locks the rear wheels, for sliding the tail round.
+    pub handbrake: bool,
+    pub turbo: bool,
+    pub fire: bool,
+}
+
+impl DriveInput {
+    pub fn turbo_factor(&self) -> f32 {
+        if self.turbo { TURBO_FACTOR } else { 1.0 }
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Wheel {
+    /// On a raycast car, a kinematic body without a collider that only
+    /// follows the chassis around, so there is something to draw.
+    pub rigid_body: RigidBodyHandle,
+    /// Joint owning AngZ (drive) + LinY (suspension). For rear wheels this
+    /// connects chassis ↔ wheel directly; for front wheels it connects the
+    /// steering knuckle ↔ wheel. `None` on a raycast car.
+    pub joint: Option<ImpulseJointHandle>,
+    /// `Some` for front wheels: the chassis ↔ knuckle joint owning AngY
+    /// (steering). The hierarchy isolates the wheel's spin axis from the
+    /// steering rotation so a single AngZ motor can't slew the wheel about
+    /// chassis Z while AngY changes.
+    pub steering_joint: Option<ImpulseJointHandle>,
+    /// The massless-ish body between chassis and wheel that
+    /// `steering_joint` turns; `Some` exactly when that joint is.
+    pub knuckle: Option<RigidBodyHandle>,
+    /// True for the front-axle wheels (those in the chassis -X half, since the
+    /// car's forward direction is -X). Steering applies to these wheels only;
+    /// rear wheels just drive.
+    pub is_steering: bool,
+    #[serde(default)]
+    pub radius: f32,
+    /// Wheel centre at rest, in chassis-local coordinates.
+    #[serde(default)]
+    pub anchor: [f32; 3],
+}
+
+/// What a wheel is asked to do for a tick: turn at `spin` (rad/s), pulled
+/// toward it with `gain` N·m per rad/s short, up to `torque` N·m.
+#[derive(Clone, Copy, Debug)]
+struct WheelMotor {
+    spin: f32,
+    gain: f32,
+    torque: f32,
+    /// Held by a brake rather than driven: a raycast wheel braked harder
+    /// than it grips locks up.
+    brake: bool,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Vehicle {
+    pub chassis: RigidBodyHandle,
+    #[serde(default)]
+    pub kind: VehicleKind,
+    pub wheels: Vec<Wheel>,
+    pub motor_max_velocity: f32,
+    pub motor_max_force: f32,
+    #[serde(default)]
+    pub steering: config::Steering,
+    #[serde(default)]
+    pub brakes: config::Brakes,
+    #[serde(default)]
+    pub drivetrain: Option<config::Drivetrain>,
+    #[serde(default)]
+    pub tires: Option<config::Tires>,
+    /// Chassis-local Y coordinate of the bottom of the AABB. Jump impulses are
+    /// applied at this offset so the push-off torque points up through the
+    /// vehicle, like real wheels pushing the body upward.
+    pub chassis_bottom_y: f32,
+    /// Chassis-local Y coordinate of the *top* of the AABB. When the chassis
+    /// is upside-down, jump impulses apply here instead of `chassis_bottom_y`
+    /// so the push always launches *away* from the surface the cabin is
+    /// resting on.
+    pub chassis_top_y: f32,
+    pub weapons: Weapons,
+    /// Hit points from car.ron, for the owner's `Damage`.
+    pub health: f32,
+}
+
+impl Vehicle {
+    /// Build the car described by `car` and its model at `pose`.
+    pub fn spawn(
+        physics: &mut Physics,
+        car: &config::Car,
+        model_desc: &ModelDesc,
+        pose: Pose,
+    ) -> Self {
+        let chassis_colliders = create_chassis_colliders(model_desc);
+
+        // The chassis collider has zero density (it's a stub — wheels own the
+        // ground interaction), so set the chassis inertial mass AND moment of
+        // inertia explicitly. additional_mass alone leaves I = 0, which makes
+        // the chassis infinitely resistant to angular acceleration — i.e. it
+        // can never yaw or roll under torque (steering becomes impossible).
+        let aabb = chassis_aabb(model_desc);
+        let lx = aabb.maxs.x - aabb.mins.x;
+        let ly = aabb.maxs.y - aabb.mins.y;
+        let lz = aabb.maxs.z - aabb.mins.z;
+        let chassis_mass = lx * ly * lz * 0.1 * car.density;
+        // Solid cuboid inertia about each principal axis: I = m/12 · (a² + b²)
+        // where a, b are the two extents perpendicular to that axis.
+        let inertia = Vec3::new(
+            chassis_mass / 12.0 * (ly * ly + lz * lz),
+            chassis_mass / 12.0 * (lx * lx + lz * lz),
+            chassis_mass / 12.0 * (lx * lx + ly * ly),
+        );
+        // Shift the center of mass below the chassis geometric origin, toward the
+        // wheel axle level. A high CoM relative to the wheel base makes the car
+        // prone to flipping during turns; pulling the CoM down here gives us a
+        // stable, low-slung buggy feel without changing the visual mass.
+        let chassis_com = Vec3::new(0.0, -0.25, 0.0);
+        log::info!(
+            "chassis mass {chassis_mass:.2} kg, principal inertia ({:.3}, {:.3}, {:.3}), com_y={}",
+            inertia.x,
+            inertia.y,
+            inertia.z,
+            chassis_com.y,
+        );
+        let mass_props = MassProperties::new(chassis_com, chassis_mass, inertia);
+
+        let rigid_body = RigidBodyBuilder::dynamic()
+            .pose(pose)
+            .additional_mass_properties(mass_props)
+            .linear_damping(0.4)
+            // rapier's angular_damping is a single scalar across all three
+            // axes, which forces us to trade upright-stability for steering
+            // response. We zero it here and instead apply per-axis damping
+            // (see Physics::apply_axial_angular_damping in `drive`) with
+            // high roll/pitch and low yaw values.
+            .angular_damping(0.0)
+            .build();
+
+        let PhysicsBodyHandle {
+            rigid_body_handle: chassis,
+            ..
+        } = physics.add_rigid_body(rigid_body, chassis_colliders);
+        physics.set_drag_area(chassis, car.drag_coefficient * car.frontal_area);
+
+        let wheels: Vec<Wheel> = car
+            .wheels
+            .iter()
+            .map(|w| {
+                let anchor_local = Vec3::from(w.position);
+                let wheel_world = pose * anchor_local;
+                let is_steering = anchor_local.x < 0.0;
+                if car.kind == VehicleKind::Raycast {
+                    // Nothing collides with or holds the wheel; `drive` moves
+                    // it to wherever its ray puts it.
+                    let wheel_body = RigidBodyBuilder::kinematic_position_based()
+                        .pose(Pose::from_parts(wheel_world, pose.rotation))
+                        .build();
+                    let PhysicsBodyHandle {
+                        rigid_body_handle: wheel_rb,
+                        ..
+                    } = physics.add_rigid_body(wheel_body, vec![]);
+                    return Wheel {
+                        rigid_body: wheel_rb,
+                        joint: None,
+                        steering_joint: None,
+                        knuckle: None,
+                        is_steering,
+                        radius: w.radius,
+                        anchor: w.position,
+                    };
+                }
+                let wheel_body = RigidBodyBuilder::dynamic()
+                    .pose(Pose::from_parts(wheel_world, pose.rotation))
+                    .angular_damping(0.2)
+                    .build();
+                let wheel_collider = ColliderBuilder::ball(w.radius)
+                    .density(car.density)
+                    // The tyre model, when there is one, supplies all the
+                    // grip itself.
+                    .friction(if car.tires.is_some() {
+                        0.0
+                    } else {
+                        WHEEL_FRICTION
+                    })
+                    .build();
+                let PhysicsBodyHandle {
+                    rigid_body_handle: wheel_rb,
+                    ..
+                } = physics.add_rigid_body(wheel_body, vec![wheel_collider]);
+
+                // Two-joint chain for front (steered) wheels and a single
+                // joint for rear wheels. Without the knuckle, a single
+                // GenericJoint's AngZ motor rotates the wheel about chassis Z
+                // — which is *not* the wheel's axle when steered, so the
+                // wheel "wobbles" around the steered direction once it starts
+                // spinning. With the knuckle: chassis ↔ knuckle owns AngY
+                // (steering), knuckle ↔ wheel owns AngZ (spin) + LinY
+                // (suspension). The knuckle-relative AngZ axis IS the steered
+                // axle. OxidizeMonk's axle is chassis Z, so `wheel_axis` is
+                // not consulted.
+                let steering = if is_steering {
+                    let knuckle_body = RigidBodyBuilder::dynamic()
+                        .pose(Pose::from_parts(wheel_world, pose.rotation))
+                        .angular_damping(0.0)
+                        .additional_mass_properties(MassProperties::new(
+                            Vec3::ZERO,
+                            0.01,
+                            Vec3::new(1e-4, 1e-4, 1e-4),
+                        ))
+                        .build();
+                    let PhysicsBodyHandle {
+                        rigid_body_handle: knuckle_rb,
+                        ..
+                    } = physics.add_rigid_body(knuckle_body, vec![]);
+                    // Chassis ↔ knuckle: lock everything except AngY.
+                    let steer_locked = JointAxesMask::LIN_X
+                        | JointAxesMask::LIN_Y
+                        | JointAxesMask::LIN_Z
+                        | JointAxesMask::ANG_X
+                        | JointAxesMask::ANG_Z;
+                    let steer_joint = GenericJointBuilder::new(steer_locked)
+                        .local_anchor1(anchor_local)
+                        .local_anchor2(Vec3::ZERO)
+                        .contacts_enabled(false)
+                        // ForceBased gives the steering motor a direct
+                        // `stiffness × pos_err` torque (up to STEER_MAX_FORCE)
+                        // independent of the knuckle's tiny inertia.
+                        // AccelerationBased would multiply by mass and produce
+                        // ~0.01 × accel = negligible torque, so even small
+                        // gyroscopic precession from the spinning wheel would
+                        // visibly wobble the steered direction.
+                        .motor_model(JointAxis::AngY, MotorModel::ForceBased)
+                        .motor_position(JointAxis::AngY, 0.0, STEER_STIFFNESS, STEER_DAMPING)
+                        .motor_max_force(JointAxis::AngY, STEER_MAX_FORCE)
+                        .limits(JointAxis::AngY, [-MAX_STEER_ANGLE, MAX_STEER_ANGLE])
+                        .build();
+                    Some((
+                        knuckle_rb,
+                        physics.add_generic_joint(chassis, knuckle_rb, steer_joint),
+                    ))
+                } else {
+                    None
+                };
+
+                // wheel_joint: handles suspension (LinY) and spin (AngZ).
+                // AngY is locked here: steering is owned by the chassis ↔
+                // knuckle joint above (for front wheels) or doesn't exist
+                // (for rear wheels).
+                let wheel_locked = JointAxesMask::LIN_X
+                    | JointAxesMask::LIN_Z
+                    | JointAxesMask::ANG_X
+                    | JointAxesMask::ANG_Y;
+                let (parent_rb, parent_anchor) = match steering {
+                    Some((knuckle_rb, _)) => (knuckle_rb, Vec3::ZERO),
+                    None => (chassis, anchor_local),
+                };
+                let wheel_joint = GenericJointBuilder::new(wheel_locked)
+                    .local_anchor1(parent_anchor)
+                    .local_anchor2(Vec3::ZERO)
+                    .contacts_enabled(false)
+                    .motor_model(JointAxis::LinY, MotorModel::ForceBased)
+                    .motor_position(
+                        JointAxis::LinY,
+                        0.0,
+                        SUSPENSION_STIFFNESS,
+                        SUSPENSION_DAMPING,
+                    )
+                    .motor_max_force(JointAxis::LinY, SUSPENSION_MAX_FORCE)
+                    .limits(JointAxis::LinY, [-SUSPENSION_TRAVEL, SUSPENSION_TRAVEL])
+                    .motor_model(JointAxis::AngZ, MotorModel::ForceBased)
+                    .motor_velocity(JointAxis::AngZ, 0.0, IDLE_BRAKE_FACTOR)
+                    .motor_max_force(JointAxis::AngZ, car.motor_max_force)
+                    .build();
+                let joint_handle = physics.add_generic_joint(parent_rb, wheel_rb, wheel_joint);
+                Wheel {
+                    rigid_body: wheel_rb,
+                    joint: Some(joint_handle),
+                    steering_joint: steering.map(|(_, j)| j),
+                    knuckle: steering.map(|(k, _)| k),
+                    is_steering,
+                    radius: w.radius,
+                    anchor: w.position,
+                }
+            })
+            .collect();
+
+        // Wheels are part of the vehicle too: a shell scraping the car's
+        // own tyre on the way out of the barrel must not go off.
+        let mut weapon_owner = vec![chassis];
+        weapon_owner.extend(wheels.iter().map(|w| w.rigid_body));
+        let weapons = Weapons::new(&car.weapons, weapon_owner);
+
+        Self {
+            chassis,
+            kind: car.kind,
+            wheels,
+            motor_max_velocity: car.motor_max_velocity,
+            motor_max_force: car.motor_max_force,
+            steering: car.steering,
+            brakes: car.brakes,
+            drivetrain: car.drivetrain.clone(),
+            tires: car.tires,
+            chassis_bottom_y: aabb.mins.y,
+            chassis_top_y: aabb.maxs.y,
+            weapons,
+            health: car.health,
+        }
+    }
+
+    /// The bodies that make up the car: the chassis first, then one per
+    /// wheel in car.ron order. Knuckles are left out; nothing strikes or
+    /// draws them.
+    pub fn bodies(&self) -> Vec<RigidBodyHandle> {
+        let mut bodies = vec![self.chassis];
+        bodies.extend(self.wheels.iter().map(|w| w.rigid_body));
+        bodies
+    }
+
+    /// Apply one tick of `input`: the per-axis chassis damping, then the
+    /// wheel motors, or the raycast wheels' forces. Call after
+    /// `Physics::update_gravity`, which resets forces, and before
+    /// `Physics::step`.
+    pub fn drive(&self, physics: &mut Physics, terrain: &TerrainBody, input: &DriveInput) {
+        // Yaw / tumble damping split: low damping about the world radial-out
+        // axis at the chassis position (steering stays responsive), high
+        // damping for everything else (the chassis stays upright through
+        // bumps). Light yaw damping so steering input integrates into a
+        // brisk chassis turn rate; the over-damped suspension stops the
+        // straight-line wobble at its source.
+        physics.apply_axial_angular_damping(self.chassis, terrain, 0.15, 2.0);
+
+        // All-wheel drive: every wheel gets the throttle. With the knuckle in
+        // the chain the AngZ motor on each wheel pushes about the wheel's
+        // actual axle (post-steer for front wheels, chassis Z for rear), so
+        // applying drive to all four no longer fights the steering as it
+        // would have on the single-joint setup.
+        let mut throttle = input.throttle.clamp(-1.0, 1.0);
+        let mut brake = input.brake.clamp(0.0, 1.0);
+        if brake > 0.0 && throttle == 0.0 && self.forward_speed(physics) < REVERSE_BELOW_SPEED {
+            throttle = -brake;
+            brake = 0.0;
+        }
+        let gearing = self.gearing(physics, throttle, input.turbo);
+        let drive_v = throttle * self.motor_max_velocity * input.turbo_factor();
+
+        // The lock narrows with speed, and the wheels swing toward it no
+        // faster than `steering.rate`; the last target is kept on the joint
+        // (or in the wheel's pose, on a raycast car), so snapshots and
+        // replays carry it along.
+        let speed = physics.body_linvel(self.chassis).length();
+        let lock = if self.steering.fade_speed > 0.0 {
+            MAX_STEER_ANGLE / (1.0 + speed / self.steering.fade_speed)
+        } else {
+            MAX_STEER_ANGLE
+        };
+        let steer_target = input.steer.clamp(-1.0, 1.0) * lock;
+        let max_swing = self.steering.rate * physics.dt();
+        let swing = |current: f32| current + (steer_target - current).clamp(-max_swing, max_swing);
+
+        // The brakes pull the wheel's spin to zero with capped torque; the
+        // handbrake overrides everything on the rear axle. The engine's
+        // torque goes in the same way, as the cap on a motor chasing the
+        // redline spin.
+        let motors: Vec<WheelMotor> = self
+            .wheels
+            .iter()
+            .enumerate()
+            .map(|(i, wheel)| {
+                let hold = |torque: f32, brake: bool| WheelMotor {
+                    spin: 0.0,
+                    gain: IDLE_BRAKE_FACTOR,
+                    torque,
+                    brake,
+                };
+                if input.handbrake && !wheel.is_steering {
+                    hold(self.brakes.handbrake_torque, true)
+                } else if brake > 0.0 {
+                    hold(brake * self.brake_torque(wheel), true)
+                } else if let Some(ref gearing) = gearing {
+                    if gearing.gear == 0 {
+                        hold(self.brakes.engine_brake, false)
+                    } else {
+                        WheelMotor {
+                            spin: gearing.top_spin,
+                            gain: IDLE_BRAKE_FACTOR,
+                            torque: gearing.torques[i],
+                            brake: false,
+                        }
+                    }
+                } else if drive_v != 0.0 {
+                    WheelMotor {
+                        spin: drive_v,
+                        gain: 1.0,
+                        torque: self.motor_max_force,
+                        brake: false,
+                    }
+                } else {
+                    hold(self.brakes.engine_brake, false)
+                }
+            })
+            .collect();
+
+        match self.kind {
+            VehicleKind::Jointed => {
+                for (wheel, motor) in self.wheels.iter().zip(&motors) {
+                    if let Some(joint) = wheel.joint {
+                        physics.set_joint_motor_velocity(joint, motor.spin, motor.gain);
+                        physics.set_joint_motor_max_force(joint, motor.torque);
+                    }
+                    if let Some(steering_joint) = wheel.steering_joint {
+                        let steer_angle = swing(self.steer_angle(physics, wheel));
+                        physics.set_joint_motor_position(
+                            steering_joint,
+                            JointAxis::AngY,
+                            steer_angle,
+                            STEER_STIFFNESS,
+                            STEER_DAMPING,
+                        );
+                    }
+                    if let Some(ref tires) = self.tires {
+                        self.apply_tire(physics, terrain, wheel, tires);
+                    }
+                }
+            }
+            VehicleKind::Raycast => self.drive_raycast(physics, &motors, swing),
+        }
+    }
+
+    /// One tick of a raycast car. Each wheel's ray runs down from the top
+    /// of its travel; where it finds the ground, the spring, the wheel's
+    /// motor and its grip push on the chassis at the wheel centre.
+    fn drive_raycast(
+        &self,
+        physics: &mut Physics,
+        motors: &[WheelMotor],
+        swing: impl Fn(f32) -> f32,
+    ) {
+        let Some(chassis_pose) = physics.body_pose(self.chassis) else {
+            return;
+        };
+        let dt = physics.dt();
+        let down = chassis_pose.rotation * Vec3::NEG_Y;
+        let hits: Vec<Option<RayHit>> = self
+            .wheels
+            .iter()
+            .map(|w| self.wheel_ray(physics, w))
+            .collect();
+        // Force per m/s that would stop one grounded wheel's share of the
+        // chassis within the tick; nothing pushes harder than that, or the
+        // wheels would overshoot and jitter.
+        let grounded = hits.iter().flatten().count().max(1);
+        let stop = physics.body_mass(self.chassis) / grounded as f32 / dt;
+        // The wheel bodies go where the chassis will be after the step,
+        // rather than trailing it by a tick.
+        let angvel = physics.body_angvel(self.chassis);
+        let next_chassis = Pose::from_parts(
+            chassis_pose.translation + physics.body_linvel(self.chassis) * dt,
+            Rotation::from_scaled_axis(angvel * dt) * chassis_pose.rotation,
+        );
+
+        for ((wheel, motor), hit) in self.wheels.iter().zip(motors).zip(&hits) {
+            let (current_steer, rolled) = self.raycast_mount(physics, wheel);
+            let mount = if wheel.is_steering {
+                Rotation::from_rotation_y(swing(current_steer))
+            } else {
+                Rotation::IDENTITY
+            };
+            let axle = chassis_pose.rotation * mount * Vec3::Z;
+            let anchor = Vec3::from(wheel.anchor);
+            // In the air a wheel hangs at rest and keeps turning as it was.
+            let mut sag = 0.0;
+            let mut spin = (physics.body_angvel(wheel.rigid_body) - angvel).dot(axle);
+            if let Some(ref hit) = *hit {
+                // How far the wheel centre sits below its rest position:
+                // negative while the suspension is compressed.
+                sag = hit.distance - SUSPENSION_TRAVEL - wheel.radius;
+                let centre = chassis_pose * anchor + down * sag;
+                let velocity = physics.velocity_at_point(self.chassis, centre);
+                let load = (-sag * SUSPENSION_STIFFNESS + velocity.dot(down) * RAYCAST_DAMPING)
+                    .clamp(0.0, SUSPENSION_MAX_FORCE);
+                // Chassis forward is -X and the axle +Z, so axle × up points
+                // ahead.
+                let forward = axle.cross(hit.normal).normalize_or_zero();
+                let lateral = hit.normal.cross(forward);
+                let (fx, fy, rolling) = self.traction(
+                    wheel,
+                    motor,
+                    load,
+                    velocity.dot(forward),
+                    velocity.dot(lateral),
+                    stop,
+                );
+                let force = -down * load + forward * fx + lateral * fy;
+                physics.apply_impulse_at_point(self.chassis, force * dt, centre);
+                spin = rolling;
+            }
+            let wheel_pose = Pose::from_parts(
+                next_chassis * (anchor + Vec3::NEG_Y * sag),
+                next_chassis.rotation * mount * Rotation::from_rotation_z(rolled + spin * dt),
+            );
+            physics.set_next_body_pose(wheel.rigid_body, wheel_pose);
+        }
+    }
+
+    /// Forward and lateral force (N) a raycast `wheel` carrying `load` grips
+    /// the ground with as it moves over it at `forward` and `lateral` m/s,
+    /// and the spin it is left turning at. `stop` is the force per m/s
+    /// that halts the wheel's share of the chassis within the tick.
+    fn traction(
+        &self,
+        wheel: &Wheel,
+        motor: &WheelMotor,
+        load: f32,
+        forward: f32,
+        lateral: f32,
+        stop: f32,
+    ) -> (f32, f32, f32) {
+        let (grip, side_grip) = match self.tires {
+            Some(ref tires) => (
+                tires::peak(&tires.longitudinal),
+                tires::peak(&tires.lateral),
+            ),
+            None => (WHEEL_FRICTION, WHEEL_FRICTION),
+        };
+        // A wheel braked harder than the ground can turn it locks, and the
+        // whole patch slides.
+        if motor.brake && motor.torque > grip * load * wheel.radius {
+            let speed = forward.hypot(lateral);
+            let friction = (speed * stop).min(grip * load) / speed.max(f32::EPSILON);
+            return (-forward * friction, -lateral * friction, 0.0);
+        }
+        // The wheel rolls with the ground; its motor pushes toward its
+        // target spin, but not past it.
+        let rolling = forward / wheel.radius;
+        let torque = (motor.gain * (motor.spin - rolling)).clamp(-motor.torque, motor.torque);
+        let reach = ((motor.spin * wheel.radius - forward) * stop).abs();
+        let push = (torque / wheel.radius).clamp(-reach, reach);
+        let settle = (lateral * stop).abs();
+        let side = match self.tires {
+            Some(ref tires) => {
+                tires::forces(tires, tires::Slip::new(forward, lateral, forward)).1 * load
+            }
+            None => -lateral * stop,
+        }
+        .clamp(-settle, settle);
+        // Driving and cornering share the grip.
+        let x = push / (grip * load).max(f32::EPSILON);
+        let y = side / (side_grip * load).max(f32::EPSILON);
+        let usage = x.hypot(y).max(1.0);
+        (push / usage, side / usage, rolling)
+    }
+
+    /// The ground under a raycast car's `wheel`, from the top of its
+    /// travel down to where its tread hangs at rest.
+    fn wheel_ray(&self, physics: &Physics, wheel: &Wheel) -> Option<RayHit> {
+        let pose = physics.body_pose(self.chassis)?;
+        let top = pose * (Vec3::from(wheel.anchor) + Vec3::Y * SUSPENSION_TRAVEL);
+        physics.cast_ray(
+            top,
+            pose.rotation * Vec3::NEG_Y,
+            SUSPENSION_TRAVEL + wheel.radius,
+            &self.bodies(),
+        )
+    }
+
+    /// How a raycast car's `wheel` sits on the chassis, read off its body:
+    /// the steering angle, and how far it has rolled about its axle.
+    fn raycast_mount(&self, physics: &Physics, wheel: &Wheel) -> (f32, f32) {
+        let (Some(chassis), Some(pose)) = (
+            physics.body_pose(self.chassis),
+            physics.body_pose(wheel.rigid_body),
+        ) else {
+            return (0.0, 0.0);
+        };
+        // Steered about chassis Y, then rolled about the steered axle.
+        let mount = chassis.rotation.inverse() * pose.rotation;
+        let axle = mount * Vec3::Z;
+        let steer = axle.x.atan2(axle.z);
+        let rim = Rotation::from_rotation_y(-steer) * mount * Vec3::X;
+        (steer, rim.y.atan2(rim.x))
+    }
+
+    /// Angle `wheel` is steered to: the target on its steering joint, or a
+    /// raycast wheel's own angle on the chassis. `0` for a rear wheel.
+    fn steer_angle(&self, physics: &Physics, wheel: &Wheel) -> f32 {
+        if !wheel.is_steering {
+            return 0.0;
+        }
+        match self.kind {
+            VehicleKind::Jointed => wheel.steering_joint.map_or(0.0, |joint| {
+                physics.joint_motor_position(joint, JointAxis::AngY)
+            }),
+            VehicleKind::Raycast => self.raycast_mount(physics, wheel).0,
+        }
+    }
+
+    /// Steering angles of the front wheels, in car.ron order.
+    pub fn steer_angles(&self, physics: &Physics) -> Vec<f32> {
+        self.wheels
+            .iter()
+            .filter(|w| w.is_steering)
+            .map(|w| self.steer_angle(physics, w))
+            .collect()
+    }
+
+    /// Chassis speed along its nose (-X), negative when rolling backward.
+    pub fn forward_speed(&self, physics: &Physics) -> f32 {
+        let Some(pose) = physics.body_pose(self.chassis) else {
+            return 0.0;
+        };
+        physics
+            .body_linvel(self.chassis)
+            .dot(pose.rotation * Vec3::NEG_X)
+    }
+
+    /// Push a grounded `wheel` with the tyre model's forces for how it
+    /// slips. Contact normals and loads are those of the last step.
+    fn apply_tire(
+        &self,
+        physics: &mut Physics,
+        terrain: &TerrainBody,
+        wheel: &Wheel,
+        tires: &config::Tires,
+    ) {
+        let Some(contact) = physics.ground_contact(wheel.rigid_body, terrain) else {
+            return;
+        };
+        let parent = wheel.knuckle.unwrap_or(self.chassis);
+        let (Some(pose), Some(parent_pose)) = (
+            physics.body_pose(wheel.rigid_body),
+            physics.body_pose(parent),
+        ) else {
+            return;
+        };
+        let axle = parent_pose.rotation * Vec3::Z;
+        // Chassis forward is -X and the axle +Z, so axle × up points ahead.
+        let forward = axle.cross(contact.normal).normalize_or_zero();
+        if forward == Vec3::ZERO {
+            return;
+        }
+        let lateral = contact.normal.cross(forward);
+        let velocity = physics.body_linvel(wheel.rigid_body);
+        let spin = physics.body_angvel(wheel.rigid_body).dot(axle);
+        let slip = tires::Slip::new(
+            velocity.dot(forward),
+            velocity.dot(lateral),
+            spin * wheel.radius,
+        );
+        let (fx, fy) = tires::forces(tires, slip);
+        let force = (forward * fx + lateral * fy) * contact.force;
+        let patch = pose.translation - contact.normal * wheel.radius;
+        physics.apply_impulse_at_point(wheel.rigid_body, force * physics.dt(), patch);
+    }
+
+    /// How fast `wheel` turns about its axle (rad/s), relative to what
+    /// holds it; positive rolls the car forward.
+    pub fn wheel_spin(&self, physics: &Physics, wheel: &Wheel) -> f32 {
+        let parent = wheel.knuckle.unwrap_or(self.chassis);
+        // A raycast wheel is steered in its own pose, not its parent's.
+        let steered = match self.kind {
+            VehicleKind::Jointed => parent,
+            VehicleKind::Raycast => wheel.rigid_body,
+        };
+        let Some(pose) = physics.body_pose(steered) else {
+            return 0.0;
+        };
+        (physics.body_angvel(wheel.rigid_body) - physics.body_angvel(parent))
+            .dot(pose.rotation * Vec3::Z)
+    }
+
+    /// What the drivetrain makes of `throttle` at the wheels' current
+    /// spin, or `None` for a car without one.
+    pub fn gearing(
+        &self,
+        physics: &Physics,
+        throttle: f32,
+        boost: bool,
+    ) -> Option<drivetrain::Output> {
+        let train = self.drivetrain.as_ref()?;
+        let wheels: Vec<DrivenWheel> = self
+            .wheels
+            .iter()
+            .map(|w| DrivenWheel {
+                front: w.is_steering,
+                spin: self.wheel_spin(physics, w),
+            })
+            .collect();
+        Some(drivetrain::run(train, throttle, boost, &wheels))
+    }
+
+    /// Torque a full pedal brakes `wheel` with: its axle's share of
+    /// `brakes.torque`, split evenly across that axle.
+    fn brake_torque(&self, wheel: &Wheel) -> f32 {
+        let share = if wheel.is_steering {
+            self.brakes.front_bias
+        } else {
+            1.0 - self.brakes.front_bias
+        };
+        let axle = self
+            .wheels
+            .iter()
+            .filter(|w| w.is_steering == wheel.is_steering)
+            .count();
+        self.brakes.torque * share.clamp(0.0, 1.0) / axle as f32
+    }
+
+    /// True if any wheel *or* the chassis itself is in contact with the
+    /// terrain; a raycast wheel is when its ray finds the ground. The
+    /// chassis fallback handles the upside-down case: when the car is on
+    /// its roof, the wheels are airborne but the chassis's top-corner balls
+    /// are pressed against the ground, and a jump from there should still
+    /// launch the cabin off the surface.
+    pub fn grounded(&self, physics: &Physics, terrain: &TerrainBody) -> bool {
+        self.wheels.iter().any(|w| match self.kind {
+            VehicleKind::Jointed => physics.is_touching_terrain(w.rigid_body, terrain),
+            VehicleKind::Raycast => self.wheel_ray(physics, w).is_some(),
+        }) || physics.is_touching_terrain(self.chassis, terrain)
+    }
+
+    /// Kick the chassis off the ground, `charge` in `0..=1` picking the
+    /// launch speed between a tap and a full jump. Returns that speed, or
+    /// `None` when the car is airborne and has nothing to push against.
+    pub fn jump(&self, physics: &mut Physics, terrain: &TerrainBody, charge: f32) -> Option<f32> {
+        if !self.grounded(physics, terrain) {
+            return None;
+        }
+        let pose = physics.body_pose(self.chassis)?;
+        let velocity =
+            JUMP_MIN_VELOCITY + (JUMP_MAX_VELOCITY - JUMP_MIN_VELOCITY) * charge.clamp(0.0, 1.0);
+
+        // Detect upside-down. World "up" is radial-outward from the world's
+        // gravity anchor (sphere origin, cylinder Z axis, torus centreline).
+        // Compare it to the chassis +Y direction: if they're on opposite
+        // sides we're upside-down and the impulse should originate from the
+        // *cabin* (chassis +Y_max) pushing the body away from the ground
+        // it's resting on, instead of from the wheels.
+        let world_up = terrain.up(pose.translation);
+        let upright = (pose.rotation * Vec3::Y).dot(world_up) >= 0.0;
+        let (anchor_y, push_dir_local) = if upright {
+            // Upright: bottom of chassis pushes off the ground in chassis +Y.
+            (self.chassis_bottom_y, Vec3::Y)
+        } else {
+            // Upside-down: top of chassis (the cabin, now resting against
+            // the ground) pushes in chassis -Y, which is world +up.
+            (self.chassis_top_y, -Vec3::Y)
+        };
+        let anchor = pose * Vec3::new(0.0, anchor_y, 0.0);
+        let mass = physics.body_mass(self.chassis);
+        let impulse = (pose.rotation * push_dir_local) * (mass * velocity);
+        physics.apply_impulse_at_point(self.chassis, impulse, anchor);
+        Some(velocity)
+    }
+
+    /// Spin the chassis about its forward axis so the car can be flipped
+    /// back upright after a roll-over: `direction` is +1 to roll right
+    /// (clockwise seen from behind), -1 to roll left. Does nothing and
+    /// returns false while airborne: in the air there is no leverage to
+    /// flip the chassis, and letting it spin freely would feel arcadey
+    /// rather than physical.
+    pub fn roll(&self, physics: &mut Physics, terrain: &TerrainBody, direction: f32) -> bool {
+        if !self.grounded(physics, terrain) {
+            return false;
+        }
+        let Some(pose) = physics.body_pose(self.chassis) else {
+            return false;
+        };
+        // ω target ~ 6 rad/s — enough to spin a typical chassis past 90°
+        // before damping kicks in. Scale by mass so light/heavy vehicles
+        // both flip in roughly the same time.
+        let target_ang_speed = 6.0;
+        let angular_impulse = physics.body_mass(self.chassis) * target_ang_speed;
+        let forward = pose.rotation * -Vec3::X;
+        physics.apply_torque_impulse(self.chassis, forward * (direction * angular_impulse));
+        true
+    }
+
+    /// Let the wheels roll free, for when nobody is driving.
+    pub fn release(&self, physics: &mut Physics) {
+        for joint in self.wheels.iter().filter_map(|w| w.joint) {
+            physics.set_joint_motor_velocity(joint, 0.0, 0.2);
+        }
+    }
+
+    /// Move the whole car, knuckles included, so the chassis sits at
+    /// `pose`, keeping every part where it is relative to the chassis.
+    /// Velocities are zeroed.
+    pub fn place(&self, physics: &mut Physics, pose: Pose) {
+        let Some(chassis_pose) = physics.body_pose(self.chassis) else {
+            return;
+        };
+        let to_new = pose * chassis_pose.inverse();
+        let knuckles = self.wheels.iter().filter_map(|w| w.knuckle);
+        for body in self.bodies().into_iter().chain(knuckles) {
+            if let Some(body_pose) = physics.body_pose(body) {
+                physics.set_body_pose(body, to_new * body_pose);
+                physics.set_linvel(body, Vec3::ZERO);
+                physics.set_angvel(body, Vec3::ZERO);
+            }
+        }
+    }
+
+    /// Shift the whole car by `offset` without touching its velocities.
+    pub fn shift(&self, physics: &mut Physics, offset: Vec3) {
+        let knuckles = self.wheels.iter().filter_map(|w| w.knuckle);
+        for body in self.bodies().into_iter().chain(knuckles) {
+            if let Some(mut body_pose) = physics.body_pose(body) {
+                body_pose.translation += offset;
+                physics.set_body_pose(body, body_pose);
+            }
+        }
+    }
+
+    /// Take every body of the car out of `physics`.
+    pub fn despawn(&self, physics: &mut Physics) {
+        for shell in self.weapons.shells() {
+            physics.remove_rigid_body(shell);
+        }
+        let knuckles = self.wheels.iter().filter_map(|w| w.knuckle);
+        for body in self.bodies().into_iter().chain(knuckles) {
+            physics.remove_rigid_body(body);
+        }
+    }
+}
+
+/// The Jump button, as a charge: pressing it on the ground starts one, and
+/// letting go jumps with the charge built up, scaled by how long it was
+/// held. Timed on the wall clock, not in physics steps.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JumpCharge {
+    start: Option<Instant>,
+}
+
+impl JumpCharge {
+    /// Jump went down: start charging, if `grounded` and not already.
+    pub fn press(&mut self, grounded: bool) {
+        if self.start.is_none() && grounded {
+            self.start = Some(Instant::now());
+        }
+    }
+
+    /// Jump came up: the charge (`0..=1`) to [`Vehicle::jump`] with, if one
+    /// was building.
+    pub fn release(&mut self) -> Option<f32> {
+        let start = self.start.take()?;
+        Some((start.elapsed().as_secs_f32() / JUMP_MAX_CHARGE.as_secs_f32()).min(1.0))
+    }
+
+    /// A full charge once Jump has been held for [`JUMP_MAX_CHARGE`], which
+    /// ends the charge. Call every frame while the car can jump.
+    pub fn full(&mut self) -> Option<f32> {
+        let start = self.start?;
+        if start.elapsed() < JUMP_MAX_CHARGE {
+            return None;
+        }
+        self.start = None;
+        Some(1.0)
+    }
+
+    /// Drop the charge without jumping.
+    pub fn cancel(&mut self) {
+        self.start = None;
+    }
+}
+
+/// Build a closed cylinder mesh centred at the origin, with its axle along
+/// local +Z, suitable for rendering a wheel attached to a rigid body whose
+/// spin axis is local Z. Returns a single-material ModelDesc with a dark
+/// tire-coloured material — uploadable through `Loader::load_model`.
+/// `radius` is the physics wheel's, from car.ron.
+pub fn wheel_mesh_desc(radius: f32) -> ModelDesc {
+    use nalgebra::{Point2, Point3, Vector3};
+    const SEGMENTS: usize = 16;
+    let radius = radius * WHEEL_MESH_RADIUS_SCALE;
+    let half_width = WHEEL_HALF_WIDTH;
+    // 4 ring vertices per segment (side+top, side+bot, cap+top, cap+bot) plus
+    // 2 cap centers. Caps need their own +Z/-Z normals — sharing the side
+    // vertices' radial-outward normals across the cap triangles smooths the
+    // edge into a sphere instead of a cylinder.
+    let mut vertices: Vec<VertexDesc> = Vec::with_capacity(SEGMENTS * 4 + 2);
+    let mut indices: Vec<[u32; 3]> = Vec::with_capacity(SEGMENTS * 4);
+
+    for i in 0..SEGMENTS {
+        let angle = (i as f32 / SEGMENTS as f32) * std::f32::consts::TAU;
+        let (s, c) = angle.sin_cos();
+        let outward = Vector3::new(c, s, 0.0);
+        let u = i as f32 / SEGMENTS as f32;
+        // Side-wall pair (radial-outward normal, used by the tread quads).
+        vertices.push(VertexDesc {
+            pos: Point3::new(radius * c, radius * s, half_width),
+            tex_coords: Point2::new(u, 0.0),
+            normal: outward,
+        });
+        vertices.push(VertexDesc {
+            pos: Point3::new(radius * c, radius * s, -half_width),
+            tex_coords: Point2::new(u, 1.0),
+            normal: outward,
+        });
+        // Cap-edge pair (axis-aligned normal, used by the cap triangle fans).
+        vertices.push(VertexDesc {
+            pos: Point3::new(radius * c, radius * s, half_width),
+            tex_coords: Point2::new(0.5 + 0.5 * c, 0.5 + 0.5 * s),
+            normal: Vector3::new(0.0, 0.0, 1.0),
+        });
+        vertices.push(VertexDesc {
+            pos: Point3::new(radius * c, radius * s, -half_width),
+            tex_coords: Point2::new(0.5 + 0.5 * c, 0.5 + 0.5 * s),
+            normal: Vector3::new(0.0, 0.0, -1.0),
+        });
+    }
+    let top_center = vertices.len() as u32;
+    vertices.push(VertexDesc {
+        pos: Point3::new(0.0, 0.0, half_width),
+        tex_coords: Point2::new(0.5, 0.5),
+        normal: Vector3::new(0.0, 0.0, 1.0),
+    });
+    let bot_center = vertices.len() as u32;
+    vertices.push(VertexDesc {
+        pos: Point3::new(0.0, 0.0, -half_width),
+        tex_coords: Point2::new(0.5, 0.5),
+        normal: Vector3::new(0.0, 0.0, -1.0),
+    });
+
+    for i in 0..SEGMENTS {
+        let next = (i + 1) % SEGMENTS;
+        let s0 = (i * 4) as u32; // side top
+        let s1 = (i * 4 + 1) as u32; // side bot
+        let ct0 = (i * 4 + 2) as u32; // cap top
+        let cb0 = (i * 4 + 3) as u32; // cap bot
+        let s2 = (next * 4) as u32;
+        let s3 = (next * 4 + 1) as u32;
+        let ct1 = (next * 4 + 2) as u32;
+        let cb1 = (next * 4 + 3) as u32;
+        indices.push([s0, s1, s2]);
+        indices.push([s1, s3, s2]);
+        indices.push([top_center, ct1, ct0]);
+        indices.push([bot_center, cb0, cb1]);
+    }
+
+    let materials = vec![
+        // Default sentinel material at index 0 — Loader::read_gltf does the
+        // same; load_model copies whatever's in slot 0 verbatim.
+        MaterialDesc::default(),
+        MaterialDesc {
+            name: Some("tire".to_string()),
+            base_color_factor: [0.4, 0.4, 0.4, 1.0],
+            normal_scale: 0.0,
+            transparent: false,
+        },
+    ];
+    let geometry = GeometryDesc {
+        name: "procedural_wheel".to_string(),
+        vertices,
+        indices,
+        index_type: Some(blade_graphics::IndexType::U32),
+        transform: nalgebra::Matrix4::identity(),
+        material_index: 1,
+    };
+    ModelDesc {
+        materials,
+        geometries: vec![geometry],
+    }
+}
+
+/// AABB of the non-wheel chassis vertices in chassis-local coords. Used as a
+/// coarse mass-volume estimate for the chassis (since the up-facing trimesh
+/// is an open surface that Rapier can't integrate over).
+fn chassis_aabb(model_desc: &ModelDesc) -> rapier3d::parry::bounding_volume::Aabb {
+    use rapier3d::parry::bounding_volume::Aabb;
+    let keep = |m: &MaterialDesc| {
+        !m.name
+            .as_deref()
+            .map(|n| n.to_lowercase().contains("wheel"))
+            .unwrap_or(false)
+    };
+    let positions = model_desc.positions_filtered(keep);
+    if positions.is_empty() {
+        return Aabb::new_invalid();
+    }
+    let mut mins = positions[0];
+    let mut maxs = positions[0];
+    for p in &positions[1..] {
+        mins.x = mins.x.min(p.x);
+        mins.y = mins.y.min(p.y);
+        mins.z = mins.z.min(p.z);
+        maxs.x = maxs.x.max(p.x);
+        maxs.y = maxs.y.max(p.y);
+        maxs.z = maxs.z.max(p.z);
+    }
+    Aabb::new(
+        Vec3::new(mins.x, mins.y, mins.z),
+        Vec3::new(maxs.x, maxs.y, maxs.z),
+    )
+}
+
+/// Build the chassis's collision proxy as a set of small balls placed at the
+/// 8 corners of the (non-wheel) chassis AABB. The bilinear-surface dispatcher
+/// only generates contacts for Ball shapes, so using balls — rather than a
+/// single Cuboid/TriMesh — lets every chassis corner get a proper smooth
+/// contact with the terrain.
+///
+/// Together the corners act as a coarse "do not sink through ground" cage:
+/// flipped over, the chassis-+Y corners (now pointing radially inward) catch
+/// on the surface before the body can fall through.
+///
+/// Wheel-vs-chassis contacts are disabled at each wheel joint
+/// (`contacts_enabled(false)`), so the corner balls don't fight the wheel
+/// colliders even if they overlap geometrically.
+fn create_chassis_colliders(model_desc: &ModelDesc) -> Vec<Collider> {
+    let aabb = chassis_aabb(model_desc);
+    // Only the TOP four corners (chassis-local +Y face). When the chassis is
+    // upright, these sit above the wheel envelope and never touch terrain,
+    // so they don't snag on ridges taller than the ground clearance. When
+    // the chassis flips upside-down, they become the new bottom and support
+    // the body from sinking through the heightfield (the original reason
+    // these colliders exist). Bottom corners were dropped because they
+    // caught on every Fostral ridge > ~0.5 m and wedged the car solid.
+    const CORNER_RADIUS: f32 = 0.10;
+    let corners = [
+        Vec3::new(aabb.mins.x, aabb.maxs.y, aabb.mins.z),
+        Vec3::new(aabb.maxs.x, aabb.maxs.y, aabb.mins.z),
+        Vec3::new(aabb.mins.x, aabb.maxs.y, aabb.maxs.z),
+        Vec3::new(aabb.maxs.x, aabb.maxs.y, aabb.maxs.z),
+    ];
+    log::info!(
+        "chassis AABB: x=[{:.2}, {:.2}] y=[{:.2}, {:.2}] z=[{:.2}, {:.2}], {} top-corner balls (r={CORNER_RADIUS})",
+        aabb.mins.x,
+        aabb.maxs.x,
+        aabb.mins.y,
+        aabb.maxs.y,
+        aabb.mins.z,
+        aabb.maxs.z,
+        corners.len(),
+    );
+    corners
+        .iter()
+        .map(|&p| {
+            ColliderBuilder::ball(CORNER_RADIUS)
+                .translation(p)
+                // Zero density — chassis mass comes from additional_mass_properties.
+                .density(0.0)
+                // Frictionless: corner balls catch the chassis radially (normal
+                // force prevents sinking through terrain) but mustn't brake the
+                // chassis when it's driving past a bump that's tall enough for a
+                // corner to graze the surface. Wheel friction (3.0) still does
+                // all the driving traction work.
+                .friction(0.0)
+                .build()
+        })
+        .collect()
+}
+
+/// Radial spawn height for `map`, whose height map is `alpha` at
+/// `width`×`height`. Cylinder/torus spawns keep the historical "just below
+/// the sky" height; the sphere samples the heightmap at the spawn (θ, v) and
+/// lands ~1 m above the actual surface so the chassis isn't dropped in from
+/// radius_end (where it would fall ~half the world's radial range).
+pub fn spawn_radius(map: &config::Map, alpha: &[u8], width: u32, height: u32) -> f32 {
+    if map.shape != WorldShape::Sphere {
+        return map.radius.end - 0.5;
+    }
+    let sample_uv = |u: f32, v: f32| -> f32 {
+        let ux = ((u * width as f32) as u32).min(width - 1);
+        let vy = ((v * height as f32) as u32).min(height - 1);
+        let idx = vy as usize * width as usize + ux as usize;
+        alpha[idx] as f32 / 255.0
+    };
+    // Spawn point in (u, v): u = 0.25 corresponds to longitude π/2
+    // (the +Y axis), v = 0.5 is the equator (sin φ = 0).
+    let spawn_alpha = sample_uv(0.25, 0.5);
+    let dr_range = map.radius.end - map.radius.start;
+    let ground_r = map.radius.start + spawn_alpha * dr_range;
+    (ground_r + 1.0).min(map.radius.end - 0.1)
+}
+
+/// Axial spawn offset: z on the cylinder, centreline arc length on the
+/// torus (both 10% into the map so the seam isn't underfoot). The sphere
+/// spawns on the equator.
+pub fn spawn_axial(map: &config::Map) -> f32 {
+    match map.shape {
+        WorldShape::Sphere => 0.0,
+        WorldShape::Cylinder | WorldShape::Torus => 0.1 * map.length,
+    }
+}
+
+/// Initial chassis pose: chassis +Y along the world "up" at the spawn
+/// point, chassis forward (-X) along the world's axial direction.
+pub fn spawn_pose(
+    map: &config::Map,
+    spawn_radius: f32,
+    spawn_axial: f32,
+) -> nalgebra::Isometry3<f32> {
+    match map.shape {
+        // Cylinder and sphere spawn on the +Y side: up = +Y, and rotating
+        // the chassis 90° about Y points its forward (-X) along +Z.
+        WorldShape::Cylinder | WorldShape::Sphere => nalgebra::Isometry3 {
+            translation: nalgebra::Vector3::new(0.0, spawn_radius, spawn_axial).into(),
+            rotation: nalgebra::UnitQuaternion::from_axis_angle(
+                &nalgebra::Vector3::y_axis(),
+                0.5 * f32::consts::PI,
+            ),
+        },
+        WorldShape::Torus => {
+            let major_radius = map.length / f32::consts::TAU;
+            let phi = spawn_axial / major_radius;
+            // Tube angle π/2: the +Z side of the tube, so up = +Z there.
+            let translation = nalgebra::Vector3::new(
+                major_radius * phi.cos(),
+                major_radius * phi.sin(),
+                spawn_radius,
+            );
+            let forward = nalgebra::Vector3::new(-phi.sin(), phi.cos(), 0.0);
+            upright_pose(translation, nalgebra::Vector3::z(), forward)
+        }
+    }
+}
+
+/// Chassis pose at `translation` with its +Y along `up` and its nose (-X)
+/// along `forward`, flattened onto the plane across `up`.
+pub fn upright_pose(
+    translation: nalgebra::Vector3<f32>,
+    up: nalgebra::Vector3<f32>,
+    forward: nalgebra::Vector3<f32>,
+) -> nalgebra::Isometry3<f32> {
+    let c_y = up.normalize();
+    let flat = forward - c_y * forward.dot(&c_y);
+    let flat = if flat.norm() > 1e-6 {
+        flat
+    } else {
+        // Nose straight up or down: any heading will do.
+        let side = if c_y.x.abs() < 0.9 {
+            nalgebra::Vector3::x()
+        } else {
+            nalgebra::Vector3::y()
+        };
+        c_y.cross(&side)
+    };
+    let c_x = -flat.normalize(); // chassis forward is -X
+    let c_z = c_x.cross(&c_y);
+    let rotation = nalgebra::UnitQuaternion::from_rotation_matrix(
+        &nalgebra::Rotation3::from_matrix_unchecked(nalgebra::Matrix3::from_columns(&[
+            c_x, c_y, c_z,
+        ])),
+    );
+    nalgebra::Isometry3 {
+        translation: translation.into(),
+        rotation,
+    }
+}
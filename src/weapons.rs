@@ -0,0 +1,309 @@
+//! Vehicle-mounted weapons. Cannons launch shells — CCD balls that fall
+//! under the map's gravity like any other body — while beams strike the
+//! first collider along the barrel at once. Either way a hit turns into an
+//! [`Impact`], which the owner feeds to [`Damage`] and, for ground hits, to
+//! `TerrainMesh::dig_crater`. [`Shells`] draws the shells in flight.
+
+use crate::config;
+use crate::loader::Loader;
+use crate::model::{GeometryDesc, MaterialDesc, Model, ModelDesc, ModelInstance, VertexDesc};
+use crate::physics::{Physics, PhysicsBodyHandle};
+use nalgebra::{Point2, Point3, Vector3};
+use rapier3d::dynamics::{RigidBodyBuilder, RigidBodyHandle};
+use rapier3d::geometry::ColliderBuilder;
+use rapier3d::math::{Pose, Vec3};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Seconds a shell may fly before it is removed without going off.
+const SHELL_LIFETIME: f32 = 10.0;
+/// Visual radius of a shell (m). Shells are tiny next to the car; drawing
+/// them a little larger than the typical collider keeps them trackable.
+const SHELL_MESH_RADIUS: f32 = 0.12;
+
+/// A round striking something.
+#[derive(Clone, Copy, Debug)]
+pub struct Impact {
+    pub point: Vec3,
+    /// Body struck; compare with `TerrainBody::body` to spot ground hits.
+    pub body: Option<RigidBodyHandle>,
+    pub damage: f32,
+    pub crater_radius: f32,
+    pub crater_depth: f32,
+}
+
+impl Impact {
+    fn new(weapon: &config::Weapon, point: Vec3, body: Option<RigidBodyHandle>) -> Self {
+        Self {
+            point,
+            body,
+            damage: weapon.damage,
+            crater_radius: weapon.crater_radius,
+            crater_depth: weapon.crater_depth,
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct Mount {
+    weapon: config::Weapon,
+    /// Seconds until the next shot is allowed.
+    cooldown: f32,
+    ammo: Option<u32>,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct Shell {
+    body: RigidBodyHandle,
+    /// Index of the mount that fired it.
+    mount: usize,
+    expires_at: f32,
+}
+
+/// The weapons of one vehicle, with their cooldown and ammo state and the
+/// shells they have in flight.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Weapons {
+    mounts: Vec<Mount>,
+    /// Bodies of the carrying vehicle. Its own rounds never strike them.
+    owner: Vec<RigidBodyHandle>,
+    shells: Vec<Shell>,
+    /// Beam hits waiting for the next `update`. Never saved: `update`
+    /// drains it in the same tick the beam fires.
+    #[serde(skip)]
+    pending: Vec<Impact>,
+}
+
+impl Weapons {
+    pub fn new(weapons: &[config::Weapon], owner: Vec<RigidBodyHandle>) -> Self {
+        Self {
+            mounts: weapons
+                .iter()
+                .map(|weapon| Mount {
+                    weapon: weapon.clone(),
+                    cooldown: 0.0,
+                    ammo: weapon.ammo,
+                })
+                .collect(),
+            owner,
+            shells: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn mount_count(&self) -> usize {
+        self.mounts.len()
+    }
+
+    /// Rounds left in mount `index`; `None` when unlimited or no such mount.
+    pub fn ammo(&self, index: usize) -> Option<u32> {
+        self.mounts.get(index).and_then(|mount| mount.ammo)
+    }
+
+    pub fn is_ready(&self, index: usize) -> bool {
+        self.mounts
+            .get(index)
+            .is_some_and(|mount| mount.cooldown <= 0.0 && mount.ammo != Some(0))
+    }
+
+    /// Pull the trigger of mount `index` on the vehicle whose chassis is
+    /// `chassis`. Returns false, doing nothing, while the mount cools down
+    /// or once it is out of ammo.
+    pub fn fire(&mut self, index: usize, physics: &mut Physics, chassis: RigidBodyHandle) -> bool {
+        if !self.is_ready(index) {
+            return false;
+        }
+        let Some(pose) = physics.body_pose(chassis) else {
+            return false;
+        };
+        let mount = &mut self.mounts[index];
+        mount.cooldown = mount.weapon.cooldown;
+        if let Some(ammo) = mount.ammo.as_mut() {
+            *ammo -= 1;
+        }
+        let weapon = &mount.weapon;
+        let muzzle = pose.rotation * Vec3::from(weapon.position) + pose.translation;
+        let dir = (pose.rotation * Vec3::from(weapon.direction)).normalize();
+        match weapon.kind {
+            config::WeaponKind::Cannon {
+                muzzle_velocity,
+                shell_radius,
+                shell_mass,
+            } => {
+                let rb = RigidBodyBuilder::dynamic()
+                    .pose(Pose::from_translation(muzzle))
+                    .linvel(physics.body_linvel(chassis) + dir * muzzle_velocity)
+                    .ccd_enabled(true)
+                    .build();
+                let collider = ColliderBuilder::ball(shell_radius).mass(shell_mass).build();
+                let PhysicsBodyHandle {
+                    rigid_body_handle, ..
+                } = physics.add_rigid_body(rb, vec![collider]);
+                self.shells.push(Shell {
+                    body: rigid_body_handle,
+                    mount: index,
+                    expires_at: physics.last_time() + SHELL_LIFETIME,
+                });
+            }
+            config::WeaponKind::Beam { range } => {
+                if let Some(hit) = physics.cast_ray(muzzle, dir, range, &self.owner) {
+                    self.pending.push(Impact::new(weapon, hit.point, hit.body));
+                }
+            }
+        }
+        true
+    }
+
+    /// Advance the cooldowns by `dt` seconds and collect the impacts since
+    /// the last call: beam hits, and shells that touched anything but their
+    /// own vehicle. Spent and expired shells are removed from `physics`.
+    /// Call once after every `Physics::step`.
+    pub fn update(&mut self, physics: &mut Physics, dt: f32) -> Vec<Impact> {
+        for mount in self.mounts.iter_mut() {
+            mount.cooldown = (mount.cooldown - dt).max(0.0);
+        }
+        let mut impacts = std::mem::take(&mut self.pending);
+        let now = physics.last_time();
+        self.shells.retain(|shell| {
+            let struck = physics
+                .touching_bodies(shell.body)
+                .into_iter()
+                .find(|body| !self.owner.contains(body));
+            if struck.is_none() && now < shell.expires_at {
+                return true;
+            }
+            if let (Some(body), Some(pose)) = (struck, physics.body_pose(shell.body)) {
+                let weapon = &self.mounts[shell.mount].weapon;
+                impacts.push(Impact::new(weapon, pose.translation, Some(body)));
+            }
+            physics.remove_rigid_body(shell.body);
+            false
+        });
+        impacts
+    }
+
+    /// Bodies of the shells in flight, for drawing them.
+    pub fn shells(&self) -> impl Iterator<Item = RigidBodyHandle> + '_ {
+        self.shells.iter().map(|shell| shell.body)
+    }
+}
+
+/// Health bookkeeping. A target may span several bodies — a car's chassis
+/// and wheels — that share one pool, so a hit anywhere on it counts.
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Damage {
+    /// The pool each tracked body draws from, keyed by its first body.
+    pools: HashMap<RigidBodyHandle, RigidBodyHandle>,
+    health: HashMap<RigidBodyHandle, f32>,
+}
+
+impl Damage {
+    /// Track `bodies` as one target with `health` points, identified from
+    /// now on by its first body. Tracking again resets the health.
+    pub fn track(&mut self, bodies: &[RigidBodyHandle], health: f32) {
+        let Some(&id) = bodies.first() else {
+            return;
+        };
+        for &body in bodies {
+            self.pools.insert(body, id);
+        }
+        self.health.insert(id, health);
+    }
+
+    /// Health left in the pool `body` belongs to.
+    pub fn health(&self, body: RigidBodyHandle) -> Option<f32> {
+        let id = self.pools.get(&body)?;
+        self.health.get(id).copied()
+    }
+
+    /// Deal `impact`'s damage. Returns the target's id when this blow is
+    /// the one that wrecks it; hits on a wreck change nothing.
+    pub fn apply(&mut self, impact: &Impact) -> Option<RigidBodyHandle> {
+        let id = *self.pools.get(&impact.body?)?;
+        let health = self.health.get_mut(&id)?;
+        if *health <= 0.0 {
+            return None;
+        }
+        *health -= impact.damage;
+        (*health <= 0.0).then_some(id)
+    }
+}
+
+/// One render instance per shell in flight, all sharing a small mesh.
+pub struct Shells {
+    model: Arc<Model>,
+    pub instances: Vec<ModelInstance>,
+}
+
+impl Shells {
+    pub fn new(loader: &mut Loader) -> Self {
+        Self {
+            model: Arc::new(loader.load_model(&shell_mesh_desc(SHELL_MESH_RADIUS))),
+            instances: Vec::new(),
+        }
+    }
+
+    /// Match the instances to the shells `weapons` has in flight.
+    pub fn sync(&mut self, physics: &Physics, weapons: &Weapons) {
+        self.instances.clear();
+        for body in weapons.shells() {
+            self.instances.push(ModelInstance {
+                model: self.model.clone(),
+                transform: physics.get_transform(body),
+                geometry_filter: None,
+                casts_shadow: true,
+            });
+        }
+    }
+
+    pub fn free(&self, ctx: &blade_graphics::Context) {
+        self.model.free(ctx);
+    }
+}
+
+fn shell_mesh_desc(radius: f32) -> ModelDesc {
+    // Octahedron, flat-shaded: 8 faces with their own normals. Like the
+    // snowflakes, shells are too small on screen for a rounder mesh to pay.
+    let axes = [Vector3::x(), Vector3::y(), -Vector3::x(), -Vector3::y()];
+    let mut vertices: Vec<VertexDesc> = Vec::with_capacity(24);
+    let mut indices: Vec<[u32; 3]> = Vec::with_capacity(8);
+    for pole in [Vector3::z(), -Vector3::z()] {
+        for i in 0..axes.len() {
+            let (a, b) = (axes[i], axes[(i + 1) % axes.len()]);
+            // Keep the winding counter-clockwise seen from outside.
+            let (a, b) = if pole.z > 0.0 { (a, b) } else { (b, a) };
+            let n = (a + b + pole).normalize();
+            let base = vertices.len() as u32;
+            for v in [a, b, pole] {
+                vertices.push(VertexDesc {
+                    pos: Point3::from(v * radius),
+                    tex_coords: Point2::new(0.5, 0.5),
+                    normal: n,
+                });
+            }
+            indices.push([base, base + 1, base + 2]);
+        }
+    }
+
+    let materials = vec![
+        MaterialDesc::default(),
+        MaterialDesc {
+            name: Some("shell".to_string()),
+            base_color_factor: [0.25, 0.22, 0.2, 1.0],
+            normal_scale: 0.0,
+            transparent: false,
+        },
+    ];
+    let geometry = GeometryDesc {
+        name: "shell".to_string(),
+        vertices,
+        indices,
+        index_type: Some(blade_graphics::IndexType::U32),
+        transform: nalgebra::Matrix4::identity(),
+        material_index: 1,
+    };
+    ModelDesc {
+        materials,
+        geometries: vec![geometry],
+    }
+}
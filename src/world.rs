@@ -0,0 +1,71 @@
+//! The map and car `data/config.ron` names, loaded the same way by the
+//! game, the server and the headless simulation: a client's prediction only
+//! holds if it collides with the very ground the server does.
+
+use crate::{Loader, ModelDesc, assets, config, tin};
+use blade_graphics::Extent;
+use nalgebra::Matrix4;
+use std::{f32, path::PathBuf};
+
+pub struct World {
+    pub map: config::Map,
+    /// Size of the height map, after any downsampling.
+    pub extent: Extent,
+    /// The map image as RGBA8; its alpha is the height.
+    pub rgba: Vec<u8>,
+    /// The alpha channel of `rgba` alone, for the CPU side.
+    pub alpha: Vec<u8>,
+    /// The height map triangulated once: the renderer draws these triangles
+    /// and the physics collides with them.
+    pub mesh: tin::TerrainMesh,
+    pub car: config::Car,
+    pub car_model: ModelDesc,
+}
+
+pub fn load_world(config: &config::Config) -> World {
+    log::info!("Loading map: {}", config.map);
+    let map_path = PathBuf::from("data/maps").join(&config.map);
+    let mut map: config::Map = ron::de::from_bytes(&assets::read(&map_path.join("map.ron")))
+        .expect("Unable to parse the map config");
+
+    // The map is far denser than the gameplay needs (~3 cm/texel on
+    // Fostral). The web build shrinks it 4x: the single-threaded TIN fit,
+    // the vertex buffers, and the shadow map all drop well inside browser
+    // budgets, at ~12 cm/texel.
+    let downsample = if cfg!(target_arch = "wasm32") { 4 } else { 1 };
+    let (extent, rgba) = Loader::decode_png(&assets::read(&map_path.join("map.png")), downsample);
+    let alpha = Loader::height_alpha(&rgba);
+    if map.length == 0.0 {
+        let circumference = 2.0 * f32::consts::PI * map.radius.start;
+        map.length = circumference * (extent.height as f32) / (extent.width as f32);
+        log::info!("Derived map length to be {}", map.length);
+    }
+    let mesh = tin::build(
+        &alpha,
+        extent.width,
+        extent.height,
+        &map,
+        config.terrain_quality,
+    );
+
+    log::info!("Loading car: {}", config.car);
+    let car_path = PathBuf::from("data/cars").join(&config.car);
+    let car: config::Car = ron::de::from_bytes(&assets::read(&car_path.join("car.ron")))
+        .expect("Unable to parse the car config");
+    let model_path = car_path.join("body.glb");
+    let car_model = Loader::read_gltf_data(
+        &assets::read(&model_path),
+        &model_path,
+        Matrix4::identity().scale(car.scale),
+    );
+
+    World {
+        map,
+        extent,
+        rgba,
+        alpha,
+        mesh,
+        car,
+        car_model,
+    }
+}
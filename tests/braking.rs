@@ -0,0 +1,121 @@
+//! Braking on the flat cylinder with the production car, jointed and
+//! raycast: the pedal stops it well short of where it would coast to, a
+//! held brake turns into reverse once stopped, and the handbrake locks the
+//! rear wheels only.
+
+use rapier3d::math::Vec3;
+use vandals_and_heroes::{
+    Physics, TerrainBody, Timeline, TimelinePlayer, Vehicle, config, timeline::Key,
+};
+
+mod common;
+use common::{KINDS, play, spawn_car, tick};
+
+/// Give up on a stop after this long.
+const STOP_SECS: f32 = 5.0;
+
+fn position(physics: &Physics, vehicle: &Vehicle) -> Vec3 {
+    physics.body_pose(vehicle.chassis).unwrap().translation
+}
+
+/// Settle, then run flat out for three seconds.
+fn up_to_speed(kind: config::VehicleKind) -> (Physics, TerrainBody, Vehicle) {
+    let (mut physics, terrain, vehicle) = spawn_car(kind);
+    let timeline = Timeline::default()
+        .hold(&[], 1.0)
+        .hold(&[Key::Forward], 3.0);
+    play(&mut physics, &terrain, &vehicle, timeline);
+    (physics, terrain, vehicle)
+}
+
+/// Hold `keys` until the car stops rolling forward; returns the distance
+/// covered and the ticks it took.
+fn stop(
+    physics: &mut Physics,
+    terrain: &TerrainBody,
+    vehicle: &Vehicle,
+    keys: &[Key],
+) -> (f32, usize) {
+    let start = position(physics, vehicle);
+    let timeline = Timeline::default().hold(keys, STOP_SECS);
+    for (ticks, step) in TimelinePlayer::new(timeline, physics.dt()).enumerate() {
+        tick(physics, terrain, vehicle, &step.input);
+        if vehicle.forward_speed(physics) < 0.1 {
+            return ((position(physics, vehicle) - start).length(), ticks + 1);
+        }
+    }
+    panic!(
+        "still at {:.2} m/s after {STOP_SECS} s",
+        vehicle.forward_speed(physics)
+    );
+}
+
+#[test]
+fn brakes_stop_short_of_coasting() {
+    for kind in KINDS {
+        let (mut physics, terrain, vehicle) = up_to_speed(kind);
+        let speed = vehicle.forward_speed(&physics);
+        assert!(speed > 1.0, "{kind:?}: only reached {speed:.2} m/s");
+        let at_speed = physics.snapshot();
+
+        let (coasting, coast_ticks) = stop(&mut physics, &terrain, &vehicle, &[]);
+        physics.restore(&at_speed);
+        let (braking, brake_ticks) = stop(&mut physics, &terrain, &vehicle, &[Key::Brake]);
+        println!(
+            "{kind:?} from {speed:.2} m/s: coasted {coasting:.2} m in {coast_ticks} ticks, \
+             braked {braking:.2} m in {brake_ticks} ticks"
+        );
+        assert!(
+            braking < 0.6 * coasting,
+            "{kind:?}: braked in {braking:.2} m, coasted in {coasting:.2} m"
+        );
+        // v²/2a for a deceleration of at least 3 m/s².
+        let bound = speed * speed / (2.0 * 3.0);
+        assert!(
+            braking < bound,
+            "{kind:?}: braked in {braking:.2} m, over {bound:.2} m"
+        );
+    }
+}
+
+#[test]
+fn held_brake_reverses_once_stopped() {
+    for kind in KINDS {
+        let (mut physics, terrain, vehicle) = up_to_speed(kind);
+        stop(&mut physics, &terrain, &vehicle, &[Key::Brake]);
+        let timeline = Timeline::default().hold(&[Key::Brake], 2.0);
+        play(&mut physics, &terrain, &vehicle, timeline);
+        let speed = vehicle.forward_speed(&physics);
+        assert!(
+            speed < -0.5,
+            "{kind:?}: still at {speed:.2} m/s after 2 s on the brake"
+        );
+    }
+}
+
+#[test]
+fn handbrake_locks_the_rear_wheels() {
+    for kind in KINDS {
+        let (mut physics, terrain, vehicle) = up_to_speed(kind);
+        let timeline = Timeline::default().hold(&[Key::Handbrake], 0.25);
+        play(&mut physics, &terrain, &vehicle, timeline);
+        assert!(
+            vehicle.forward_speed(&physics) > 0.3,
+            "{kind:?}: stopped already"
+        );
+        let chassis_spin = physics.body_angvel(vehicle.chassis);
+        let spin = |steering: bool| -> f32 {
+            vehicle
+                .wheels
+                .iter()
+                .filter(|w| w.is_steering == steering)
+                .map(|w| (physics.body_angvel(w.rigid_body) - chassis_spin).length())
+                .fold(0.0, f32::max)
+        };
+        let (front, rear) = (spin(true), spin(false));
+        assert!(
+            rear < 0.1 * front,
+            "{kind:?}: rear wheels spin at {rear:.2} rad/s, fronts at {front:.2}"
+        );
+    }
+}
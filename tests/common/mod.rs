@@ -0,0 +1,93 @@
+//! Fixtures the integration tests share: a flat cylinder world and the
+//! production OxidizeMonk car on it.
+
+// Every test crate compiles this module whole but uses only part of it.
+#![allow(dead_code)]
+
+use nalgebra::Matrix4;
+use std::path::Path;
+use vandals_and_heroes::{
+    DriveInput, Loader, ModelDesc, Physics, TerrainBody, Timeline, TimelinePlayer, Vehicle, config,
+    vehicle,
+};
+
+pub const WIDTH: u32 = 64;
+pub const HEIGHT: u32 = 256;
+/// Just below the outer cylinder, where the game spawns the car.
+pub const SPAWN_RADIUS: f32 = 19.5;
+pub const KINDS: [config::VehicleKind; 2] =
+    [config::VehicleKind::Jointed, config::VehicleKind::Raycast];
+
+/// A 100 m cylinder with the ground between 10 and 20 m out.
+pub fn flat_map() -> config::Map {
+    config::Map {
+        radius: 10.0..20.0,
+        length: 100.0,
+        density: 10.0,
+        shape: config::WorldShape::Cylinder,
+        ..Default::default()
+    }
+}
+
+/// Uniform alpha 128 → ground radius = lerp(10, 20, 128/255) ≈ 15.02.
+pub fn flat_alpha() -> Vec<u8> {
+    vec![128u8; (WIDTH * HEIGHT) as usize]
+}
+
+/// The flat heightfield, shaped by `map`.
+pub fn flat_terrain(physics: &mut Physics, map: &config::Map) -> TerrainBody {
+    physics.create_terrain(map, flat_alpha(), WIDTH, HEIGHT)
+}
+
+pub fn flat_world() -> (Physics, TerrainBody) {
+    let mut physics = Physics::default();
+    let terrain = flat_terrain(&mut physics, &flat_map());
+    (physics, terrain)
+}
+
+/// The production car's `car.ron` and body.
+pub fn load_car() -> (config::Car, ModelDesc) {
+    let car_path = Path::new("data/cars/OxidizeMonk");
+    let car: config::Car =
+        ron::de::from_bytes(&std::fs::read(car_path.join("car.ron")).expect("car.ron present"))
+            .expect("parse car.ron");
+    let model = Loader::read_gltf(
+        &car_path.join("body.glb"),
+        Matrix4::identity().scale(car.scale),
+    );
+    (car, model)
+}
+
+/// `car` with its chassis `radius` out from the axis at the start of the
+/// flat cylinder, nose along +Z.
+pub fn place_car(
+    physics: &mut Physics,
+    car: &config::Car,
+    model: &ModelDesc,
+    radius: f32,
+) -> Vehicle {
+    let pose = vehicle::spawn_pose(&flat_map(), radius, 0.0);
+    Vehicle::spawn(physics, car, model, pose.into())
+}
+
+/// The production car as `kind`, alone on a fresh flat world.
+pub fn spawn_car(kind: config::VehicleKind) -> (Physics, TerrainBody, Vehicle) {
+    let (mut physics, terrain) = flat_world();
+    let (mut car, model) = load_car();
+    car.kind = kind;
+    let vehicle = place_car(&mut physics, &car, &model, SPAWN_RADIUS);
+    (physics, terrain, vehicle)
+}
+
+pub fn tick(physics: &mut Physics, terrain: &TerrainBody, vehicle: &Vehicle, input: &DriveInput) {
+    physics.update_gravity(terrain);
+    vehicle.drive(physics, terrain, input);
+    physics.step();
+}
+
+/// Plays `timeline` through on `vehicle`, a physics tick per step.
+pub fn play(physics: &mut Physics, terrain: &TerrainBody, vehicle: &Vehicle, timeline: Timeline) {
+    for step in TimelinePlayer::new(timeline, physics.dt()) {
+        tick(physics, terrain, vehicle, &step.input);
+    }
+}
@@ -0,0 +1,193 @@
+//! The drivetrain on its own — torque curve, automatic gear choice,
+//! differentials and boost — and then on the production car, jointed and
+//! raycast, which has to work its way up the gears on the flat cylinder.
+
+use vandals_and_heroes::{
+    Timeline, TimelinePlayer, config,
+    drivetrain::{self, DrivenWheel},
+    timeline::Key,
+};
+
+mod common;
+use common::{KINDS, SPAWN_RADIUS, flat_world, load_car, place_car, play, tick};
+
+fn train() -> config::Drivetrain {
+    config::Drivetrain {
+        engine: config::Engine {
+            torque_curve: vec![(1000.0, 1.0), (4000.0, 2.0), (6000.0, 1.0)],
+            idle_rpm: 1000.0,
+            redline_rpm: 6500.0,
+            boost: 1.5,
+        },
+        gearbox: config::Gearbox {
+            ratios: vec![4.0, 2.0, 1.0],
+            reverse: 3.0,
+            final_drive: 2.0,
+        },
+        front_share: 0.0,
+        differential: config::Differential::Open,
+    }
+}
+
+/// Two front wheels rolling at `front`, two rear ones at `rear` (rad/s).
+fn wheels(front: f32, rear: [f32; 2]) -> Vec<DrivenWheel> {
+    vec![
+        DrivenWheel {
+            front: true,
+            spin: front,
+        },
+        DrivenWheel {
+            front: true,
+            spin: front,
+        },
+        DrivenWheel {
+            front: false,
+            spin: rear[0],
+        },
+        DrivenWheel {
+            front: false,
+            spin: rear[1],
+        },
+    ]
+}
+
+/// Wheel spin (rad/s) that turns the engine at `rpm` through `ratio`.
+fn spin_for(train: &config::Drivetrain, ratio: f32, rpm: f32) -> f32 {
+    rpm * std::f32::consts::TAU / 60.0 / (ratio * train.gearbox.final_drive)
+}
+
+#[test]
+fn torque_curve_interpolates_and_cuts_at_the_redline() {
+    let engine = train().engine;
+    let torque = |rpm| drivetrain::engine_torque(&engine, rpm);
+    assert_eq!(torque(500.0), 1.0);
+    assert_eq!(torque(2500.0), 1.5);
+    assert_eq!(torque(5000.0), 1.5);
+    assert_eq!(torque(6200.0), 1.0);
+    assert_eq!(torque(6500.0), 0.0);
+}
+
+#[test]
+fn gearbox_shifts_up_with_speed() {
+    let train = train();
+    let mut last_gear = 0;
+    // Up to just short of the redline in top gear (~340 rad/s).
+    for step in 0..34 {
+        let spin = step as f32 * 10.0;
+        let out = drivetrain::run(&train, 1.0, false, &wheels(spin, [spin; 2]));
+        assert!(
+            out.gear >= last_gear,
+            "shifted down to {} at {spin}",
+            out.gear
+        );
+        assert!(out.rpm <= train.engine.redline_rpm);
+        last_gear = out.gear;
+    }
+    assert_eq!(last_gear, 3);
+
+    // From rest: first gear, engine at idle, pulling.
+    let out = drivetrain::run(&train, 1.0, false, &wheels(0.0, [0.0; 2]));
+    assert_eq!(out.gear, 1);
+    assert_eq!(out.rpm, train.engine.idle_rpm);
+    assert!(out.torques.iter().sum::<f32>() > 0.0);
+    // Half throttle, half the torque.
+    let half = drivetrain::run(&train, 0.5, false, &wheels(0.0, [0.0; 2]));
+    let total = |out: &drivetrain::Output| out.torques.iter().sum::<f32>();
+    assert!((total(&half) - 0.5 * total(&out)).abs() < 1e-4);
+}
+
+#[test]
+fn top_gear_runs_into_the_limiter() {
+    let train = train();
+    let top = spin_for(&train, 1.0, train.engine.redline_rpm);
+    let out = drivetrain::run(&train, 1.0, false, &wheels(top, [top + 1.0; 2]));
+    assert_eq!(out.gear, 3);
+    assert!(out.torques.iter().all(|&t| t == 0.0), "{:?}", out.torques);
+    assert!((out.top_spin - top).abs() < 1e-3);
+}
+
+#[test]
+fn reverse_and_neutral() {
+    let train = train();
+    let out = drivetrain::run(&train, -1.0, false, &wheels(0.0, [0.0; 2]));
+    assert_eq!(out.gear, -1);
+    assert!(out.top_spin < 0.0);
+    assert!(out.torques[2] > 0.0);
+
+    let idle = drivetrain::run(&train, 0.0, false, &wheels(5.0, [5.0; 2]));
+    assert_eq!(idle.gear, 0);
+    assert!(idle.torques.iter().all(|&t| t == 0.0));
+}
+
+#[test]
+fn axles_and_differentials_split_the_torque() {
+    let mut train = train();
+    // Rear-wheel drive: nothing reaches the front.
+    let out = drivetrain::run(&train, 1.0, false, &wheels(0.0, [0.0; 2]));
+    assert_eq!(&out.torques[..2], &[0.0, 0.0]);
+    assert_eq!(out.torques[2], out.torques[3]);
+
+    // An open differential splits evenly however the wheels turn.
+    let slipping = wheels(0.0, [6.0, 1.0]);
+    let open = drivetrain::run(&train, 1.0, false, &slipping);
+    assert_eq!(open.torques[2], open.torques[3]);
+
+    // A limited-slip one favours the slower wheel by its bias.
+    train.differential = config::Differential::LimitedSlip { bias: 3.0 };
+    let locked = drivetrain::run(&train, 1.0, false, &slipping);
+    assert!((locked.torques[3] - 3.0 * locked.torques[2]).abs() < 1e-4);
+    let total = |out: &drivetrain::Output| out.torques.iter().sum::<f32>();
+    assert!((total(&locked) - total(&open)).abs() < 1e-4);
+
+    // All-wheel drive splits between the axles first.
+    train.front_share = 0.25;
+    let awd = drivetrain::run(&train, 1.0, false, &wheels(0.0, [0.0; 2]));
+    let front = awd.torques[0] + awd.torques[1];
+    assert!((front - 0.25 * total(&awd)).abs() < 1e-4);
+}
+
+#[test]
+fn boost_multiplies_the_engine_torque() {
+    let train = train();
+    let spin = spin_for(&train, 2.0, 3000.0);
+    let plain = drivetrain::run(&train, 1.0, false, &wheels(spin, [spin; 2]));
+    let boosted = drivetrain::run(&train, 1.0, true, &wheels(spin, [spin; 2]));
+    assert_eq!(plain.gear, boosted.gear);
+    for (p, b) in plain.torques.iter().zip(&boosted.torques) {
+        assert!((b - 1.5 * p).abs() < 1e-4);
+    }
+    // Boost adds torque, not speed.
+    assert_eq!(plain.top_spin, boosted.top_spin);
+}
+
+#[test]
+fn production_car_climbs_the_gears() {
+    for kind in KINDS {
+        let (mut physics, terrain) = flat_world();
+        let (mut car, model) = load_car();
+        assert!(car.drivetrain.is_some(), "the production car has an engine");
+        car.kind = kind;
+        let vehicle = place_car(&mut physics, &car, &model, SPAWN_RADIUS);
+
+        let settle = Timeline::default().hold(&[], 1.0);
+        play(&mut physics, &terrain, &vehicle, settle);
+        let flat_out = Timeline::default().hold(&[Key::Forward], 10.0);
+        let mut gears = Vec::new();
+        for step in TimelinePlayer::new(flat_out, physics.dt()) {
+            let gearing = vehicle.gearing(&physics, 1.0, false).unwrap();
+            if gears.last() != Some(&gearing.gear) {
+                gears.push(gearing.gear);
+            }
+            tick(&mut physics, &terrain, &vehicle, &step.input);
+        }
+        println!(
+            "{kind:?} gears: {gears:?}, at {:.2} m/s",
+            vehicle.forward_speed(&physics)
+        );
+        assert_eq!(gears.first(), Some(&1), "{kind:?}");
+        assert!(
+            gears.last().is_some_and(|&gear| gear >= 3),
+            "{kind:?}: never got past {gears:?}"
+        );
+    }
+}
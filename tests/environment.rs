@@ -0,0 +1,148 @@
+//! Headless checks of the environmental forces — gravity and air — on a flat
+//! cylindrical world, using bare balls instead of a car so the numbers stay
+//! easy to reason about.
+
+use rapier3d::dynamics::{RigidBodyBuilder, RigidBodyHandle};
+use rapier3d::geometry::ColliderBuilder;
+use rapier3d::math::{Pose, Vec3};
+use vandals_and_heroes::{Physics, PhysicsBodyHandle, config};
+
+mod common;
+use common::{flat_map, flat_terrain, flat_world};
+
+fn spawn_ball(physics: &mut Physics, pos: Vec3) -> RigidBodyHandle {
+    let rb = RigidBodyBuilder::dynamic()
+        .pose(Pose::from_translation(pos))
+        .build();
+    let collider = ColliderBuilder::ball(0.2).density(1.0).build();
+    let PhysicsBodyHandle {
+        rigid_body_handle, ..
+    } = physics.add_rigid_body(rb, vec![collider]);
+    rigid_body_handle
+}
+
+#[test]
+fn uniform_wind_pushes_a_falling_ball_downwind() {
+    let (mut physics, terrain) = flat_world();
+    let ball = spawn_ball(&mut physics, Vec3::new(0.0, 19.5, 0.0));
+    physics.set_drag_area(ball, 0.47 * std::f32::consts::PI * 0.2 * 0.2);
+    let atmosphere = config::Atmosphere {
+        wind: config::Wind::Uniform {
+            velocity: [0.0, 0.0, 5.0],
+        },
+        ..Default::default()
+    };
+
+    for _ in 0..30 {
+        physics.update_gravity(&terrain);
+        physics.update_aerodynamics(&atmosphere);
+        physics.step();
+    }
+
+    let v = physics.body_linvel(ball);
+    assert!(v.z > 0.05, "wind did not push the ball along +Z: v = {v:?}");
+    assert!(v.z < 5.0, "ball overtook the wind itself: v = {v:?}");
+}
+
+#[test]
+fn drag_slows_a_body_in_still_air() {
+    let (mut physics, terrain) = flat_world();
+    let dragged = spawn_ball(&mut physics, Vec3::new(0.0, 19.5, -10.0));
+    let free = spawn_ball(&mut physics, Vec3::new(0.0, 19.5, 10.0));
+    physics.set_drag_area(dragged, 0.1);
+    physics.set_linvel(dragged, Vec3::new(0.0, 0.0, 20.0));
+    physics.set_linvel(free, Vec3::new(0.0, 0.0, 20.0));
+    let atmosphere = config::Atmosphere::default();
+
+    // Both balls fall identically; only the air tells them apart. Half a
+    // second is not enough for either to reach the ground.
+    for _ in 0..30 {
+        physics.update_gravity(&terrain);
+        physics.update_aerodynamics(&atmosphere);
+        physics.step();
+    }
+
+    let v_dragged = physics.body_linvel(dragged).z;
+    let v_free = physics.body_linvel(free).z;
+    assert!(
+        v_dragged < v_free - 0.5,
+        "drag had no visible effect: dragged {v_dragged:.3} vs free {v_free:.3}"
+    );
+    assert!(v_dragged > 0.0, "drag reversed the motion: {v_dragged:.3}");
+}
+
+#[test]
+fn constant_gravity_is_the_same_at_every_height() {
+    let mut physics = Physics::default();
+    let cfg = config::Map {
+        gravity: config::GravityModel::Constant { accel: 3.0 },
+        ..flat_map()
+    };
+    let terrain = flat_terrain(&mut physics, &cfg);
+
+    for pos in [Vec3::new(0.0, 16.0, 0.0), Vec3::new(-19.0, 0.0, 5.0)] {
+        let g = physics.gravity_at(&terrain, pos);
+        assert!(
+            (g.length() - 3.0).abs() < 1e-4,
+            "|g| = {} at {pos:?}",
+            g.length()
+        );
+        assert!(
+            g.dot(terrain.up(pos)) < -2.99,
+            "gravity at {pos:?} does not point at the axis: {g:?}"
+        );
+    }
+}
+
+#[test]
+fn gravity_zones_scale_and_reverse_gravity() {
+    let mut physics = Physics::default();
+    let cfg = config::Map {
+        gravity: config::GravityModel::Constant { accel: 10.0 },
+        gravity_zones: vec![
+            config::GravityZone {
+                region: config::GravityRegion::Sphere {
+                    center: [0.0, 18.0, 0.0],
+                    radius: 2.5,
+                },
+                scale: -0.5,
+            },
+            config::GravityZone {
+                region: config::GravityRegion::Band { radius: 0.0..12.0 },
+                scale: 0.1,
+            },
+        ],
+        ..flat_map()
+    };
+    let terrain = flat_terrain(&mut physics, &cfg);
+
+    let lift = physics.gravity_at(&terrain, Vec3::new(0.0, 18.0, 0.0));
+    assert!((lift.y - 5.0).abs() < 1e-4, "lift zone: g = {lift:?}");
+    let low = physics.gravity_at(&terrain, Vec3::new(0.0, 11.0, 0.0));
+    assert!((low.y + 1.0).abs() < 1e-4, "low-g band: g = {low:?}");
+    let outside = physics.gravity_at(&terrain, Vec3::new(0.0, -18.0, 0.0));
+    assert!((outside.y - 10.0).abs() < 1e-4, "no zone: g = {outside:?}");
+
+    // A ball released inside the lift drifts away from the ground.
+    let ball = spawn_ball(&mut physics, Vec3::new(0.0, 18.0, 0.0));
+    for _ in 0..30 {
+        physics.update_gravity(&terrain);
+        physics.step();
+    }
+    let v = physics.body_linvel(ball);
+    assert!(v.y > 1.0, "ball in the lift did not rise: v = {v:?}");
+}
+
+#[test]
+fn default_newtonian_gravity_is_capped() {
+    let mut physics = Physics::default();
+    let cfg = config::Map {
+        shape: config::WorldShape::Sphere,
+        ..flat_map()
+    };
+    let terrain = flat_terrain(&mut physics, &cfg);
+
+    // The sphere's inflated gravity mass saturates the cap near the ground.
+    let g = physics.gravity_at(&terrain, Vec3::new(0.0, 15.0, 0.0));
+    assert!((g.length() - 12.0).abs() < 1e-3, "|g| = {}", g.length());
+}
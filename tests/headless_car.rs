@@ -25,6 +25,7 @@ fn build_flat_terrain(physics: &mut Physics) -> TerrainBody {
         length: 100.0,
         density: 10.0,
         shape: config::WorldShape::Cylinder,
+        ..Default::default()
     };
     physics.create_terrain(&cfg, alpha, WIDTH, HEIGHT)
 }
@@ -302,6 +303,7 @@ fn build_flat_torus(physics: &mut Physics) -> (TerrainBody, f32) {
         // ~3 m/s^2 regime the synthetic car's motors are tuned for.
         density: 2.5,
         shape: config::WorldShape::Torus,
+        ..Default::default()
     };
     let terrain = physics.create_terrain(&cfg, alpha, WIDTH, HEIGHT);
     let major_radius = terrain.major_radius;
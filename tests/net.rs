@@ -0,0 +1,180 @@
+//! Multiplayer over loopback: a server and two clients in one process,
+//! talking through real UDP sockets on 127.0.0.1. One player drives, the
+//! other watches; both must see the driver's car where the server has it.
+
+use std::net::UdpSocket;
+use vandals_and_heroes::{
+    DriveInput, Kinematics, Physics, TerrainBody, Vehicle, config,
+    net::{self, PackedBody},
+};
+
+mod common;
+use common::{SPAWN_RADIUS, flat_map, flat_world, load_car, place_car};
+
+fn start_server() -> net::Server {
+    let (physics, terrain) = flat_world();
+    let (car, car_model) = load_car();
+    let map = flat_map();
+    let arena = net::Arena {
+        spawn_radius: SPAWN_RADIUS,
+        map,
+        car,
+        car_model,
+        recovery: config::Recovery::default(),
+    };
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    net::Server::new(socket, physics, terrain, arena).unwrap()
+}
+
+/// A client with its own copy of the world to predict its car in.
+struct Player {
+    client: net::Client,
+    physics: Physics,
+    terrain: TerrainBody,
+    vehicle: Vehicle,
+    /// Position errors `reconcile` reported, in order.
+    errors: Vec<f32>,
+}
+
+fn join(server: &net::Server) -> Player {
+    let (mut physics, terrain) = flat_world();
+    let (car, model) = load_car();
+    // Anywhere will do: the first server update puts the car in its slot.
+    let vehicle = place_car(&mut physics, &car, &model, SPAWN_RADIUS);
+    Player {
+        client: net::Client::connect(server.local_addr().unwrap()).unwrap(),
+        physics,
+        terrain,
+        vehicle,
+        errors: Vec::new(),
+    }
+}
+
+/// One client tick, the way the game runs it.
+fn step(player: &mut Player, input: DriveInput) {
+    player.client.poll().unwrap();
+    if let Some(error) = player
+        .client
+        .reconcile(&mut player.physics, &player.vehicle)
+    {
+        player.errors.push(error);
+    }
+    player.client.send_input(input).unwrap();
+    player.physics.update_gravity(&player.terrain);
+    player
+        .physics
+        .update_aerodynamics(&config::Atmosphere::default());
+    player
+        .vehicle
+        .drive(&mut player.physics, &player.terrain, &input);
+    player.physics.step();
+    player.client.record(&player.physics, &player.vehicle);
+}
+
+fn chassis(physics: &Physics, vehicle: &Vehicle) -> Kinematics {
+    physics.body_kinematics(vehicle.chassis).unwrap()
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f32>()
+        .sqrt()
+}
+
+#[test]
+fn packed_state_round_trips() {
+    let q = nalgebra::UnitQuaternion::from_euler_angles(2.5, -0.4, 1.9);
+    let original = Kinematics {
+        translation: [1.5, -17.25, 40.125],
+        rotation: [q.i, q.j, q.k, q.w],
+        linvel: [3.21, -0.004, 12.5],
+        angvel: [-50.0, 0.3, 0.0],
+    };
+    let unpacked = PackedBody::pack(&original).unpack();
+    assert_eq!(unpacked.translation, original.translation);
+    // q and -q are the same rotation.
+    let dot: f32 = unpacked
+        .rotation
+        .iter()
+        .zip(original.rotation)
+        .map(|(a, b)| a * b)
+        .sum();
+    assert!(dot.abs() > 0.99999, "rotation drifted: dot {dot}");
+    for (a, b) in unpacked
+        .linvel
+        .iter()
+        .chain(&unpacked.angvel)
+        .zip(original.linvel.iter().chain(&original.angvel))
+    {
+        assert!((a - b).abs() <= 0.005, "velocity {b} came back as {a}");
+    }
+}
+
+#[test]
+fn two_clients_follow_the_server() {
+    let mut server = start_server();
+    let mut driver = join(&server);
+    let mut watcher = join(&server);
+    let tick = |server: &mut net::Server, driver: &mut Player, watcher: &mut Player, throttle| {
+        let input = DriveInput {
+            throttle,
+            ..Default::default()
+        };
+        step(driver, input);
+        step(watcher, DriveInput::default());
+        server.tick().unwrap();
+    };
+
+    // Settle onto the ground.
+    for _ in 0..120 {
+        tick(&mut server, &mut driver, &mut watcher, 0.0);
+    }
+    assert_eq!(driver.client.player(), Some(0));
+    assert_eq!(watcher.client.player(), Some(1));
+    assert_eq!(server.player_count(), 2);
+    let server_car = |server: &net::Server, player| {
+        let vehicle = server.vehicle(player).unwrap();
+        chassis(server.physics(), vehicle).translation
+    };
+    let driver_start = server_car(&server, 0);
+    let watcher_start = server_car(&server, 1);
+    let synced_errors = driver.errors.len();
+
+    for _ in 0..180 {
+        tick(&mut server, &mut driver, &mut watcher, 1.0);
+    }
+    let driver_end = server_car(&server, 0);
+    let moved = distance(driver_start, driver_end);
+    assert!(moved > 3.0, "driver moved only {moved:.2} m on the server");
+    let idle = distance(watcher_start, server_car(&server, 1));
+    assert!(idle < 0.5, "idle watcher drifted {idle:.2} m");
+
+    // The driver's prediction keeps up with the server without snapping.
+    let predicted = chassis(&driver.physics, &driver.vehicle).translation;
+    let off = distance(predicted, driver_end);
+    assert!(off < 0.5, "prediction {off:.2} m from the server");
+    let worst = driver.errors[synced_errors..]
+        .iter()
+        .fold(0.0f32, |a, &b| a.max(b));
+    assert!(worst < 1.0, "prediction error reached {worst:.2} m");
+
+    // The watcher sees the driver a few ticks late, interpolated.
+    let seen = watcher.client.remote_cars();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].0, 0);
+    let lag = distance(seen[0].1[0].translation, driver_end);
+    assert!(lag < 1.0, "watcher sees the driver {lag:.2} m away");
+    assert!(distance(seen[0].1[0].translation, driver_start) > 2.0);
+
+    // Leaving takes the car out of the world and out of the watcher's view.
+    driver.client.disconnect().unwrap();
+    for _ in 0..10 {
+        step(&mut watcher, DriveInput::default());
+        server.tick().unwrap();
+    }
+    assert_eq!(server.player_count(), 1);
+    assert!(server.vehicle(0).is_none());
+    assert!(watcher.client.remote_cars().is_empty());
+}
@@ -0,0 +1,169 @@
+//! Respawns on the flat cylinder with the production car: driving off the
+//! end, landing on the roof and wedging against a wall each put it back
+//! upright on the ground it last stood on, invulnerable for a moment.
+
+use rapier3d::dynamics::RigidBodyBuilder;
+use rapier3d::geometry::ColliderBuilder;
+use rapier3d::math::{Pose, Vec3};
+use vandals_and_heroes::{
+    DriveInput, Physics, Recovery, TerrainBody, Trouble, Vehicle, config, vehicle,
+};
+
+mod common;
+use common::{SPAWN_RADIUS, flat_map, flat_world, load_car, place_car};
+
+const TICKS_PER_SECOND: f32 = 60.0;
+
+struct World {
+    physics: Physics,
+    terrain: TerrainBody,
+    map: config::Map,
+    vehicle: Vehicle,
+    recovery: Recovery,
+}
+
+impl World {
+    fn new() -> Self {
+        let (mut physics, terrain) = flat_world();
+        let map = flat_map();
+        let (car, model) = load_car();
+        let vehicle = place_car(&mut physics, &car, &model, SPAWN_RADIUS);
+        let pose = vehicle::spawn_pose(&map, SPAWN_RADIUS, 0.0);
+        let recovery = Recovery::new(config::Recovery::default(), pose.into());
+        Self {
+            physics,
+            terrain,
+            map,
+            vehicle,
+            recovery,
+        }
+    }
+
+    /// One tick of `input`, then the recovery check.
+    fn tick(&mut self, input: &DriveInput) -> Option<Trouble> {
+        self.physics.update_gravity(&self.terrain);
+        self.vehicle.drive(&mut self.physics, &self.terrain, input);
+        self.physics.step();
+        self.recovery.check(
+            &self.physics,
+            &self.terrain,
+            &self.map,
+            &self.vehicle,
+            input,
+        )
+    }
+
+    /// Hold `input` for `seconds`, expecting nothing to go wrong.
+    fn run(&mut self, input: &DriveInput, seconds: f32) {
+        for _ in 0..(seconds * TICKS_PER_SECOND) as usize {
+            assert_eq!(self.tick(input), None);
+        }
+    }
+
+    /// Hold `input` until the car is in trouble; returns what and after
+    /// how many seconds.
+    fn until_trouble(&mut self, input: &DriveInput, give_up: f32) -> (Trouble, f32) {
+        for ticks in 1..=(give_up * TICKS_PER_SECOND) as usize {
+            if let Some(trouble) = self.tick(input) {
+                return (trouble, ticks as f32 / TICKS_PER_SECOND);
+            }
+        }
+        panic!("no trouble within {give_up} s");
+    }
+
+    fn position(&self) -> Vec3 {
+        self.physics
+            .body_pose(self.vehicle.chassis)
+            .unwrap()
+            .translation
+    }
+
+    /// Cosine of the chassis tilt off the local up.
+    fn uprightness(&self) -> f32 {
+        let pose = self.physics.body_pose(self.vehicle.chassis).unwrap();
+        (pose.rotation * Vec3::Y).dot(self.terrain.up(pose.translation))
+    }
+
+    fn respawn(&mut self) {
+        self.recovery
+            .respawn(&mut self.physics, &self.terrain, &self.vehicle);
+    }
+}
+
+fn full_throttle() -> DriveInput {
+    DriveInput {
+        throttle: 1.0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn falling_off_the_end_comes_back() {
+    let mut world = World::new();
+    world.run(&DriveInput::default(), 1.0);
+    world.run(&full_throttle(), 2.0);
+    world.run(&DriveInput::default(), 2.0);
+    let last_ground = world.position();
+
+    let mut pose = world.physics.body_pose(world.vehicle.chassis).unwrap();
+    pose.translation.z = 0.5 * world.map.length + 5.0;
+    world.vehicle.place(&mut world.physics, pose);
+    assert_eq!(
+        world.tick(&DriveInput::default()),
+        Some(Trouble::OutOfBounds)
+    );
+
+    world.respawn();
+    let offset = world.position() - last_ground;
+    assert!(offset.length() < 1.0, "put back {offset:?} away");
+    assert!(world.uprightness() > 0.99);
+    assert!(world.recovery.invulnerable());
+    world.run(&DriveInput::default(), 2.5);
+    assert!(!world.recovery.invulnerable(), "invulnerable for good");
+}
+
+#[test]
+fn upside_down_car_is_righted() {
+    let mut world = World::new();
+    world.run(&DriveInput::default(), 1.0);
+    let position = world.position();
+    let up = world.terrain.up(position);
+    let na = |v: Vec3| nalgebra::Vector3::new(v.x, v.y, v.z);
+    let flipped = vehicle::upright_pose(na(position + up), na(-up), nalgebra::Vector3::z());
+    world.vehicle.place(&mut world.physics, flipped.into());
+
+    let (trouble, after) = world.until_trouble(&DriveInput::default(), 10.0);
+    assert_eq!(trouble, Trouble::Inverted);
+    let wait = config::Recovery::default().inverted_time;
+    assert!(
+        (after - wait).abs() < 0.5,
+        "righted after {after:.2} s, not {wait}"
+    );
+    world.respawn();
+    world.run(&DriveInput::default(), 1.0);
+    assert!(world.uprightness() > 0.9, "{}", world.uprightness());
+}
+
+#[test]
+fn wedged_car_is_freed() {
+    let mut world = World::new();
+    world.run(&DriveInput::default(), 1.0);
+    // A wall across the road a few metres ahead (forward is +Z here).
+    let position = world.position();
+    let wall = RigidBodyBuilder::fixed()
+        .pose(Pose::from_translation(position + Vec3::new(0.0, 0.0, 4.0)))
+        .build();
+    let collider = ColliderBuilder::cuboid(3.0, 2.0, 0.2).build();
+    world.physics.add_rigid_body(wall, vec![collider]);
+
+    let (trouble, after) = world.until_trouble(&full_throttle(), 15.0);
+    assert_eq!(trouble, Trouble::Stuck);
+    let wait = config::Recovery::default().stuck_time;
+    assert!(after > wait, "gave up after {after:.2} s");
+    world.respawn();
+    assert!(world.uprightness() > 0.99);
+    assert!(
+        world.position().z < position.z + 4.0,
+        "respawned through the wall"
+    );
+}
@@ -0,0 +1,126 @@
+//! Save-game round trips: a world written out mid-simulation and read back
+//! must carry on exactly like the one that never stopped.
+//!
+//! The scene exercises the state a save has to carry: a cratered ground, a
+//! small motor-driven vehicle, a body feeling the air, and resting contacts
+//! whose solver caches feed the next step.
+
+use rapier3d::dynamics::{RevoluteJointBuilder, RigidBodyBuilder, RigidBodyHandle};
+use rapier3d::geometry::ColliderBuilder;
+use rapier3d::math::{Pose, Vec3};
+use vandals_and_heroes::{Physics, PhysicsBodyHandle, TerrainBody, config, tin};
+
+mod common;
+use common::{HEIGHT, WIDTH, flat_alpha, flat_map};
+
+fn add_body(physics: &mut Physics, pos: Vec3, radius: f32) -> RigidBodyHandle {
+    let rb = RigidBodyBuilder::dynamic()
+        .pose(Pose::from_translation(pos))
+        .build();
+    let collider = ColliderBuilder::ball(radius)
+        .density(10.0)
+        .friction(2.0)
+        .build();
+    let PhysicsBodyHandle {
+        rigid_body_handle, ..
+    } = physics.add_rigid_body(rb, vec![collider]);
+    rigid_body_handle
+}
+
+/// Build the scene and let it run for a second, so bodies are resting on
+/// the ground and rolling when the save is taken.
+fn build_scene() -> (Physics, TerrainBody, Vec<RigidBodyHandle>) {
+    let mut physics = Physics::default();
+    let map = flat_map();
+    let mut alpha = flat_alpha();
+    let mut mesh = tin::build(&alpha, WIDTH, HEIGHT, &map, 1.0);
+    let mut terrain = physics.create_terrain_mesh(&map, &mesh);
+    let deformation = mesh.dig_crater(&mut alpha, [0.0, 15.0, 3.0], 2.0, 1.0);
+    physics.rebuild_terrain_chunks(&mut terrain, &mesh, &deformation.chunks);
+
+    // A chassis on two motor-driven wheels, facing +Z.
+    let chassis = add_body(&mut physics, Vec3::new(0.0, 15.8, 0.0), 0.2);
+    let mut bodies = vec![chassis];
+    for z in [-0.4, 0.4] {
+        let wheel = add_body(&mut physics, Vec3::new(0.0, 15.3, z), 0.2);
+        let joint = RevoluteJointBuilder::new(Vec3::X)
+            .local_anchor1(Vec3::new(0.0, -0.5, z))
+            .local_anchor2(Vec3::ZERO)
+            .contacts_enabled(false)
+            .motor_max_force(20.0)
+            .build();
+        let joint = physics.add_revolute_joint(chassis, wheel, joint);
+        physics.set_joint_motor_velocity(joint, 4.0, 1.0);
+        bodies.push(wheel);
+    }
+    // A ball rolling toward the crater, slowed by the air.
+    let ball = add_body(&mut physics, Vec3::new(0.0, 15.4, -3.0), 0.3);
+    physics.set_linvel(ball, Vec3::new(0.0, 0.0, 3.0));
+    physics.set_drag_area(ball, 0.2);
+    bodies.push(ball);
+
+    run_ticks(&mut physics, &terrain, 60);
+    (physics, terrain, bodies)
+}
+
+fn run_ticks(physics: &mut Physics, terrain: &TerrainBody, ticks: usize) {
+    let atmosphere = config::Atmosphere {
+        wind: config::Wind::Uniform {
+            velocity: [1.0, 0.0, 0.0],
+        },
+        ..Default::default()
+    };
+    for _ in 0..ticks {
+        physics.update_gravity(terrain);
+        physics.update_aerodynamics(&atmosphere);
+        physics.step();
+    }
+}
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+    let config = bincode::config::standard();
+    let bytes = bincode::serde::encode_to_vec(value, config).expect("serialize");
+    let (value, read) = bincode::serde::decode_from_slice(&bytes, config).expect("deserialize");
+    assert_eq!(read, bytes.len(), "trailing bytes in the save");
+    value
+}
+
+#[test]
+fn loaded_world_continues_like_the_original() {
+    let (mut original, terrain, bodies) = build_scene();
+    let mut loaded: Physics = round_trip(&original);
+    let loaded_terrain: TerrainBody = round_trip(&terrain);
+    assert_eq!(loaded.last_time(), original.last_time());
+
+    run_ticks(&mut original, &terrain, 120);
+    run_ticks(&mut loaded, &loaded_terrain, 120);
+
+    for (i, &body) in bodies.iter().enumerate() {
+        let a = original.body_kinematics(body).unwrap();
+        let b = loaded
+            .body_kinematics(body)
+            .expect("handle lost in the save");
+        assert_eq!(a.translation, b.translation, "body {i} position diverged");
+        assert_eq!(a.rotation, b.rotation, "body {i} rotation diverged");
+        assert_eq!(a.linvel, b.linvel, "body {i} velocity diverged");
+        assert_eq!(a.angvel, b.angvel, "body {i} spin diverged");
+    }
+}
+
+#[test]
+fn loaded_terrain_keeps_its_craters() {
+    let (original, terrain, bodies) = build_scene();
+    let mut loaded: Physics = round_trip(&original);
+    loaded.step();
+
+    let probe = Vec3::new(0.0, 18.0, 3.0);
+    let down = Vec3::new(0.0, -1.0, 0.0);
+    let hit = loaded.cast_ray(probe, down, 10.0, &bodies).unwrap();
+    assert_eq!(hit.body, Some(terrain.body()));
+    // Flat ground sits ~3 m below the probe; the crater dips below that.
+    assert!(
+        hit.distance > 3.3,
+        "crater lost: ground at {:.3} m",
+        hit.distance
+    );
+}
@@ -0,0 +1,89 @@
+//! Steering response on the production car, jointed and raycast: the front
+//! wheels swing toward the input no faster than `steering.rate`, and the
+//! lock narrows with speed as `steering.fade_speed` asks.
+
+use rapier3d::math::Vec3;
+use vandals_and_heroes::{DriveInput, Physics, Timeline, config};
+
+mod common;
+use common::{KINDS, play, spawn_car, tick};
+
+const MAX_STEER_ANGLE: f32 = std::f32::consts::FRAC_PI_4;
+
+/// How closely a steering angle reads back: exactly off a steering joint's
+/// target, but off a raycast wheel's pose only as well as the wheel was
+/// placed ahead of where the chassis ended up.
+fn slack(kind: config::VehicleKind) -> f32 {
+    match kind {
+        config::VehicleKind::Jointed => 1e-5,
+        config::VehicleKind::Raycast => 1e-3,
+    }
+}
+
+#[test]
+fn wheels_swing_at_the_steering_rate() {
+    for kind in KINDS {
+        let (mut physics, terrain, vehicle) = spawn_car(kind);
+        let settle = Timeline::default().hold(&[], 1.0);
+        play(&mut physics, &terrain, &vehicle, settle);
+        let full_right = DriveInput {
+            steer: 1.0,
+            ..Default::default()
+        };
+        let max_swing = vehicle.steering.rate * physics.dt();
+        let mut last = vehicle.steer_angles(&physics);
+        assert_eq!(last.len(), 2);
+        let mut ticks = 0;
+        while last.iter().any(|&angle| angle < 0.95 * MAX_STEER_ANGLE) {
+            tick(&mut physics, &terrain, &vehicle, &full_right);
+            let now = vehicle.steer_angles(&physics);
+            for (&before, &after) in last.iter().zip(&now) {
+                assert!(
+                    after > before,
+                    "{kind:?}: the wheels stopped turning at {after}"
+                );
+                assert!(
+                    after - before <= max_swing + slack(kind),
+                    "{kind:?}: swung {} in a tick, the rate allows {max_swing}",
+                    after - before
+                );
+            }
+            last = now;
+            ticks += 1;
+            assert!(ticks < 120, "{kind:?}: never got near the lock: {last:?}");
+        }
+        // A tap on a key or stick is over before the wheels get far.
+        assert!(ticks > 1, "{kind:?}: reached {last:?} in {ticks} tick(s)");
+    }
+}
+
+#[test]
+fn lock_narrows_with_speed() {
+    for kind in KINDS {
+        let (mut physics, terrain, mut vehicle) = spawn_car(kind);
+        // Take the rate limit out so one tick shows the lock itself.
+        vehicle.steering.rate = f32::MAX;
+        let full_left = DriveInput {
+            steer: -1.0,
+            ..Default::default()
+        };
+        // A raycast wheel only turns as the step moves it.
+        let lock_at = |physics: &mut Physics, speed: f32| {
+            physics.set_linvel(vehicle.chassis, Vec3::new(0.0, 0.0, speed));
+            tick(physics, &terrain, &vehicle, &full_left);
+            vehicle.steer_angles(physics)[0]
+        };
+        let standing = lock_at(&mut physics, 0.0);
+        assert!(
+            (standing + MAX_STEER_ANGLE).abs() < slack(kind),
+            "{kind:?}: {standing}"
+        );
+        let fade_speed = vehicle.steering.fade_speed;
+        assert!(fade_speed > 0.0, "car.ron should fade the steering");
+        let fast = lock_at(&mut physics, fade_speed);
+        assert!(
+            (fast + 0.5 * MAX_STEER_ANGLE).abs() < 1e-4 + slack(kind),
+            "{kind:?}: {fast}"
+        );
+    }
+}
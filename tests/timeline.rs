@@ -0,0 +1,102 @@
+//! Timeline playback: holds last their length in ticks, events land on the
+//! tick after them, looping timelines start over, and the shipped demo
+//! parses.
+
+use vandals_and_heroes::{
+    DriveInput, Timeline, TimelinePlayer,
+    timeline::{Event, Key},
+};
+
+const DT: f32 = 1.0 / 60.0;
+
+#[test]
+fn holds_last_their_length() {
+    let timeline = Timeline::default()
+        .hold(&[], 0.5)
+        .hold(&[Key::Forward, Key::Left], 0.25);
+    let player = TimelinePlayer::new(timeline, DT);
+    assert_eq!(player.duration(), 45);
+    let ticks: Vec<_> = player.collect();
+    assert_eq!(ticks.len(), 45);
+    assert_eq!(ticks[29].input, DriveInput::default());
+    let turning = DriveInput {
+        throttle: 1.0,
+        steer: -1.0,
+        ..Default::default()
+    };
+    assert!(ticks[30..].iter().all(|tick| tick.input == turning));
+    assert!(ticks.iter().all(|tick| tick.events.is_empty()));
+}
+
+#[test]
+fn events_fire_with_the_next_tick() {
+    let jump = Event::Jump { charge: 1.0 };
+    let timeline = Timeline::default()
+        .hold(&[Key::Forward], 0.1)
+        .event(jump)
+        .hold(&[Key::Backward], 0.1)
+        .event(jump);
+    let ticks: Vec<_> = TimelinePlayer::new(timeline, DT).collect();
+    // 6 + 6 ticks of holds, plus one for the trailing jump.
+    assert_eq!(ticks.len(), 13);
+    let fired: Vec<_> = ticks
+        .iter()
+        .enumerate()
+        .filter(|&(_, tick)| !tick.events.is_empty())
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(fired, [6, 12]);
+    assert_eq!(ticks[6].input.throttle, -1.0);
+    assert_eq!(ticks[12].input, DriveInput::default());
+}
+
+#[test]
+fn looping_starts_over() {
+    let mut timeline = Timeline::default()
+        .hold(&[Key::Forward], 0.05)
+        .hold(&[Key::Backward], 0.05);
+    timeline.looping = true;
+    let ticks: Vec<_> = TimelinePlayer::new(timeline, DT).take(12).collect();
+    let forward: Vec<_> = ticks.iter().map(|tick| tick.input.throttle > 0.0).collect();
+    assert_eq!(
+        forward,
+        [
+            true, true, true, false, false, false, true, true, true, false, false, false
+        ]
+    );
+
+    // Nothing to hold: a loop would never produce a tick.
+    let empty = Timeline {
+        steps: Vec::new(),
+        looping: true,
+    };
+    assert_eq!(TimelinePlayer::new(empty, DT).next(), None);
+}
+
+#[test]
+fn rewind_replays_from_the_start() {
+    let timeline = Timeline::default()
+        .event(Event::Jump { charge: 0.0 })
+        .hold(&[Key::Turbo], 0.1);
+    let mut player = TimelinePlayer::new(timeline, DT);
+    let first: Vec<_> = player.by_ref().collect();
+    assert_eq!(player.next(), None);
+    player.rewind();
+    let second: Vec<_> = player.collect();
+    assert_eq!(first, second);
+    assert_eq!(second[0].events.len(), 1);
+}
+
+#[test]
+fn shipped_demos_parse() {
+    for entry in std::fs::read_dir("data/demos").unwrap() {
+        let path = entry.unwrap().path();
+        let timeline: Timeline = ron::de::from_bytes(&std::fs::read(&path).unwrap())
+            .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        assert!(
+            TimelinePlayer::new(timeline, DT).duration() > 0,
+            "{} holds nothing",
+            path.display()
+        );
+    }
+}
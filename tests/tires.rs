@@ -0,0 +1,163 @@
+//! The tyre model: the curves on their own, then the production car, jointed
+//! and raycast, taking the same corner on the flat cylinder with plain
+//! friction and with tyres.
+
+use rapier3d::math::Vec3;
+use vandals_and_heroes::{
+    Physics, Timeline, TimelinePlayer, config,
+    timeline::Key,
+    tires::{self, Slip},
+};
+
+mod common;
+use common::{KINDS, SPAWN_RADIUS, flat_world, load_car, place_car, play, tick};
+
+const TIRES: config::Tires = config::Tires {
+    longitudinal: config::TireCurve::Pacejka {
+        b: 10.0,
+        c: 1.65,
+        d: 1.5,
+        e: 0.5,
+    },
+    lateral: config::TireCurve::Pacejka {
+        b: 6.0,
+        c: 1.3,
+        d: 1.5,
+        e: 0.0,
+    },
+};
+
+#[test]
+fn curves_rise_peak_and_stay_odd() {
+    let brush = config::TireCurve::Brush {
+        stiffness: 20.0,
+        friction: 1.2,
+    };
+    assert!((tires::grip(&brush, 0.01) - 0.2).abs() < 1e-6);
+    assert_eq!(tires::grip(&brush, 0.5), 1.2);
+    assert_eq!(tires::grip(&brush, -0.5), -1.2);
+
+    let curve = TIRES.longitudinal;
+    assert_eq!(tires::grip(&curve, 0.0), 0.0);
+    let samples: Vec<f32> = (0..100)
+        .map(|i| tires::grip(&curve, i as f32 * 0.01))
+        .collect();
+    let max = samples.iter().copied().fold(0.0, f32::max);
+    assert!(max <= tires::peak(&curve) + 1e-5);
+    assert!(max > 0.95 * tires::peak(&curve), "peaks at {max}");
+    // Rises first, then falls away past the peak.
+    assert!(samples[1] < samples[5]);
+    assert!(samples[99] < max);
+    for slip in [0.03, 0.2, 0.7] {
+        assert!((tires::grip(&curve, -slip) + tires::grip(&curve, slip)).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn slip_is_measured_against_ground_speed() {
+    // Rolling freely: no slip.
+    let rolling = Slip::new(5.0, 0.0, 5.0);
+    assert_eq!(
+        rolling,
+        Slip {
+            ratio: 0.0,
+            angle: 0.0
+        }
+    );
+    // Wheelspin, a locked wheel and a drift.
+    assert_eq!(Slip::new(5.0, 0.0, 7.5).ratio, 0.5);
+    assert_eq!(Slip::new(5.0, 0.0, 0.0).ratio, -1.0);
+    let drift = Slip::new(5.0, 5.0, 5.0);
+    assert!((drift.angle - std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+    // At rest slip is taken against `MIN_SPEED`, not zero.
+    assert_eq!(Slip::new(0.0, 0.0, 0.5).ratio, 0.5 / tires::MIN_SPEED);
+}
+
+#[test]
+fn combined_slip_stays_inside_the_ellipse() {
+    let (forward, lateral) = tires::forces(&TIRES, Slip::new(5.0, 0.0, 6.0));
+    assert!(forward > 0.0);
+    assert_eq!(lateral, 0.0);
+    // Drifting right pushes left.
+    let (_, lateral) = tires::forces(&TIRES, Slip::new(5.0, 1.0, 5.0));
+    assert!(lateral < 0.0);
+
+    let (forward, lateral) = tires::forces(&TIRES, Slip::new(5.0, 2.0, 6.0));
+    let x = forward / tires::peak(&TIRES.longitudinal);
+    let y = lateral / tires::peak(&TIRES.lateral);
+    assert!(x * x + y * y <= 1.0 + 1e-5);
+}
+
+/// How a corner went: degrees turned through, and the largest tilt off
+/// the local up along the way.
+#[derive(Debug)]
+struct Corner {
+    turned: f32,
+    max_tilt: f32,
+}
+
+/// Settle, pull away for two seconds, then hold full left lock for three.
+fn take_corner(kind: config::VehicleKind, tires: Option<config::Tires>) -> Corner {
+    let (mut physics, terrain) = flat_world();
+    let (mut car, model) = load_car();
+    car.kind = kind;
+    car.tires = tires;
+    let vehicle = place_car(&mut physics, &car, &model, SPAWN_RADIUS);
+
+    // The chassis nose and the local up.
+    let heading = |physics: &Physics| {
+        let pose = physics.body_pose(vehicle.chassis).unwrap();
+        let up = terrain.up(pose.translation);
+        let nose = pose.rotation * Vec3::NEG_X;
+        let flat = (nose - up * nose.dot(up)).normalize();
+        (flat, up, (pose.rotation * Vec3::Y).angle_between(up))
+    };
+
+    let pull_away = Timeline::default()
+        .hold(&[], 1.0)
+        .hold(&[Key::Forward], 2.0);
+    play(&mut physics, &terrain, &vehicle, pull_away);
+    let turn = Timeline::default().hold(&[Key::Forward, Key::Left], 3.0);
+    let mut corner = Corner {
+        turned: 0.0,
+        max_tilt: 0.0,
+    };
+    let (mut last, _, _) = heading(&physics);
+    for step in TimelinePlayer::new(turn, physics.dt()) {
+        tick(&mut physics, &terrain, &vehicle, &step.input);
+        let (now, up, tilt) = heading(&physics);
+        // Left about the local up is positive.
+        corner.turned += last.cross(now).dot(up).atan2(last.dot(now)).to_degrees();
+        corner.max_tilt = corner.max_tilt.max(tilt.to_degrees());
+        last = now;
+    }
+    corner
+}
+
+#[test]
+fn tyres_corner_like_friction_does() {
+    for kind in KINDS {
+        let friction = take_corner(kind, None);
+        let tyres = take_corner(kind, Some(TIRES));
+        println!("{kind:?}\nfriction: {friction:?}\ntyres: {tyres:?}");
+        for corner in [&friction, &tyres] {
+            assert!(
+                corner.turned.abs() > 30.0,
+                "{kind:?}: barely turned: {corner:?}"
+            );
+            assert!(
+                corner.max_tilt < 60.0,
+                "{kind:?}: nearly rolled: {corner:?}"
+            );
+        }
+        // Same car, same lock: the tyres may grip a little more or less, but
+        // not turn an altogether different line, or the other way.
+        let ratio = tyres.turned / friction.turned;
+        assert!(
+            (0.33..3.0).contains(&ratio),
+            "{kind:?}: tyres turned {:.0}°, friction {:.0}°",
+            tyres.turned,
+            friction.turned
+        );
+    }
+}
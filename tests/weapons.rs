@@ -0,0 +1,149 @@
+//! Headless weapon scenarios on a flat cylinder: beams against a fixed
+//! target, and a cannon shell arcing into the ground and cratering it.
+//! The shooter is a bare fixed body — the weapons only need its pose.
+
+use rapier3d::dynamics::{RigidBodyBuilder, RigidBodyHandle};
+use rapier3d::geometry::ColliderBuilder;
+use rapier3d::math::{Pose, Vec3};
+use vandals_and_heroes::{Damage, Physics, PhysicsBodyHandle, Weapons, config, tin};
+
+mod common;
+use common::{HEIGHT, WIDTH, flat_alpha, flat_terrain};
+
+const DT: f32 = 1.0 / 60.0;
+
+fn flat_map() -> config::Map {
+    config::Map {
+        gravity: config::GravityModel::Constant { accel: 10.0 },
+        ..common::flat_map()
+    }
+}
+
+fn fixed_body(physics: &mut Physics, pos: Vec3, radius: f32) -> RigidBodyHandle {
+    let rb = RigidBodyBuilder::fixed()
+        .pose(Pose::from_translation(pos))
+        .build();
+    let colliders = if radius > 0.0 {
+        vec![ColliderBuilder::ball(radius).build()]
+    } else {
+        Vec::new()
+    };
+    let PhysicsBodyHandle {
+        rigid_body_handle, ..
+    } = physics.add_rigid_body(rb, colliders);
+    rigid_body_handle
+}
+
+fn weapon(kind: config::WeaponKind, direction: [f32; 3]) -> config::Weapon {
+    config::Weapon {
+        position: [0.0, 0.0, 0.0],
+        direction,
+        kind,
+        damage: 30.0,
+        cooldown: 0.25,
+        ammo: Some(2),
+        crater_radius: 1.5,
+        crater_depth: 1.0,
+    }
+}
+
+#[test]
+fn beam_damages_until_the_target_is_wrecked() {
+    let mut physics = Physics::default();
+    let _terrain = flat_terrain(&mut physics, &flat_map());
+    // Ground sits at r ≈ 15; at y = 16 the chord along -X stays airborne.
+    let shooter = fixed_body(&mut physics, Vec3::new(0.0, 16.0, 0.0), 0.0);
+    let target = fixed_body(&mut physics, Vec3::new(-4.0, 16.0, 0.0), 0.5);
+    let beam = weapon(config::WeaponKind::Beam { range: 20.0 }, [-1.0, 0.0, 0.0]);
+    let mut weapons = Weapons::new(&[beam], vec![shooter]);
+    let mut damage = Damage::default();
+    damage.track(&[target], 50.0);
+    // Colliders join the ray-cast structures on the first step.
+    physics.step();
+
+    assert!(weapons.fire(0, &mut physics, shooter));
+    assert!(!weapons.fire(0, &mut physics, shooter), "fired too soon");
+    let impacts = weapons.update(&mut physics, DT);
+    assert_eq!(impacts.len(), 1);
+    assert_eq!(impacts[0].body, Some(target));
+    assert!((impacts[0].point.x + 3.5).abs() < 1e-2, "{:?}", impacts[0]);
+    assert_eq!(damage.apply(&impacts[0]), None);
+    assert_eq!(damage.health(target), Some(20.0));
+
+    let mut wrecked = None;
+    for _ in 0..30 {
+        physics.step();
+        if weapons.fire(0, &mut physics, shooter) {
+            for impact in weapons.update(&mut physics, DT) {
+                wrecked = wrecked.or(damage.apply(&impact));
+            }
+        } else {
+            weapons.update(&mut physics, DT);
+        }
+    }
+    assert_eq!(wrecked, Some(target));
+    assert_eq!(weapons.ammo(0), Some(0));
+    assert!(!weapons.is_ready(0), "an empty mount reports ready");
+}
+
+#[test]
+fn cannon_shell_falls_and_craters_the_ground() {
+    let mut physics = Physics::default();
+    let map = flat_map();
+    let mut alpha = flat_alpha();
+    let mut mesh = tin::build(&alpha, WIDTH, HEIGHT, &map, 1.0);
+    let mut terrain = physics.create_terrain_mesh(&map, &mesh);
+    let shooter = fixed_body(&mut physics, Vec3::new(0.0, 16.0, 0.0), 0.0);
+    let cannon = weapon(
+        config::WeaponKind::Cannon {
+            muzzle_velocity: 10.0,
+            shell_radius: 0.1,
+            shell_mass: 0.5,
+        },
+        [0.0, 0.0, 1.0],
+    );
+    let mut weapons = Weapons::new(&[cannon], vec![shooter]);
+    physics.step();
+
+    assert!(weapons.fire(0, &mut physics, shooter));
+    assert_eq!(weapons.shells().count(), 1);
+    let mut landed = None;
+    for _ in 0..120 {
+        physics.update_gravity(&terrain);
+        physics.step();
+        if let Some(impact) = weapons.update(&mut physics, DT).pop() {
+            landed = Some(impact);
+            break;
+        }
+    }
+    let impact = landed.expect("the shell never landed");
+    assert_eq!(impact.body, Some(terrain.body()));
+    assert_eq!(weapons.shells().count(), 0, "spent shell left behind");
+    // Dropping ~0.9 m at 10 m/s² takes ~0.42 s, ~4.2 m downrange.
+    assert!(
+        (2.5..6.0).contains(&impact.point.z),
+        "shell landed at {:?}",
+        impact.point
+    );
+
+    let probe = Vec3::new(0.0, 18.0, impact.point.z);
+    let down = Vec3::new(0.0, -1.0, 0.0);
+    let before = physics.cast_ray(probe, down, 10.0, &[]).unwrap();
+    let p = impact.point;
+    let deformation = mesh.dig_crater(
+        &mut alpha,
+        [p.x, p.y, p.z],
+        impact.crater_radius,
+        impact.crater_depth,
+    );
+    physics.rebuild_terrain_chunks(&mut terrain, &mesh, &deformation.chunks);
+    physics.step();
+    let after = physics.cast_ray(probe, down, 10.0, &[]).unwrap();
+    assert_eq!(after.body, Some(terrain.body()));
+    assert!(
+        after.distance > before.distance + 0.3,
+        "crater too shallow: ground at {:.3} m, was {:.3} m",
+        after.distance,
+        before.distance
+    );
+}